@@ -0,0 +1,67 @@
+//! Compare loading a database from typed CSV and from the binary format.
+//!
+//! Run with `cargo run --release --example binary_benchmark`.
+
+use std::time::{Duration, Instant};
+
+use rust_modules::tinycsv_db::*;
+
+const ROWS: i64 = 200_000;
+
+fn build() -> Database {
+    let mut db = Database::new(Schema::from_columns(vec![
+        Column::new("id", DataType::Integer).primary_key(),
+        Column::new("name", DataType::Text),
+        Column::new("score", DataType::Float).nullable(),
+        Column::new("active", DataType::Boolean),
+    ]));
+    for id in 0..ROWS {
+        let score = match id % 10 {
+            0 => Value::Null,
+            _ => Value::Float(id as f64 / 7.0),
+        };
+        let row = Row::new(vec![
+            Value::Integer(id),
+            Value::Text(format!("user {}, \"{}\"", id, id % 97)),
+            score,
+            Value::Boolean(id % 3 == 0),
+        ]);
+        insert(&mut db, row).unwrap();
+    }
+    db
+}
+
+/// Run `load` a few times and return the fastest time.
+fn time(mut load: impl FnMut() -> Database) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            let db = load();
+            let elapsed = start.elapsed();
+            assert_eq!(db.get_rows().len(), ROWS as usize);
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let db = build();
+    let csv = to_csv_typed(&db);
+    let bytes = db.to_bytes();
+    println!(
+        "{} rows: CSV {} bytes, binary {} bytes",
+        ROWS,
+        csv.len(),
+        bytes.len()
+    );
+
+    let from_csv = time(|| Database::from_csv(&csv).unwrap());
+    let from_bytes = time(|| Database::from_bytes(&bytes).unwrap());
+    println!(
+        "load CSV {:>10.2?}  binary {:>10.2?}  speedup {:>5.1}x",
+        from_csv,
+        from_bytes,
+        from_csv.as_secs_f64() / from_bytes.as_secs_f64().max(1e-9)
+    );
+}
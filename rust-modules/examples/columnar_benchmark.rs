@@ -0,0 +1,77 @@
+//! Compare a filtered aggregate over the rows of a database and over its columnar copy.
+//!
+//! Run with `cargo run --release --example columnar_benchmark`.
+
+use std::time::{Duration, Instant};
+
+use rust_modules::tinycsv_db::*;
+
+const ROWS: i64 = 500_000;
+const REGIONS: [&str; 4] = ["north", "south", "east", "west"];
+
+fn build() -> Database {
+    let mut db = Database::new(Schema::from_columns(vec![
+        Column::new("id", DataType::Integer).primary_key(),
+        Column::new("region", DataType::Text),
+        Column::new("amount", DataType::Float).nullable(),
+        Column::new("refunded", DataType::Boolean),
+    ]));
+    for id in 0..ROWS {
+        let amount = match id % 10 {
+            0 => Value::Null,
+            _ => Value::Float((id % 1000) as f64 / 4.0),
+        };
+        let row = Row::new(vec![
+            Value::Integer(id),
+            Value::Text(REGIONS[id as usize % REGIONS.len()].to_string()),
+            amount,
+            Value::Boolean(id % 7 == 0),
+        ]);
+        insert(&mut db, row).unwrap();
+    }
+    db
+}
+
+/// Run `scan` a few times and return the fastest time with its result.
+fn time(mut scan: impl FnMut() -> Value) -> (Duration, Value) {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            let value = scan();
+            (start.elapsed(), value)
+        })
+        .min_by_key(|(elapsed, _)| *elapsed)
+        .unwrap()
+}
+
+fn main() {
+    let db = build();
+    let start = Instant::now();
+    let table = db.to_columnar();
+    println!("{} rows, converted in {:.2?}", ROWS, start.elapsed());
+
+    let filter = Filter::eq("region", Value::Text("east".to_string()))
+        .and(Filter::eq("refunded", Value::Boolean(false)))
+        .and(Filter::gt("amount", Value::Float(100.0)));
+    let sum = Aggregate::sum("amount");
+
+    let (rows, expected) = time(|| {
+        let grouped = (db.group_by(&[]).filter(filter.clone()))
+            .aggregate(sum.clone())
+            .execute()
+            .unwrap();
+        grouped.get_rows()[0].get_values()[0].clone()
+    });
+    let (columns, found) = time(|| {
+        let selection = table.filter(&filter).unwrap();
+        table.aggregate(&sum, Some(&selection)).unwrap()
+    });
+    assert_eq!(found, expected);
+
+    println!(
+        "filtered sum: rows {:>10.2?}  columns {:>10.2?}  speedup {:>5.1}x",
+        rows,
+        columns,
+        rows.as_secs_f64() / columns.as_secs_f64().max(1e-9)
+    );
+}
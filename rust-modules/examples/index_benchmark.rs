@@ -0,0 +1,73 @@
+//! Compare lookups with and without secondary indexes on a million rows.
+//!
+//! Run with `cargo run --release --example index_benchmark`.
+
+use std::time::{Duration, Instant};
+
+use rust_modules::tinycsv_db::*;
+
+const ROWS: i64 = 1_000_000;
+const LOOKUPS: i64 = 100;
+
+fn build() -> Database {
+    let mut db = Database::new(Schema::from_columns(vec![
+        Column::new("id", DataType::Integer),
+        Column::new("bucket", DataType::Integer),
+        Column::new("name", DataType::Text),
+    ]));
+    for id in 0..ROWS {
+        let row = Row::new(vec![
+            Value::Integer(id),
+            Value::Integer(id * 7919 % 10_007),
+            Value::Text(format!("user{}", id)),
+        ]);
+        insert(&mut db, row).unwrap();
+    }
+    db
+}
+
+/// Run `lookup` for every probe and return the total time and number of rows found.
+fn time(mut lookup: impl FnMut(i64) -> usize) -> (Duration, usize) {
+    let start = Instant::now();
+    let found = (0..LOOKUPS).map(|probe| lookup(probe * 9_973 % ROWS)).sum();
+    (start.elapsed(), found)
+}
+
+fn report(name: &str, scan: (Duration, usize), indexed: (Duration, usize)) {
+    assert_eq!(scan.1, indexed.1, "{}: index and scan disagree", name);
+    println!(
+        "{:<28} scan {:>10.2?}  indexed {:>10.2?}  speedup {:>8.0}x",
+        name,
+        scan.0,
+        indexed.0,
+        scan.0.as_secs_f64() / indexed.0.as_secs_f64().max(1e-9)
+    );
+}
+
+fn main() {
+    let mut db = build();
+    println!("{} rows, {} lookups each", ROWS, LOOKUPS);
+
+    let exact =
+        |db: &Database| time(|probe| find_exact(db, "id", &Value::Integer(probe)).unwrap().len());
+    let range = |db: &Database| {
+        time(|probe| {
+            let filter = Filter::between(
+                "bucket",
+                Value::Integer(probe % 10_000),
+                Value::Integer(probe % 10_000 + 5),
+            );
+            find_where(db, &filter).unwrap().len()
+        })
+    };
+
+    let scan = (exact(&db), range(&db));
+
+    let start = Instant::now();
+    db.create_index("id", IndexKind::Hash).unwrap();
+    db.create_index("bucket", IndexKind::BTree).unwrap();
+    println!("building both indexes took {:.2?}", start.elapsed());
+
+    report("find_exact (hash index)", scan.0, exact(&db));
+    report("BETWEEN (B-tree index)", scan.1, range(&db));
+}
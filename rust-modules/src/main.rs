@@ -21,7 +21,8 @@ fn main() {
             Value::Float(95.5),
             Value::Boolean(true),
         ]),
-    );
+    )
+    .unwrap();
 
     // Get the rows from the database
     let rows = db.get_rows();
@@ -33,11 +34,11 @@ fn main() {
     assert_eq!(row.get_value(3), Some(&Value::Boolean(true)));
 
     // Find rows with an exact match for the value "Alice" in the "name" column
-    let ids = find_exact(&db, "name", &Value::Text("Alice".to_string()));
+    let ids = find_exact(&db, "name", &Value::Text("Alice".to_string())).unwrap();
     assert_eq!(ids.len(), 1);
 
     // Find rows containing the text "lic" in the "name" column
-    let contains = find_contains(&db, "name", "lic");
+    let contains = find_contains(&db, "name", "lic").unwrap();
     assert_eq!(contains.len(), 1);
 
     // Convert the database to a CSV string
@@ -45,7 +46,7 @@ fn main() {
     assert_eq!(csv, "id,name,score,active\n1,Alice,95.5,true\n");
 
     // Restore the database from the CSV string
-    let db2 = Database::from_csv(&csv);
+    let db2 = Database::from_csv(&csv).unwrap();
     let rows = db2.get_rows();
     let row = &rows[0];
     assert_eq!(rows.len(), 1);
@@ -55,7 +56,7 @@ fn main() {
     assert_eq!(row.get_value(3), Some(&Value::Boolean(true)));
 
     // Delete the row
-    delete(&mut db, |row| row.get_value(0) == Some(&Value::Integer(1)));
+    delete(&mut db, |row| row.get_value(0) == Some(&Value::Integer(1))).unwrap();
     assert_eq!(db.get_rows().len(), 0);
 
     println!("All tests passed!");
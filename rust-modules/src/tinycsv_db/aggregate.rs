@@ -0,0 +1,648 @@
+use std::collections::{HashMap, HashSet};
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::filter::Filter;
+use super::index::Key;
+use super::row::Row;
+use super::schema::{Column, Schema};
+use super::types::{DataType, Value};
+
+/// The function computed by an `Aggregate`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AggregateFn {
+    Count,
+    CountDistinct,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFn {
+    fn name(&self) -> &'static str {
+        match self {
+            AggregateFn::Count | AggregateFn::CountDistinct => "count",
+            AggregateFn::Sum => "sum",
+            AggregateFn::Avg => "avg",
+            AggregateFn::Min => "min",
+            AggregateFn::Max => "max",
+        }
+    }
+}
+
+/// An aggregate over the rows of a group, such as `SUM(score)`.
+///
+/// Every function except `COUNT(*)` ignores `Value::Null`. The result column is named like
+/// the SQL expression in lower case (`count(*)`, `count(distinct name)`, `sum(score)`)
+/// unless it is given an alias.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Aggregate {
+    pub(crate) function: AggregateFn,
+    pub(crate) column: Option<String>,
+    pub(crate) alias: Option<String>,
+}
+
+impl Aggregate {
+    pub(crate) fn new(function: AggregateFn, column: Option<&str>) -> Self {
+        Aggregate {
+            function,
+            column: column.map(str::to_string),
+            alias: None,
+        }
+    }
+
+    /// `COUNT(*)`: the number of rows in the group.
+    pub fn count_all() -> Self {
+        Aggregate::new(AggregateFn::Count, None)
+    }
+
+    /// `COUNT(column)`: the number of non-null values.
+    pub fn count(column: &str) -> Self {
+        Aggregate::new(AggregateFn::Count, Some(column))
+    }
+
+    /// `COUNT(DISTINCT column)`: the number of different non-null values.
+    pub fn count_distinct(column: &str) -> Self {
+        Aggregate::new(AggregateFn::CountDistinct, Some(column))
+    }
+
+    /// `SUM(column)` of an integer or float column; `NULL` if there are no values.
+    pub fn sum(column: &str) -> Self {
+        Aggregate::new(AggregateFn::Sum, Some(column))
+    }
+
+    /// `AVG(column)` of an integer or float column, as a float; `NULL` if there are no values.
+    pub fn avg(column: &str) -> Self {
+        Aggregate::new(AggregateFn::Avg, Some(column))
+    }
+
+    /// `MIN(column)`, ordered like `Value::total_cmp`; `NULL` if there are no values.
+    pub fn min(column: &str) -> Self {
+        Aggregate::new(AggregateFn::Min, Some(column))
+    }
+
+    /// `MAX(column)`, ordered like `Value::total_cmp`; `NULL` if there are no values.
+    pub fn max(column: &str) -> Self {
+        Aggregate::new(AggregateFn::Max, Some(column))
+    }
+
+    /// Name the result column.
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    /// The name of the result column.
+    pub fn get_name(&self) -> String {
+        if let Some(alias) = &self.alias {
+            return alias.clone();
+        }
+        match (self.function, &self.column) {
+            (AggregateFn::CountDistinct, Some(column)) => format!("count(distinct {})", column),
+            (function, Some(column)) => format!("{}({})", function.name(), column),
+            (function, None) => format!("{}(*)", function.name()),
+        }
+    }
+}
+
+/// A grouping of the rows of a database, built step by step and run with `GroupBy::execute`.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let db = Database::from_csv("id,name,score\n1,Alice,95.5\n2,Bob,80\n3,Alice,70\n").unwrap();
+/// let totals = db
+///     .group_by(&["name"])
+///     .aggregate(Aggregate::count_all().alias("n"))
+///     .aggregate(Aggregate::avg("score"))
+///     .having(Filter::gt("n", Value::Integer(1)))
+///     .execute()
+///     .unwrap();
+///
+/// assert_eq!(
+///     totals.get_rows()[0].get_values(),
+///     &vec![Value::Text("Alice".to_string()), Value::Integer(2), Value::Float(82.75)]
+/// );
+/// ```
+pub struct GroupBy<'a> {
+    db: &'a Database,
+    columns: Vec<String>,
+    aggregates: Vec<Aggregate>,
+    filters: Vec<Filter>,
+    having: Vec<Filter>,
+}
+
+impl Database {
+    /// Start grouping rows by the given columns; with no columns, all rows form one group.
+    pub fn group_by(&self, columns: &[&str]) -> GroupBy<'_> {
+        GroupBy {
+            db: self,
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            aggregates: Vec::new(),
+            filters: Vec::new(),
+            having: Vec::new(),
+        }
+    }
+}
+
+impl<'a> GroupBy<'a> {
+    /// Add an aggregate column to the result.
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregates.push(aggregate);
+        self
+    }
+
+    /// Keep only rows matching the filter before grouping, like SQL `WHERE`.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Keep only groups matching the filter, like SQL `HAVING`.
+    ///
+    /// The filter refers to the result columns: the grouped columns and the aggregate names.
+    pub fn having(mut self, filter: Filter) -> Self {
+        self.having.push(filter);
+        self
+    }
+
+    /// Run the grouping.
+    ///
+    /// Groups appear in the order of their first row. Rows whose grouped values are all
+    /// `Value::Null` form one group. Without grouped columns there is exactly one group,
+    /// even for an empty database.
+    ///
+    /// # Returns
+    /// A new database with one row per group: the grouped columns, keeping their type and
+    /// nullability, followed by the aggregates. Counts are NOT NULL integers, averages are
+    /// floats and the other aggregates have the type of their column.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if a column does not exist, `Error::TypeMismatch` if
+    /// `SUM` or `AVG` is used on a non-numeric column or a filter value cannot be compared
+    /// with its column, and `Error::Overflow` if an integer sum does not fit in an `i64`.
+    pub fn execute(&self) -> Result<Database> {
+        let source = &self.db.schema;
+        let keys = self
+            .columns
+            .iter()
+            .map(|column| source.column_index(column))
+            .collect::<Result<Vec<_>>>()?;
+        let inputs = self
+            .aggregates
+            .iter()
+            .map(|aggregate| {
+                aggregate
+                    .column
+                    .as_ref()
+                    .map(|column| source.column_index(column))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicates = self
+            .filters
+            .iter()
+            .map(|filter| filter.compile(source))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut columns: Vec<Column> = keys
+            .iter()
+            .map(|&index| {
+                let column = &source.columns[index];
+                let result = Column::new(&column.name, column.data_type);
+                match column.nullable {
+                    true => result.nullable(),
+                    false => result,
+                }
+            })
+            .collect();
+        for (aggregate, input) in self.aggregates.iter().zip(&inputs) {
+            columns.push(result_column(
+                aggregate,
+                input.map(|index| &source.columns[index]),
+            )?);
+        }
+
+        let mut positions: HashMap<Vec<Option<Key>>, usize> = HashMap::new();
+        let mut groups: Vec<(Vec<Value>, Vec<Accumulator>)> = Vec::new();
+        let new_group = |row: Option<&Row>| {
+            let values = match row {
+                Some(row) => keys
+                    .iter()
+                    .map(|&index| row.values[index].clone())
+                    .collect(),
+                None => Vec::new(),
+            };
+            let accumulators = self
+                .aggregates
+                .iter()
+                .map(|aggregate| Accumulator::new(aggregate.function))
+                .collect();
+            (values, accumulators)
+        };
+
+        for row in &self.db.rows {
+            if !predicates.iter().all(|matches| matches(row)) {
+                continue;
+            }
+            let key = keys
+                .iter()
+                .map(|&index| Key::new(&row.values[index]))
+                .collect();
+            let position = *positions.entry(key).or_insert_with(|| {
+                groups.push(new_group(Some(row)));
+                groups.len() - 1
+            });
+
+            for ((accumulator, input), aggregate) in groups[position]
+                .1
+                .iter_mut()
+                .zip(&inputs)
+                .zip(&self.aggregates)
+            {
+                accumulator.add(input.map(|index| &row.values[index]), aggregate)?;
+            }
+        }
+
+        if keys.is_empty() && groups.is_empty() {
+            groups.push(new_group(None));
+        }
+
+        let mut db = Database::new(Schema::from_columns(columns));
+        db.rows = groups
+            .into_iter()
+            .map(|(mut values, accumulators)| {
+                values.extend(accumulators.into_iter().map(Accumulator::finish));
+                Row { values }
+            })
+            .collect();
+
+        let having = self
+            .having
+            .iter()
+            .map(|filter| filter.compile(&db.schema))
+            .collect::<Result<Vec<_>>>()?;
+        db.rows
+            .retain(|row| having.iter().all(|matches| matches(row)));
+
+        Ok(db)
+    }
+}
+
+/// The result column of an aggregate over the given input column.
+fn result_column(aggregate: &Aggregate, input: Option<&Column>) -> Result<Column> {
+    let name = aggregate.get_name();
+    let Some(input) = input else {
+        return Ok(Column::new(&name, DataType::Integer));
+    };
+
+    let data_type = match aggregate.function {
+        AggregateFn::Count | AggregateFn::CountDistinct => {
+            return Ok(Column::new(&name, DataType::Integer));
+        }
+        AggregateFn::Sum | AggregateFn::Avg
+            if !matches!(input.data_type, DataType::Integer | DataType::Float) =>
+        {
+            return Err(Error::TypeMismatch {
+                column: input.name.clone(),
+                expected: DataType::Float,
+                found: input.data_type,
+            });
+        }
+        AggregateFn::Avg => DataType::Float,
+        AggregateFn::Sum | AggregateFn::Min | AggregateFn::Max => input.data_type,
+    };
+
+    Ok(Column::new(&name, data_type).nullable())
+}
+
+/// The running state of one aggregate over one group.
+enum Accumulator {
+    Count(i64),
+    Distinct(HashSet<Key>),
+    Sum(Option<Value>),
+    Avg { sum: f64, count: i64 },
+    Min(Option<Value>),
+    Max(Option<Value>),
+}
+
+impl Accumulator {
+    fn new(function: AggregateFn) -> Self {
+        match function {
+            AggregateFn::Count => Accumulator::Count(0),
+            AggregateFn::CountDistinct => Accumulator::Distinct(HashSet::new()),
+            AggregateFn::Sum => Accumulator::Sum(None),
+            AggregateFn::Avg => Accumulator::Avg { sum: 0.0, count: 0 },
+            AggregateFn::Min => Accumulator::Min(None),
+            AggregateFn::Max => Accumulator::Max(None),
+        }
+    }
+
+    /// Add the input value of a row; `None` for `COUNT(*)`, which has no input column.
+    fn add(&mut self, value: Option<&Value>, aggregate: &Aggregate) -> Result<()> {
+        let value = match value {
+            Some(Value::Null) => return Ok(()),
+            Some(value) => value,
+            None => {
+                if let Accumulator::Count(count) = self {
+                    *count += 1;
+                }
+                return Ok(());
+            }
+        };
+
+        match self {
+            Accumulator::Count(count) => *count += 1,
+            Accumulator::Distinct(keys) => {
+                keys.extend(Key::new(value));
+            }
+            Accumulator::Sum(sum) => {
+                *sum = Some(match (sum.take(), value) {
+                    (None, value) => value.clone(),
+                    (Some(Value::Integer(a)), Value::Integer(b)) => {
+                        let column = aggregate.column.clone().unwrap_or_default();
+                        Value::Integer(a.checked_add(*b).ok_or(Error::Overflow(column))?)
+                    }
+                    (Some(a), b) => Value::Float(as_f64(&a) + as_f64(b)),
+                });
+            }
+            Accumulator::Avg { sum, count } => {
+                *sum += as_f64(value);
+                *count += 1;
+            }
+            Accumulator::Min(min) => {
+                if min.as_ref().is_none_or(|min| value.total_cmp(min).is_lt()) {
+                    *min = Some(value.clone());
+                }
+            }
+            Accumulator::Max(max) => {
+                if max.as_ref().is_none_or(|max| value.total_cmp(max).is_gt()) {
+                    *max = Some(value.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        match self {
+            Accumulator::Count(count) => Value::Integer(count),
+            Accumulator::Distinct(keys) => Value::Integer(keys.len() as i64),
+            Accumulator::Avg { count: 0, .. } => Value::Null,
+            Accumulator::Avg { sum, count } => Value::Float(sum / count as f64),
+            Accumulator::Sum(value) | Accumulator::Min(value) | Accumulator::Max(value) => {
+                value.unwrap_or(Value::Null)
+            }
+        }
+    }
+}
+
+fn as_f64(value: &Value) -> f64 {
+    match value {
+        Value::Integer(num) => *num as f64,
+        Value::Float(num) => *num,
+        _ => f64::NAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::operations::insert;
+
+    fn sales() -> Database {
+        let rows = [
+            ("north", "apple", 10, Some(1.5)),
+            ("south", "apple", 4, Some(1.0)),
+            ("north", "pear", 6, None),
+            ("north", "apple", 2, Some(2.5)),
+            ("", "plum", 1, Some(3.0)),
+        ];
+        let rows = (rows.into_iter())
+            .map(|(region, product, units, price)| {
+                let region = match region {
+                    "" => Value::Null,
+                    region => text(region),
+                };
+                let price = price.map_or(Value::Null, Value::Float);
+                vec![region, text(product), Value::Integer(units), price]
+            })
+            .collect();
+        table(
+            vec![
+                Column::new("region", DataType::Text).nullable(),
+                Column::new("product", DataType::Text),
+                Column::new("units", DataType::Integer),
+                Column::new("price", DataType::Float).nullable(),
+            ],
+            rows,
+        )
+    }
+
+    #[test]
+    fn test_group_by() {
+        let db = sales();
+        let result = db
+            .group_by(&["region"])
+            .aggregate(Aggregate::count_all())
+            .aggregate(Aggregate::count("price"))
+            .aggregate(Aggregate::count_distinct("product"))
+            .aggregate(Aggregate::sum("units"))
+            .aggregate(Aggregate::avg("price"))
+            .aggregate(Aggregate::min("product"))
+            .aggregate(Aggregate::max("price"))
+            .execute()
+            .unwrap();
+
+        let names: Vec<&str> = result
+            .get_schema()
+            .get_columns()
+            .iter()
+            .map(Column::get_name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "region",
+                "count(*)",
+                "count(price)",
+                "count(distinct product)",
+                "sum(units)",
+                "avg(price)",
+                "min(product)",
+                "max(price)",
+            ]
+        );
+
+        let rows: Vec<&Vec<Value>> = result.get_rows().iter().map(Row::get_values).collect();
+        assert_eq!(
+            rows,
+            vec![
+                &vec![
+                    text("north"),
+                    Value::Integer(3),
+                    Value::Integer(2),
+                    Value::Integer(2),
+                    Value::Integer(18),
+                    Value::Float(2.0),
+                    text("apple"),
+                    Value::Float(2.5),
+                ],
+                &vec![
+                    text("south"),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Integer(4),
+                    Value::Float(1.0),
+                    text("apple"),
+                    Value::Float(1.0),
+                ],
+                &vec![
+                    Value::Null,
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Float(3.0),
+                    text("plum"),
+                    Value::Float(3.0),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inferred_schema() {
+        let db = sales();
+        let result = db
+            .group_by(&["product"])
+            .aggregate(Aggregate::count_all().alias("n"))
+            .aggregate(Aggregate::sum("price"))
+            .aggregate(Aggregate::avg("units"))
+            .execute()
+            .unwrap();
+
+        let columns: Vec<(&str, DataType, bool)> = result
+            .get_schema()
+            .get_columns()
+            .iter()
+            .map(|column| {
+                (
+                    column.get_name(),
+                    column.get_data_type(),
+                    column.is_nullable(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("product", DataType::Text, false),
+                ("n", DataType::Integer, false),
+                ("sum(price)", DataType::Float, true),
+                ("avg(units)", DataType::Float, true),
+            ]
+        );
+        assert_eq!(result.get_rows()[1].get_values()[2], Value::Null);
+    }
+
+    #[test]
+    fn test_filter_and_having() {
+        let db = sales();
+        let result = db
+            .group_by(&["region", "product"])
+            .aggregate(Aggregate::sum("units").alias("units"))
+            .filter(Filter::ge("units", Value::Integer(2)))
+            .having(Filter::gt("units", Value::Integer(5)))
+            .execute()
+            .unwrap();
+
+        let rows: Vec<&Vec<Value>> = result.get_rows().iter().map(Row::get_values).collect();
+        assert_eq!(
+            rows,
+            vec![
+                &vec![text("north"), text("apple"), Value::Integer(12)],
+                &vec![text("north"), text("pear"), Value::Integer(6)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_without_groups() {
+        let mut db = sales();
+        let total = || {
+            db.group_by(&[])
+                .aggregate(Aggregate::count_all())
+                .aggregate(Aggregate::sum("units"))
+                .filter(Filter::eq("product", text("apple")))
+                .execute()
+                .unwrap()
+                .get_rows()[0]
+                .get_values()
+                .clone()
+        };
+        assert_eq!(total(), vec![Value::Integer(3), Value::Integer(16)]);
+
+        db = Database::new(db.get_schema().clone());
+        let empty = db
+            .group_by(&[])
+            .aggregate(Aggregate::count_all())
+            .aggregate(Aggregate::max("units"))
+            .execute()
+            .unwrap();
+        assert_eq!(
+            empty.get_rows()[0].get_values(),
+            &vec![Value::Integer(0), Value::Null]
+        );
+        assert!(
+            db.group_by(&["region"])
+                .aggregate(Aggregate::count_all())
+                .execute()
+                .unwrap()
+                .get_rows()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut db = sales();
+
+        assert!(matches!(
+            db.group_by(&["city"]).execute(),
+            Err(Error::UnknownColumn(_))
+        ));
+        assert!(matches!(
+            db.group_by(&[])
+                .aggregate(Aggregate::avg("product"))
+                .execute(),
+            Err(Error::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            db.group_by(&[])
+                .aggregate(Aggregate::count_all())
+                .having(Filter::gt("units", Value::Integer(1)))
+                .execute(),
+            Err(Error::UnknownColumn(_))
+        ));
+
+        insert(
+            &mut db,
+            Row::new(vec![
+                Value::Null,
+                text("fig"),
+                Value::Integer(i64::MAX),
+                Value::Null,
+            ]),
+        )
+        .unwrap();
+        assert!(matches!(
+            db.group_by(&[]).aggregate(Aggregate::sum("units")).execute(),
+            Err(Error::Overflow(column)) if column == "units"
+        ));
+    }
+}
@@ -0,0 +1,323 @@
+use std::num::NonZeroUsize;
+
+use super::codec::{Reader, crc32, put_str, put_type, put_u32, put_u64, put_value};
+use super::database::Database;
+use super::error::{Error, Result};
+use super::operations::load_rows;
+use super::row::Row;
+use super::schema::{Column, Schema};
+
+const MAGIC: &[u8; 4] = b"TCDB";
+const VERSION: u32 = 1;
+
+const NULLABLE: u8 = 1;
+const PRIMARY_KEY: u8 = 2;
+const UNIQUE: u8 = 4;
+const DEFAULT: u8 = 8;
+
+impl Database {
+    /// Encode the database in a compact binary format, read back by `Database::from_bytes`.
+    ///
+    /// The format starts with the magic bytes `TCDB` and a format version, followed by the
+    /// schema (names, types, nullability, primary key, unique and default of the columns)
+    /// and the rows, each value as a type tag and its little-endian bytes, with text
+    /// prefixed by its length. A CRC-32 checksum of everything before it ends the data.
+    /// Floats keep their exact bits, including the sign of zero and NaN payloads.
+    ///
+    /// CHECK constraints are closures and are not stored, and neither are indexes.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_modules::tinycsv_db::*;
+    ///
+    /// let db = Database::from_csv("id:int,ratio:float?\n1,0.1\n2,\n").unwrap();
+    /// let restored = Database::from_bytes(&db.to_bytes()).unwrap();
+    ///
+    /// assert_eq!(restored.get_rows(), db.get_rows());
+    /// assert!(restored.get_schema().get_columns()[1].is_nullable());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        put_u32(&mut bytes, VERSION);
+
+        put_u32(&mut bytes, self.schema.columns.len() as u32);
+        for column in &self.schema.columns {
+            put_str(&mut bytes, &column.name);
+            put_type(&mut bytes, column.data_type);
+            let flags = [
+                (column.nullable, NULLABLE),
+                (column.primary_key, PRIMARY_KEY),
+                (column.unique, UNIQUE),
+                (column.default.is_some(), DEFAULT),
+            ];
+            bytes.push(
+                flags
+                    .iter()
+                    .filter(|(set, _)| *set)
+                    .map(|(_, flag)| flag)
+                    .sum(),
+            );
+            if let Some(default) = &column.default {
+                put_value(&mut bytes, default);
+            }
+        }
+
+        put_u64(&mut bytes, self.rows.len() as u64);
+        for row in &self.rows {
+            for value in &row.values {
+                put_value(&mut bytes, value);
+            }
+        }
+
+        let checksum = crc32(&bytes);
+        put_u32(&mut bytes, checksum);
+        bytes
+    }
+
+    /// Decode a database written by `Database::to_bytes`.
+    ///
+    /// The rows are taken as they were stored: column defaults are not applied to their
+    /// null values.
+    ///
+    /// # Errors
+    /// Returns `Error::Corrupt` if the bytes are not in the binary format, were written by
+    /// an unsupported version, fail the checksum or hold a value that does not fit its
+    /// column, and `Error::UniqueViolation` if a unique column repeats a value.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Database> {
+        let corrupt = |message: &str| Error::Corrupt(message.to_string());
+
+        if bytes.len() < MAGIC.len() + 8 || !bytes.starts_with(MAGIC) {
+            return Err(corrupt("not a tinycsv_db binary file"));
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 4);
+        let mut reader = Reader::new(body);
+        reader.position = MAGIC.len();
+
+        let version = reader.u32().ok_or_else(|| corrupt("truncated data"))?;
+        if version != VERSION {
+            return Err(Error::Corrupt(format!(
+                "unsupported binary format version {}",
+                version
+            )));
+        }
+        if crc32(body).to_le_bytes() != checksum {
+            return Err(corrupt("checksum mismatch"));
+        }
+
+        let (schema, rows) = read_body(&mut reader).ok_or_else(|| corrupt("invalid data"))?;
+        if !reader.is_empty() {
+            return Err(corrupt("trailing data"));
+        }
+
+        let mut db = Database::new(schema);
+        load_rows(&mut db, rows)?;
+        Ok(db)
+    }
+}
+
+fn read_body(reader: &mut Reader) -> Option<(Schema, Vec<Row>)> {
+    let count = reader.u32()?;
+    let mut columns = Vec::new();
+
+    for _ in 0..count {
+        let name = reader.str()?;
+        let mut column = Column::new(name, reader.data_type()?);
+        let flags = reader.u8()?;
+        if flags & !(NULLABLE | PRIMARY_KEY | UNIQUE | DEFAULT) != 0 {
+            return None;
+        }
+        column.nullable = flags & NULLABLE != 0;
+        column.primary_key = flags & PRIMARY_KEY != 0;
+        column.unique = flags & UNIQUE != 0;
+        if flags & DEFAULT != 0 {
+            column.default = Some(reader.value()?);
+        }
+        columns.push(column);
+    }
+
+    // Every row takes at least one byte per column, so a corrupt count is rejected before
+    // anything is allocated. Rows of a table without columns take no bytes at all, and are
+    // only bounded by the memory they need.
+    let count = reader.u64()?;
+    let Some(width) = NonZeroUsize::new(columns.len()) else {
+        let count = usize::try_from(count).ok()?;
+        let mut rows = Vec::new();
+        rows.try_reserve_exact(count).ok()?;
+        rows.resize(count, Row::new(Vec::new()));
+        return Some((Schema::from_columns(columns), rows));
+    };
+    if count > (reader.remaining() / width) as u64 {
+        return None;
+    }
+
+    let mut rows = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let values = (columns.iter())
+            .map(|column| {
+                let value = reader.value()?;
+                let fits = (value.data_type())
+                    .map_or(column.nullable, |data_type| data_type == column.data_type);
+                fits.then_some(value)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        rows.push(Row::new(values));
+    }
+
+    Some((Schema::from_columns(columns), rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{sample, text};
+    use crate::tinycsv_db::{DataType, Value, insert, to_csv_typed, update};
+
+    #[test]
+    fn test_round_trip_keeps_types_bits_and_constraints() {
+        let mut db = sample();
+        for (id, ratio) in [(8, f64::NAN), (9, f64::NEG_INFINITY), (10, 0.1 + 0.2)] {
+            let values = vec![
+                Value::Integer(id),
+                text("1.5"),
+                Value::Float(ratio),
+                Value::Null,
+            ];
+            insert(&mut db, Row::new(values)).unwrap();
+        }
+        let restored = Database::from_bytes(&db.to_bytes()).unwrap();
+
+        assert_eq!(to_csv_typed(&restored), to_csv_typed(&db));
+        for (restored, row) in restored.get_rows().iter().zip(db.get_rows()) {
+            for (restored, value) in restored.values.iter().zip(&row.values) {
+                match (restored, value) {
+                    (Value::Float(a), Value::Float(b)) => assert_eq!(a.to_bits(), b.to_bits()),
+                    (a, b) => assert_eq!(a, b),
+                }
+            }
+        }
+
+        let columns = restored.get_schema().get_columns();
+        assert!(columns[0].is_primary_key() && columns[0].is_unique());
+        assert!(columns[3].is_unique() && columns[3].is_nullable());
+
+        let mut restored = restored;
+        assert!(matches!(
+            insert(
+                &mut restored,
+                Row::new(vec![Value::Integer(7), text(""), Value::Null, Value::Null])
+            ),
+            Err(Error::UniqueViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_empty_database() {
+        let db = Database::new(Schema::from_columns(vec![]));
+        let restored = Database::from_bytes(&db.to_bytes()).unwrap();
+
+        assert!(restored.get_schema().get_columns().is_empty());
+        assert!(restored.get_rows().is_empty());
+    }
+
+    #[test]
+    fn test_rows_without_columns() {
+        let mut db = Database::new(Schema::from_columns(vec![]));
+        insert(&mut db, Row::new(vec![])).unwrap();
+        insert(&mut db, Row::new(vec![])).unwrap();
+
+        let restored = Database::from_bytes(&db.to_bytes()).unwrap();
+        assert_eq!(restored.get_rows(), &vec![Row::new(vec![]); 2]);
+    }
+
+    #[test]
+    fn test_damaged_bytes_are_rejected() {
+        let bytes = sample().to_bytes();
+        let message = |bytes: &[u8]| match Database::from_bytes(bytes) {
+            Err(Error::Corrupt(message)) => message,
+            other => panic!("expected corrupt data, got {:?}", other.map(|_| ())),
+        };
+
+        assert_eq!(message(b"id,name\n1,a\n"), "not a tinycsv_db binary file");
+        for end in 0..bytes.len() {
+            message(&bytes[..end]);
+        }
+
+        let mut flipped = bytes.clone();
+        flipped[40] ^= 1;
+        assert_eq!(message(&flipped), "checksum mismatch");
+
+        let mut newer = bytes.clone();
+        newer[4] = 2;
+        assert_eq!(message(&newer), "unsupported binary format version 2");
+    }
+
+    #[test]
+    fn test_defaults_are_not_applied_to_stored_nulls() {
+        let mut db = Database::new(Schema::from_columns(vec![
+            Column::new("note", DataType::Text)
+                .nullable()
+                .default(Value::Text("?".to_string())),
+        ]));
+        insert(&mut db, Row::new(vec![Value::Null])).unwrap();
+        update(&mut db, |_| true, &[("note", Value::Null)]).unwrap();
+
+        let restored = Database::from_bytes(&db.to_bytes()).unwrap();
+        assert_eq!(restored.get_rows(), &vec![Row::new(vec![Value::Null])]);
+        assert_eq!(
+            restored.get_schema().get_columns()[0].get_default(),
+            Some(&text("?"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_bodies_are_rejected() {
+        let sealed = |body: &[u8]| {
+            let mut bytes = MAGIC.to_vec();
+            put_u32(&mut bytes, VERSION);
+            bytes.extend_from_slice(body);
+            let checksum = crc32(&bytes);
+            put_u32(&mut bytes, checksum);
+            Database::from_bytes(&bytes)
+        };
+        let column = |data_type: u8, flags: u8| {
+            let mut bytes = Vec::new();
+            put_u32(&mut bytes, 1);
+            put_str(&mut bytes, "id");
+            bytes.extend_from_slice(&[data_type, flags]);
+            bytes
+        };
+
+        // Rows of a table without columns take no bytes, but still need memory.
+        let mut body = Vec::new();
+        put_u32(&mut body, 0);
+        put_u64(&mut body, u64::MAX);
+        assert!(matches!(sealed(&body), Err(Error::Corrupt(_))));
+
+        let mut body = column(1, 16);
+        put_u64(&mut body, 0);
+        assert!(matches!(sealed(&body), Err(Error::Corrupt(_))));
+
+        // The count of rows cannot exceed the bytes left.
+        let mut body = column(1, 0);
+        put_u64(&mut body, u64::MAX);
+        put_value(&mut body, &Value::Integer(1));
+        assert!(matches!(sealed(&body), Err(Error::Corrupt(_))));
+
+        let mut body = column(9, 0);
+        put_u64(&mut body, 0);
+        assert!(matches!(sealed(&body), Err(Error::Corrupt(_))));
+
+        for value in [Value::Null, Value::Text("1".to_string())] {
+            let mut body = column(1, 0);
+            put_u64(&mut body, 1);
+            put_value(&mut body, &value);
+            assert!(matches!(sealed(&body), Err(Error::Corrupt(_))));
+        }
+
+        let mut body = column(1, UNIQUE);
+        put_u64(&mut body, 2);
+        put_value(&mut body, &Value::Integer(1));
+        put_value(&mut body, &Value::Integer(1));
+        assert!(matches!(sealed(&body), Err(Error::UniqueViolation { .. })));
+    }
+}
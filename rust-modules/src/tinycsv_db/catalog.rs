@@ -0,0 +1,447 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use super::csv::CsvDialect;
+use super::database::Database;
+use super::error::{Error, Result};
+use super::schema::Schema;
+use super::sql::{SqlResult, execute_statement, parse};
+
+/// The file in a directory written by `Catalog::save_dir` that lists the tables saved there,
+/// separated by NUL bytes, which table names cannot contain.
+const MANIFEST: &str = ".tables";
+
+/// A set of named tables, each one a `Database`.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let mut catalog = Catalog::new();
+/// catalog
+///     .create_table("users", Schema::new(vec![("name".to_string(), DataType::Text)]))
+///     .unwrap();
+/// catalog.execute("INSERT INTO users VALUES ('Alice')").unwrap();
+/// catalog.rename_table("users", "people").unwrap();
+///
+/// assert_eq!(catalog.get_table_names(), vec!["people"]);
+/// assert_eq!(catalog.get_table("people").unwrap().get_rows().len(), 1);
+/// ```
+#[derive(Default)]
+pub struct Catalog {
+    tables: BTreeMap<String, Database>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Catalog::default()
+    }
+
+    /// The names of the tables, in sorted order.
+    pub fn get_table_names(&self) -> Vec<&str> {
+        self.tables.keys().map(String::as_str).collect()
+    }
+
+    /// # Errors
+    /// Returns `Error::UnknownTable` if there is no such table.
+    pub fn get_table(&self, name: &str) -> Result<&Database> {
+        self.tables
+            .get(name)
+            .ok_or_else(|| Error::UnknownTable(name.to_string()))
+    }
+
+    /// # Errors
+    /// Returns `Error::UnknownTable` if there is no such table.
+    pub fn get_table_mut(&mut self, name: &str) -> Result<&mut Database> {
+        self.tables
+            .get_mut(name)
+            .ok_or_else(|| Error::UnknownTable(name.to_string()))
+    }
+
+    /// Create an empty table.
+    ///
+    /// # Returns
+    /// The new table.
+    ///
+    /// # Errors
+    /// Returns the errors of `Catalog::add_table`.
+    pub fn create_table(&mut self, name: &str, schema: Schema) -> Result<&mut Database> {
+        self.add_table(name, Database::new(schema))?;
+        self.get_table_mut(name)
+    }
+
+    /// Add an existing database as a table.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidTableName` if the name is empty, starts with `.` or contains a
+    /// path separator, since tables are saved as files, and `Error::TableExists` if the name
+    /// is taken.
+    pub fn add_table(&mut self, name: &str, db: Database) -> Result<()> {
+        check_name(name)?;
+        if self.tables.contains_key(name) {
+            return Err(Error::TableExists(name.to_string()));
+        }
+
+        self.tables.insert(name.to_string(), db);
+        Ok(())
+    }
+
+    /// Remove a table.
+    ///
+    /// # Returns
+    /// The removed table.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownTable` if there is no such table.
+    pub fn drop_table(&mut self, name: &str) -> Result<Database> {
+        self.tables
+            .remove(name)
+            .ok_or_else(|| Error::UnknownTable(name.to_string()))
+    }
+
+    /// Give a table a new name.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownTable` if there is no table named `from`, and the errors of
+    /// `Catalog::add_table` for the name `to`.
+    pub fn rename_table(&mut self, from: &str, to: &str) -> Result<()> {
+        check_name(to)?;
+        if !self.tables.contains_key(from) {
+            return Err(Error::UnknownTable(from.to_string()));
+        }
+        if from != to && self.tables.contains_key(to) {
+            return Err(Error::TableExists(to.to_string()));
+        }
+
+        let db = self.drop_table(from)?;
+        self.tables.insert(to.to_string(), db);
+        Ok(())
+    }
+
+    /// Run a SQL statement against the table it names (see `execute`).
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownTable` if there is no such table and the errors of `execute`.
+    pub fn execute(&mut self, sql: &str) -> Result<SqlResult> {
+        let statement = parse(sql)?;
+        let db = self.get_table_mut(statement.table())?;
+        execute_statement(db, statement)
+    }
+
+    /// Load every `.csv` file of a directory as a table named after the file.
+    ///
+    /// The files need typed headers (`name:type`, with `?` for nullable columns), as written
+    /// by `Catalog::save_dir`. Other files are ignored.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if the directory or a file cannot be read,
+    /// `Error::InvalidTableName` if a file name is not valid UTF-8 and the errors of
+    /// `Database::read_csv`.
+    pub fn load_dir(path: impl AsRef<Path>) -> Result<Catalog> {
+        let dialect = CsvDialect {
+            typed_header: true,
+            ..CsvDialect::default()
+        };
+        let mut catalog = Catalog::new();
+
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().is_none_or(|extension| extension != "csv") {
+                continue;
+            }
+            let stem = path.file_stem().unwrap_or_default();
+            let name = stem
+                .to_str()
+                .ok_or_else(|| Error::InvalidTableName(stem.to_string_lossy().into_owned()))?;
+
+            let db = Database::read_csv(BufReader::new(File::open(&path)?), None, &dialect)?;
+            catalog.add_table(name, db)?;
+        }
+
+        Ok(catalog)
+    }
+
+    /// Save every table to `<name>.csv` in a directory, creating the directory if needed.
+    ///
+    /// Files are written with typed headers, so column types and nullability survive a
+    /// `Catalog::load_dir`; other constraints do not. Each file is written to a temporary
+    /// file, synced and then renamed, so an interrupted save never leaves a half-written table.
+    /// The saved tables are listed in a hidden `.tables` file, and the files of tables saved
+    /// by an earlier call but since dropped or renamed are removed, so `Catalog::load_dir`
+    /// finds exactly the saved tables. Other files in the directory are left alone.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if the directory or a file cannot be written or removed.
+    pub fn save_dir(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let dialect = CsvDialect {
+            typed_header: true,
+            ..CsvDialect::default()
+        };
+        fs::create_dir_all(path)?;
+        let saved = match fs::read(path.join(MANIFEST)) {
+            Ok(manifest) => manifest,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        for (name, db) in &self.tables {
+            let target = path.join(format!("{}.csv", name));
+            let temporary = path.join(format!(".{}.csv.tmp", name));
+
+            let mut writer = BufWriter::new(File::create(&temporary)?);
+            db.write_csv(&mut writer, &dialect)?;
+            writer
+                .into_inner()
+                .map_err(|error| error.into_error())?
+                .sync_all()?;
+            fs::rename(&temporary, &target)?;
+        }
+
+        for name in saved.split(|&byte| byte == 0) {
+            let Ok(name) = std::str::from_utf8(name) else {
+                continue;
+            };
+            if name.is_empty() || check_name(name).is_err() || self.tables.contains_key(name) {
+                continue;
+            }
+            match fs::remove_file(path.join(format!("{}.csv", name))) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+                _ => {}
+            }
+        }
+
+        let manifest = path.join(MANIFEST);
+        let temporary = path.join(format!("{}.tmp", MANIFEST));
+        let names = self.tables.keys().map(String::as_str).collect::<Vec<_>>();
+        let mut file = File::create(&temporary)?;
+        file.write_all(names.join("\0").as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temporary, &manifest)?;
+
+        sync_dir(path)?;
+        Ok(())
+    }
+}
+
+/// Make the renames and removals in a directory durable, where the platform supports it.
+pub(crate) fn sync_dir(path: &Path) -> io::Result<()> {
+    if cfg!(unix) {
+        File::open(path)?.sync_all()?;
+    }
+    Ok(())
+}
+
+fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', '\0']) {
+        return Err(Error::InvalidTableName(name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::TempDir;
+    use crate::tinycsv_db::{Column, DataType, Value};
+
+    fn users() -> Schema {
+        Schema::from_columns(vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("name", DataType::Text),
+            Column::new("score", DataType::Float).nullable(),
+        ])
+    }
+
+    fn orders() -> Schema {
+        Schema::from_columns(vec![
+            Column::new("user_id", DataType::Integer),
+            Column::new("paid", DataType::Boolean),
+        ])
+    }
+
+    #[test]
+    fn test_create_drop_rename() {
+        let mut catalog = Catalog::new();
+        catalog.create_table("users", users()).unwrap();
+        catalog.create_table("orders", orders()).unwrap();
+
+        assert_eq!(catalog.get_table_names(), vec!["orders", "users"]);
+        assert!(matches!(
+            catalog.create_table("users", orders()),
+            Err(Error::TableExists(name)) if name == "users"
+        ));
+
+        catalog.rename_table("users", "people").unwrap();
+        assert_eq!(catalog.get_table_names(), vec!["orders", "people"]);
+        assert!(matches!(
+            catalog.rename_table("people", "orders"),
+            Err(Error::TableExists(_))
+        ));
+        assert!(matches!(
+            catalog.rename_table("users", "members"),
+            Err(Error::UnknownTable(_))
+        ));
+        catalog.rename_table("people", "people").unwrap();
+
+        assert_eq!(
+            catalog
+                .drop_table("orders")
+                .unwrap()
+                .get_schema()
+                .get_columns()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            catalog.get_table("orders"),
+            Err(Error::UnknownTable(_))
+        ));
+        assert_eq!(catalog.get_table_names(), vec!["people"]);
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let mut catalog = Catalog::new();
+
+        for name in ["", ".hidden", "a/b", "a\\b"] {
+            assert!(matches!(
+                catalog.create_table(name, users()),
+                Err(Error::InvalidTableName(_))
+            ));
+        }
+        catalog.create_table("users", users()).unwrap();
+        assert!(matches!(
+            catalog.rename_table("users", "../users"),
+            Err(Error::InvalidTableName(_))
+        ));
+    }
+
+    #[test]
+    fn test_execute_dispatches_by_table() {
+        let mut catalog = Catalog::new();
+        catalog.create_table("users", users()).unwrap();
+        catalog.create_table("orders", orders()).unwrap();
+
+        catalog
+            .execute("INSERT INTO users VALUES (1, 'Alice', 9.5), (2, 'Bob', NULL)")
+            .unwrap();
+        catalog
+            .execute("INSERT INTO \"orders\" VALUES (1, TRUE)")
+            .unwrap();
+
+        assert_eq!(catalog.get_table("users").unwrap().get_rows().len(), 2);
+        assert_eq!(catalog.get_table("orders").unwrap().get_rows().len(), 1);
+        assert!(matches!(
+            catalog.execute("SELECT * FROM invoices"),
+            Err(Error::UnknownTable(name)) if name == "invoices"
+        ));
+        assert!(matches!(
+            catalog.execute("DELETE FROM orders WHERE id = 1"),
+            Err(Error::Sql {
+                line: 1,
+                column: 26,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load_dir() {
+        let dir = TempDir::new("catalog_round_trip");
+        let mut catalog = Catalog::new();
+        catalog.create_table("users", users()).unwrap();
+        catalog.create_table("orders", orders()).unwrap();
+        catalog
+            .execute("INSERT INTO users VALUES (1, 'Alice, \"Al\"', 9.5), (2, 'Bob', NULL)")
+            .unwrap();
+        catalog
+            .execute("INSERT INTO orders VALUES (2, FALSE)")
+            .unwrap();
+
+        catalog.save_dir(&dir.0).unwrap();
+        fs::write(dir.0.join("notes.txt"), "not a table").unwrap();
+        let loaded = Catalog::load_dir(&dir.0).unwrap();
+
+        assert_eq!(loaded.get_table_names(), vec!["orders", "users"]);
+        for name in ["orders", "users"] {
+            let (original, loaded) = (
+                catalog.get_table(name).unwrap(),
+                loaded.get_table(name).unwrap(),
+            );
+            assert_eq!(original.get_rows(), loaded.get_rows());
+
+            let columns = |db: &Database| {
+                db.get_schema()
+                    .get_columns()
+                    .iter()
+                    .map(|column| {
+                        (
+                            column.get_name().to_string(),
+                            column.get_data_type(),
+                            column.is_nullable(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(columns(original), columns(loaded));
+        }
+        assert_eq!(
+            loaded.get_table("users").unwrap().get_rows()[1].get_value(2),
+            Some(&Value::Null)
+        );
+    }
+
+    #[test]
+    fn test_save_dir_removes_stale_tables() {
+        let dir = TempDir::new("catalog_stale");
+        let mut catalog = Catalog::new();
+        catalog.create_table("a", users()).unwrap();
+        catalog.create_table("b", orders()).unwrap();
+        catalog.save_dir(&dir.0).unwrap();
+        fs::write(dir.0.join("notes.txt"), "not a table").unwrap();
+
+        catalog.drop_table("b").unwrap();
+        catalog.rename_table("a", "c").unwrap();
+        catalog.save_dir(&dir.0).unwrap();
+
+        assert_eq!(
+            Catalog::load_dir(&dir.0).unwrap().get_table_names(),
+            vec!["c"]
+        );
+        assert!(dir.0.join("notes.txt").is_file());
+    }
+
+    #[test]
+    fn test_save_dir_keeps_other_csv_files() {
+        let dir = TempDir::new("catalog_other_files");
+        fs::write(dir.0.join("my_export.csv"), "id:int\n1\n").unwrap();
+        let mut catalog = Catalog::new();
+        catalog.create_table("users", users()).unwrap();
+        catalog.save_dir(&dir.0).unwrap();
+        catalog.save_dir(&dir.0).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.0.join("my_export.csv")).unwrap(),
+            "id:int\n1\n"
+        );
+    }
+
+    #[test]
+    fn test_load_dir_errors() {
+        let dir = TempDir::new("catalog_errors");
+
+        assert!(matches!(
+            Catalog::load_dir(dir.0.join("missing")),
+            Err(Error::Io(_))
+        ));
+
+        fs::write(dir.0.join("broken.csv"), "id:int\nnot a number\n").unwrap();
+        assert!(matches!(
+            Catalog::load_dir(&dir.0),
+            Err(Error::InvalidCell { .. })
+        ));
+    }
+}
@@ -0,0 +1,247 @@
+use super::row::Row;
+use super::types::{DataType, Value};
+
+const NULL: u8 = 0;
+const INTEGER: u8 = 1;
+const FLOAT: u8 = 2;
+const BOOLEAN: u8 = 3;
+const TEXT: u8 = 4;
+
+/// CRC-32 lookup tables of the reflected polynomial `0xEDB88320`, as used by zlib.
+///
+/// `CRC_TABLES[0]` advances the checksum by one byte; `CRC_TABLES[k]` advances it by a
+/// byte followed by `k` zero bytes, so eight bytes are folded in at once.
+const CRC_TABLES: [[u32; 256]; 8] = {
+    let mut tables = [[0; 256]; 8];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xEDB8_8320,
+                _ => crc >> 1,
+            };
+            bit += 1;
+        }
+        tables[0][byte] = crc;
+        byte += 1;
+    }
+
+    let mut table = 1;
+    while table < 8 {
+        let mut byte = 0;
+        while byte < 256 {
+            let previous = tables[table - 1][byte];
+            tables[table][byte] = (previous >> 8) ^ tables[0][(previous & 0xFF) as usize];
+            byte += 1;
+        }
+        table += 1;
+    }
+    tables
+};
+
+/// The CRC-32 checksum of the bytes, the same as zlib's `crc32`.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let tables = &CRC_TABLES;
+    let mut chunks = bytes.chunks_exact(8);
+    let mut crc = !0u32;
+
+    for chunk in &mut chunks {
+        let low = crc ^ u32::from_le_bytes(chunk[..4].try_into().unwrap());
+        let high = u32::from_le_bytes(chunk[4..].try_into().unwrap());
+        crc = tables[7][(low & 0xFF) as usize]
+            ^ tables[6][((low >> 8) & 0xFF) as usize]
+            ^ tables[5][((low >> 16) & 0xFF) as usize]
+            ^ tables[4][(low >> 24) as usize]
+            ^ tables[3][(high & 0xFF) as usize]
+            ^ tables[2][((high >> 8) & 0xFF) as usize]
+            ^ tables[1][((high >> 16) & 0xFF) as usize]
+            ^ tables[0][(high >> 24) as usize];
+    }
+    for &byte in chunks.remainder() {
+        crc = tables[0][((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+pub(crate) fn put_u32(bytes: &mut Vec<u8>, num: u32) {
+    bytes.extend_from_slice(&num.to_le_bytes());
+}
+
+pub(crate) fn put_u64(bytes: &mut Vec<u8>, num: u64) {
+    bytes.extend_from_slice(&num.to_le_bytes());
+}
+
+/// Append a length-prefixed string.
+pub(crate) fn put_str(bytes: &mut Vec<u8>, text: &str) {
+    put_u32(bytes, text.len() as u32);
+    bytes.extend_from_slice(text.as_bytes());
+}
+
+/// Append a value as a type tag followed by its little-endian bytes; floats keep their
+/// exact bits.
+pub(crate) fn put_value(bytes: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => bytes.push(NULL),
+        Value::Integer(num) => {
+            bytes.push(INTEGER);
+            bytes.extend_from_slice(&num.to_le_bytes());
+        }
+        Value::Float(num) => {
+            bytes.push(FLOAT);
+            put_u64(bytes, num.to_bits());
+        }
+        Value::Boolean(bool) => bytes.extend_from_slice(&[BOOLEAN, u8::from(*bool)]),
+        Value::Text(text) => {
+            bytes.push(TEXT);
+            put_str(bytes, text);
+        }
+    }
+}
+
+/// Append a data type as the tag of its values.
+pub(crate) fn put_type(bytes: &mut Vec<u8>, data_type: DataType) {
+    bytes.push(match data_type {
+        DataType::Integer => INTEGER,
+        DataType::Float => FLOAT,
+        DataType::Boolean => BOOLEAN,
+        DataType::Text => TEXT,
+    });
+}
+
+/// Append a row as its number of values followed by the values.
+pub(crate) fn put_row(bytes: &mut Vec<u8>, row: &Row) {
+    put_u32(bytes, row.values.len() as u32);
+    for value in &row.values {
+        put_value(bytes, value);
+    }
+}
+
+/// Reads what the `put_*` functions wrote. Every method returns `None` if the bytes end
+/// too early or do not hold a valid encoding.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    pub(crate) position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, position: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    /// The number of bytes left to read.
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let bytes = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        self.bytes(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn str(&mut self) -> Option<&'a str> {
+        let length = self.u32()? as usize;
+        std::str::from_utf8(self.bytes(length)?).ok()
+    }
+
+    pub(crate) fn value(&mut self) -> Option<Value> {
+        Some(match self.u8()? {
+            NULL => Value::Null,
+            INTEGER => Value::Integer(self.u64()? as i64),
+            FLOAT => Value::Float(f64::from_bits(self.u64()?)),
+            BOOLEAN => match self.u8()? {
+                0 => Value::Boolean(false),
+                1 => Value::Boolean(true),
+                _ => return None,
+            },
+            TEXT => Value::Text(self.str()?.to_string()),
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn data_type(&mut self) -> Option<DataType> {
+        Some(match self.u8()? {
+            INTEGER => DataType::Integer,
+            FLOAT => DataType::Float,
+            BOOLEAN => DataType::Boolean,
+            TEXT => DataType::Text,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn row(&mut self) -> Option<Row> {
+        let count = self.u32()?;
+        // Every value takes at least one byte, so a corrupt count cannot allocate much.
+        let mut values = Vec::with_capacity((count as usize).min(self.remaining()));
+        for _ in 0..count {
+            values.push(self.value()?);
+        }
+        Some(Row { values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+    }
+
+    #[test]
+    fn test_values_round_trip() {
+        let row = Row::new(vec![
+            Value::Null,
+            Value::Integer(i64::MIN),
+            Value::Float(-0.0),
+            Value::Float(f64::from_bits(0x7FF8_0000_0000_0001)),
+            Value::Boolean(true),
+            Value::Text("naïve, \"quoted\"\n".to_string()),
+        ]);
+        let mut bytes = Vec::new();
+        put_row(&mut bytes, &row);
+
+        let mut reader = Reader::new(&bytes);
+        let decoded = reader.row().unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(decoded.values[..2], row.values[..2]);
+        for (decoded, value) in decoded.values[2..4].iter().zip(&row.values[2..4]) {
+            let (Value::Float(decoded), Value::Float(value)) = (decoded, value) else {
+                unreachable!();
+            };
+            assert_eq!(decoded.to_bits(), value.to_bits());
+        }
+        assert_eq!(decoded.values[4..], row.values[4..]);
+
+        for end in 0..bytes.len() {
+            assert!(Reader::new(&bytes[..end]).row().is_none());
+        }
+    }
+}
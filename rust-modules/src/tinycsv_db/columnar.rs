@@ -0,0 +1,858 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use super::aggregate::{Aggregate, AggregateFn};
+use super::database::Database;
+use super::error::{Error, Result};
+use super::filter::{Filter, Matcher};
+use super::index::Key;
+use super::operations::load_rows;
+use super::row::Row;
+use super::schema::Schema;
+use super::types::{DataType, Value};
+
+/// A fixed-length sequence of bits, packed 64 to a word.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    /// Create a bitmap of `len` bits, all unset.
+    pub fn new(len: usize) -> Self {
+        Bitmap {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a bit; bits past the end are unset.
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Set or unset a bit.
+    ///
+    /// # Panics
+    /// Panics if the index is past the end.
+    pub fn set(&mut self, index: usize, bit: bool) {
+        assert!(
+            index < self.len,
+            "bit {} of a bitmap of {}",
+            index,
+            self.len
+        );
+        let mask = 1 << (index % 64);
+        match bit {
+            true => self.words[index / 64] |= mask,
+            false => self.words[index / 64] &= !mask,
+        }
+    }
+
+    /// Append a bit.
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        if let Some(word) = self.words.last_mut() {
+            *word |= u64::from(bit) << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The positions of the set bits, in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                let bit = (word != 0).then(|| word.trailing_zeros() as usize)?;
+                word &= word - 1;
+                Some(index * 64 + bit)
+            })
+        })
+    }
+
+    /// The bits set in both bitmaps.
+    ///
+    /// # Panics
+    /// Panics if the bitmaps have different lengths, here and in `Bitmap::or`.
+    pub fn and(&self, other: &Bitmap) -> Bitmap {
+        self.zip(other, |a, b| a & b)
+    }
+
+    /// The bits set in either bitmap.
+    pub fn or(&self, other: &Bitmap) -> Bitmap {
+        self.zip(other, |a, b| a | b)
+    }
+
+    /// The bits not set in the bitmap.
+    pub fn not(&self) -> Bitmap {
+        let mut result = Bitmap {
+            words: self.words.iter().map(|word| !word).collect(),
+            len: self.len,
+        };
+        if let Some(last) = result.words.last_mut()
+            && !self.len.is_multiple_of(64)
+        {
+            *last &= u64::MAX >> (64 - self.len % 64);
+        }
+        result
+    }
+
+    fn zip(&self, other: &Bitmap, combine: impl Fn(u64, u64) -> u64) -> Bitmap {
+        assert_eq!(self.len, other.len, "bitmaps of different lengths");
+        Bitmap {
+            words: (self.words.iter().zip(&other.words))
+                .map(|(&a, &b)| combine(a, b))
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut bitmap = Bitmap::default();
+        for bit in bits {
+            bitmap.push(bit);
+        }
+        bitmap
+    }
+}
+
+/// The values of one column, in a vector of their type. The slots of nulls hold `0`,
+/// `0.0`, `false` or the code `0`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ColumnData {
+    Integer(Vec<i64>),
+    Float(Vec<f64>),
+    /// Every distinct text once in `dictionary`, in order of first appearance, and for
+    /// every row the position of its text in the dictionary.
+    Text {
+        dictionary: Vec<String>,
+        codes: Vec<u32>,
+    },
+    Boolean(Bitmap),
+}
+
+/// A column of a `ColumnarTable`: its values and a bitmap of the rows where it is null.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnVector {
+    pub(crate) data: ColumnData,
+    pub(crate) nulls: Bitmap,
+}
+
+impl ColumnVector {
+    fn new(rows: &[Row], index: usize, data_type: DataType) -> Self {
+        let values = rows.iter().map(|row| &row.values[index]);
+        // The values of a database all have the type of their column, or are null.
+        let data = match data_type {
+            DataType::Integer => ColumnData::Integer(
+                values
+                    .map(|value| match value {
+                        Value::Integer(num) => *num,
+                        _ => 0,
+                    })
+                    .collect(),
+            ),
+            DataType::Float => ColumnData::Float(
+                values
+                    .map(|value| match value {
+                        Value::Float(num) => *num,
+                        _ => 0.0,
+                    })
+                    .collect(),
+            ),
+            DataType::Boolean => ColumnData::Boolean(
+                values
+                    .map(|value| matches!(value, Value::Boolean(true)))
+                    .collect(),
+            ),
+            DataType::Text => {
+                let mut dictionary = Vec::new();
+                let mut positions: HashMap<&str, u32> = HashMap::new();
+                let codes = values
+                    .map(|value| match value {
+                        Value::Text(text) => *positions.entry(text).or_insert_with(|| {
+                            dictionary.push(text.clone());
+                            dictionary.len() as u32 - 1
+                        }),
+                        _ => 0,
+                    })
+                    .collect();
+                ColumnData::Text { dictionary, codes }
+            }
+        };
+
+        ColumnVector {
+            data,
+            nulls: rows.iter().map(|row| row.values[index].is_null()).collect(),
+        }
+    }
+
+    pub fn get_data(&self) -> &ColumnData {
+        &self.data
+    }
+
+    /// The rows where the column is `Value::Null`.
+    pub fn get_nulls(&self) -> &Bitmap {
+        &self.nulls
+    }
+
+    pub fn len(&self) -> usize {
+        self.nulls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nulls.is_empty()
+    }
+
+    /// Get the value of a row, or `None` if there is no such row.
+    pub fn get_value(&self, index: usize) -> Option<Value> {
+        if index >= self.len() {
+            return None;
+        }
+        if self.nulls.get(index) {
+            return Some(Value::Null);
+        }
+        Some(match &self.data {
+            ColumnData::Integer(nums) => Value::Integer(nums[index]),
+            ColumnData::Float(nums) => Value::Float(nums[index]),
+            ColumnData::Text { dictionary, codes } => {
+                Value::Text(dictionary[codes[index] as usize].clone())
+            }
+            ColumnData::Boolean(bits) => Value::Boolean(bits.get(index)),
+        })
+    }
+
+    /// Apply a function to the position of every non-null value, giving unknown for nulls.
+    fn map(&self, test: impl Fn(usize) -> Option<bool>) -> Truth {
+        (0..self.len())
+            .map(|index| match self.nulls.get(index) {
+                true => None,
+                false => test(index),
+            })
+            .collect()
+    }
+
+    /// Compare every value with a constant, like `Value::sql_cmp`, and test the orderings.
+    fn compare(&self, value: &Value, accepts: impl Fn(Ordering) -> bool) -> Truth {
+        let test = |ordering: Option<Ordering>| ordering.map(&accepts);
+        match (&self.data, value) {
+            (ColumnData::Integer(nums), Value::Integer(num)) => {
+                self.map(|index| test(Some(nums[index].cmp(num))))
+            }
+            (ColumnData::Integer(nums), Value::Float(num)) => {
+                self.map(|index| test((nums[index] as f64).partial_cmp(num)))
+            }
+            (ColumnData::Float(nums), Value::Integer(num)) => {
+                let num = *num as f64;
+                self.map(|index| test(nums[index].partial_cmp(&num)))
+            }
+            (ColumnData::Float(nums), Value::Float(num)) => {
+                self.map(|index| test(nums[index].partial_cmp(num)))
+            }
+            (ColumnData::Text { dictionary, codes }, Value::Text(text)) => {
+                let results: Vec<_> = (dictionary.iter())
+                    .map(|entry| test(Some(entry.as_str().cmp(text))))
+                    .collect();
+                self.map(|index| results[codes[index] as usize])
+            }
+            (ColumnData::Boolean(bits), Value::Boolean(bool)) => {
+                self.map(|index| test(Some(bits.get(index).cmp(bool))))
+            }
+            _ => Truth::unknown(self.len()),
+        }
+    }
+}
+
+/// The result of a filter on every row: `yes` marks the rows where it is true and `no`
+/// those where it is false. Rows in neither are unknown.
+struct Truth {
+    yes: Bitmap,
+    no: Bitmap,
+}
+
+impl Truth {
+    fn unknown(len: usize) -> Self {
+        Truth {
+            yes: Bitmap::new(len),
+            no: Bitmap::new(len),
+        }
+    }
+
+    fn and(&self, other: &Truth) -> Truth {
+        Truth {
+            yes: self.yes.and(&other.yes),
+            no: self.no.or(&other.no),
+        }
+    }
+
+    fn or(&self, other: &Truth) -> Truth {
+        Truth {
+            yes: self.yes.or(&other.yes),
+            no: self.no.and(&other.no),
+        }
+    }
+}
+
+impl FromIterator<Option<bool>> for Truth {
+    fn from_iter<I: IntoIterator<Item = Option<bool>>>(results: I) -> Self {
+        let (mut yes, mut no) = (Bitmap::default(), Bitmap::default());
+        for result in results {
+            yes.push(result == Some(true));
+            no.push(result == Some(false));
+        }
+        Truth { yes, no }
+    }
+}
+
+/// A read-only copy of a database stored column by column, for reports that scan a few
+/// columns of many rows.
+///
+/// Each column keeps its values in one vector of their type: integers in a `Vec<i64>`,
+/// floats in a `Vec<f64>`, booleans in a `Bitmap` and text as codes into a dictionary of
+/// its distinct strings, with a second bitmap marking the nulls. Filters and aggregates
+/// run over whole columns at a time and give the same results as `Filter::compile` and
+/// `GroupBy` on the rows; text predicates are evaluated once per distinct string.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let db = Database::from_csv("id,name,score\n1,Alice,95.5\n2,Bob,80\n3,Alice,70\n").unwrap();
+/// let table = db.to_columnar();
+///
+/// let alice = table.filter(&Filter::eq("name", Value::Text("Alice".to_string()))).unwrap();
+/// assert_eq!(alice.iter_ones().collect::<Vec<_>>(), vec![0, 2]);
+/// assert_eq!(
+///     table.aggregate(&Aggregate::avg("score"), Some(&alice)).unwrap(),
+///     Value::Float(82.75)
+/// );
+/// assert_eq!(table.to_database().unwrap().get_rows(), db.get_rows());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ColumnarTable {
+    schema: Schema,
+    columns: Vec<ColumnVector>,
+    len: usize,
+}
+
+impl Database {
+    /// Copy the rows into a `ColumnarTable`. Later changes to the database do not
+    /// affect the copy.
+    pub fn to_columnar(&self) -> ColumnarTable {
+        let columns = (self.schema.columns.iter().enumerate())
+            .map(|(index, column)| ColumnVector::new(&self.rows, index, column.data_type))
+            .collect();
+
+        ColumnarTable {
+            schema: self.schema.clone(),
+            columns,
+            len: self.rows.len(),
+        }
+    }
+}
+
+impl ColumnarTable {
+    pub fn get_schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a column by name.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist.
+    pub fn get_column(&self, column_name: &str) -> Result<&ColumnVector> {
+        Ok(&self.columns[self.schema.column_index(column_name)?])
+    }
+
+    /// Get a row, or `None` if there is no such row.
+    pub fn get_row(&self, index: usize) -> Option<Row> {
+        (self.columns.iter())
+            .map(|column| column.get_value(index))
+            .collect::<Option<Vec<_>>>()
+            .filter(|_| index < self.len)
+            .map(Row::new)
+    }
+
+    /// Copy the rows back into a row-oriented database with the same schema. The rows are
+    /// kept as they are, so nulls stay null in columns with a default. Indexes other than
+    /// the unique ones are not kept by the table and are not rebuilt.
+    ///
+    /// # Errors
+    /// Returns `Error::UniqueViolation` if a UNIQUE or primary key column repeats a value.
+    pub fn to_database(&self) -> Result<Database> {
+        let rows = (0..self.len)
+            .map(|index| self.get_row(index).unwrap())
+            .collect();
+        let mut db = Database::new(self.schema.clone());
+        load_rows(&mut db, rows)?;
+        Ok(db)
+    }
+
+    /// Find the rows matching a filter, evaluating it a column at a time.
+    ///
+    /// # Returns
+    /// A bitmap with one bit per row, set for the rows matching the filter.
+    ///
+    /// # Errors
+    /// Returns the errors of `Filter::compile`.
+    pub fn filter(&self, filter: &Filter) -> Result<Bitmap> {
+        filter.compile(&self.schema).map(drop)?;
+        Ok(self.eval(filter)?.yes)
+    }
+
+    fn eval(&self, filter: &Filter) -> Result<Truth> {
+        Ok(match filter {
+            Filter::Compare { column, op, value } => self
+                .get_column(column)?
+                .compare(value, |ordering| op.accepts(ordering)),
+            Filter::In { column, values } => {
+                let column = self.get_column(column)?;
+                let mut result = Truth {
+                    yes: Bitmap::new(self.len),
+                    no: Bitmap::new(self.len).not(),
+                };
+                for candidate in values {
+                    let equal = column.compare(candidate, Ordering::is_eq);
+                    // Unknown unless some candidate is equal.
+                    result = Truth {
+                        yes: result.yes.or(&equal.yes),
+                        no: result.no.and(&equal.no),
+                    };
+                }
+                result
+            }
+            Filter::Between { column, low, high } => {
+                let column = self.get_column(column)?;
+                let above = column.compare(low, Ordering::is_ge);
+                above.and(&column.compare(high, Ordering::is_le))
+            }
+            Filter::IsNull(column) => {
+                let nulls = &self.get_column(column)?.nulls;
+                Truth {
+                    yes: nulls.clone(),
+                    no: nulls.not(),
+                }
+            }
+            Filter::Text { column, pattern } => {
+                let column = self.get_column(column)?;
+                let ColumnData::Text { dictionary, codes } = &column.data else {
+                    return Ok(Truth::unknown(self.len));
+                };
+                let matcher = Matcher::new(pattern)?;
+                let results: Vec<_> = (dictionary.iter())
+                    .map(|text| matcher.matches(text))
+                    .collect();
+                column.map(|index| Some(results[codes[index] as usize]))
+            }
+            Filter::And(a, b) => self.eval(a)?.and(&self.eval(b)?),
+            Filter::Or(a, b) => self.eval(a)?.or(&self.eval(b)?),
+            Filter::Not(a) => {
+                let Truth { yes, no } = self.eval(a)?;
+                Truth { yes: no, no: yes }
+            }
+        })
+    }
+
+    /// Compute an aggregate over a column at a time.
+    ///
+    /// # Arguments
+    /// * `aggregate` - The aggregate to compute, such as `Aggregate::sum("score")`
+    /// * `selection` - The rows to aggregate, as returned by `ColumnarTable::filter`, or
+    ///   `None` for all rows
+    ///
+    /// # Returns
+    /// The value `GroupBy` computes for the aggregate over the selected rows. Its alias is
+    /// ignored.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist, `Error::TypeMismatch` if
+    /// `SUM` or `AVG` is used on a non-numeric column, and `Error::Overflow` if an integer
+    /// sum does not fit in an `i64`.
+    ///
+    /// # Panics
+    /// Panics if the selection does not have one bit per row.
+    pub fn aggregate(&self, aggregate: &Aggregate, selection: Option<&Bitmap>) -> Result<Value> {
+        let Some(name) = &aggregate.column else {
+            let count = selection.map_or(self.len, Bitmap::count_ones);
+            return Ok(Value::Integer(count as i64));
+        };
+        let index = self.schema.column_index(name)?;
+        let (column, data_type) = (&self.columns[index], self.schema.columns[index].data_type);
+        let mut present = column.nulls.not();
+        if let Some(selection) = selection {
+            present = present.and(selection);
+        }
+        let rows = present.iter_ones();
+
+        if matches!(aggregate.function, AggregateFn::Sum | AggregateFn::Avg)
+            && !matches!(data_type, DataType::Integer | DataType::Float)
+        {
+            return Err(Error::TypeMismatch {
+                column: name.clone(),
+                expected: DataType::Float,
+                found: data_type,
+            });
+        }
+
+        Ok(match (aggregate.function, &column.data) {
+            (AggregateFn::Count, _) => Value::Integer(present.count_ones() as i64),
+            (AggregateFn::CountDistinct, data) => {
+                let count = match data {
+                    ColumnData::Integer(nums) => {
+                        rows.map(|index| nums[index]).collect::<HashSet<_>>().len()
+                    }
+                    // Index keys count -0.0 as 0.0 and all NaNs as one value.
+                    ColumnData::Float(nums) => rows
+                        .filter_map(|index| Key::new(&Value::Float(nums[index])))
+                        .collect::<HashSet<_>>()
+                        .len(),
+                    // Different codes stand for different texts.
+                    ColumnData::Text { codes, .. } => {
+                        rows.map(|index| codes[index]).collect::<HashSet<_>>().len()
+                    }
+                    ColumnData::Boolean(bits) => rows
+                        .map(|index| bits.get(index))
+                        .collect::<HashSet<_>>()
+                        .len(),
+                };
+                Value::Integer(count as i64)
+            }
+            (AggregateFn::Sum, ColumnData::Integer(nums)) => {
+                let mut sum: Option<i64> = None;
+                for index in rows {
+                    sum = Some(match sum {
+                        None => nums[index],
+                        Some(sum) => (sum.checked_add(nums[index]))
+                            .ok_or_else(|| Error::Overflow(name.clone()))?,
+                    });
+                }
+                sum.map_or(Value::Null, Value::Integer)
+            }
+            (AggregateFn::Sum, ColumnData::Float(nums)) => (rows.map(|index| nums[index]))
+                .reduce(|sum, num| sum + num)
+                .map_or(Value::Null, Value::Float),
+            (AggregateFn::Avg, data) => {
+                let (mut sum, mut count) = (0.0, 0);
+                for index in rows {
+                    sum += match data {
+                        ColumnData::Integer(nums) => nums[index] as f64,
+                        ColumnData::Float(nums) => nums[index],
+                        _ => unreachable!(),
+                    };
+                    count += 1;
+                }
+                match count {
+                    0 => Value::Null,
+                    count => Value::Float(sum / count as f64),
+                }
+            }
+            (AggregateFn::Min, data) => extreme(data, rows, Ordering::Less),
+            (AggregateFn::Max, data) => extreme(data, rows, Ordering::Greater),
+            (AggregateFn::Sum, _) => unreachable!(),
+        })
+    }
+}
+
+/// The first of the smallest or largest values at the given rows, ordered like
+/// `Value::total_cmp`, or `Value::Null` if there are none.
+fn extreme(data: &ColumnData, rows: impl Iterator<Item = usize>, wanted: Ordering) -> Value {
+    fn pick<T>(
+        values: impl Iterator<Item = T>,
+        cmp: impl Fn(&T, &T) -> Ordering,
+        wanted: Ordering,
+    ) -> Option<T> {
+        values.reduce(|best, value| match cmp(&value, &best) == wanted {
+            true => value,
+            false => best,
+        })
+    }
+
+    let value = match data {
+        ColumnData::Integer(nums) => {
+            pick(rows.map(|index| nums[index]), i64::cmp, wanted).map(Value::Integer)
+        }
+        ColumnData::Float(nums) => {
+            pick(rows.map(|index| nums[index]), f64::total_cmp, wanted).map(Value::Float)
+        }
+        ColumnData::Text { dictionary, codes } => pick(
+            rows.map(|index| &dictionary[codes[index] as usize]),
+            |a, b| a.cmp(b),
+            wanted,
+        )
+        .map(|text| Value::Text(text.clone())),
+        ColumnData::Boolean(bits) => {
+            pick(rows.map(|index| bits.get(index)), bool::cmp, wanted).map(Value::Boolean)
+        }
+    };
+    value.unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{Column, insert, update};
+
+    fn people() -> Database {
+        let person =
+            |id, name, score, active, visits| vec![Value::Integer(id), name, score, active, visits];
+        table(
+            vec![
+                Column::new("id", DataType::Integer).primary_key(),
+                Column::new("name", DataType::Text).nullable(),
+                Column::new("score", DataType::Float).nullable(),
+                Column::new("active", DataType::Boolean).nullable(),
+                Column::new("visits", DataType::Integer).nullable(),
+            ],
+            vec![
+                person(
+                    1,
+                    text("Alice"),
+                    Value::Float(95.5),
+                    Value::Boolean(true),
+                    Value::Integer(3),
+                ),
+                person(
+                    2,
+                    text("Bob"),
+                    Value::Float(-0.0),
+                    Value::Boolean(false),
+                    Value::Null,
+                ),
+                person(
+                    3,
+                    Value::Null,
+                    Value::Float(f64::NAN),
+                    Value::Null,
+                    Value::Integer(-7),
+                ),
+                person(
+                    4,
+                    text("alice"),
+                    Value::Null,
+                    Value::Boolean(true),
+                    Value::Integer(3),
+                ),
+                person(
+                    5,
+                    text("Alice"),
+                    Value::Float(0.0),
+                    Value::Boolean(false),
+                    Value::Integer(10),
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let mut bits: Bitmap = (0..130).map(|index| index % 3 == 0).collect();
+        assert_eq!(bits.len(), 130);
+        assert_eq!(bits.count_ones(), 44);
+        assert_eq!(bits.not().count_ones(), 86);
+        assert!(!bits.get(130));
+
+        bits.set(129, true);
+        bits.set(0, false);
+        assert_eq!(bits.iter_ones().take(2).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(bits.iter_ones().last(), Some(129));
+        assert_eq!(bits.and(&bits.not()).count_ones(), 0);
+        assert_eq!(bits.or(&bits.not()), Bitmap::new(130).not());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let db = people();
+        let table = db.to_columnar();
+
+        assert_eq!(table.len(), 5);
+        let ColumnData::Text { dictionary, codes } = table.get_column("name").unwrap().get_data()
+        else {
+            unreachable!();
+        };
+        assert_eq!(dictionary, &["Alice", "Bob", "alice"]);
+        assert_eq!(codes, &[0, 1, 0, 2, 0]);
+        assert_eq!(
+            table
+                .get_column("visits")
+                .unwrap()
+                .get_nulls()
+                .iter_ones()
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(table.get_row(4).as_ref(), db.get_rows().get(4));
+        assert_eq!(table.get_row(5), None);
+
+        let restored = table.to_database().unwrap();
+        assert_eq!(
+            format!("{:?}", restored.get_rows()),
+            format!("{:?}", db.get_rows())
+        );
+        let mut restored = restored;
+        let duplicate = Row::new(vec![
+            Value::Integer(1),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+        ]);
+        assert!(matches!(
+            insert(&mut restored, duplicate),
+            Err(Error::UniqueViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_round_trip_keeps_nulls_in_defaulted_columns() {
+        let mut db = Database::new(Schema::from_columns(vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("visits", DataType::Integer)
+                .nullable()
+                .default(Value::Integer(5)),
+        ]));
+        insert(
+            &mut db,
+            Row::new(vec![Value::Integer(1), Value::Integer(2)]),
+        )
+        .unwrap();
+        update(&mut db, |_| true, &[("visits", Value::Null)]).unwrap();
+
+        let restored = db.to_columnar().to_database().unwrap();
+        assert_eq!(
+            restored.get_rows(),
+            &vec![Row::new(vec![Value::Integer(1), Value::Null])]
+        );
+    }
+
+    #[test]
+    fn test_filters_match_row_filters() {
+        let db = people();
+        let table = db.to_columnar();
+        let filters = [
+            Filter::gt("score", Value::Integer(0)),
+            Filter::le("score", Value::Float(0.0)),
+            Filter::ne("name", Value::Text("Alice".to_string())),
+            Filter::eq("active", Value::Boolean(false)),
+            Filter::lt("visits", Value::Float(3.5)),
+            Filter::eq("visits", Value::Null),
+            Filter::in_list("visits", vec![Value::Integer(10), Value::Null]),
+            Filter::in_list("visits", vec![]),
+            !Filter::in_list("name", vec![Value::Text("Bob".to_string())]),
+            Filter::between("id", Value::Integer(2), Value::Float(4.5)),
+            Filter::is_null("name").or(Filter::is_null("score")),
+            !Filter::gt("visits", Value::Integer(0)),
+            Filter::contains_ignore_case("name", "ALI").and(!Filter::is_null("score")),
+            Filter::like("name", "_l%").or(Filter::eq("active", Value::Boolean(true))),
+            Filter::regex("name", "^[AB]"),
+        ];
+
+        for filter in &filters {
+            let matches = filter.compile(db.get_schema()).unwrap();
+            let expected: Vec<usize> = (0..db.get_rows().len())
+                .filter(|&index| matches(&db.get_rows()[index]))
+                .collect();
+            let found: Vec<usize> = table.filter(filter).unwrap().iter_ones().collect();
+            assert_eq!(found, expected, "{:?}", filter);
+        }
+
+        assert!(matches!(
+            table.filter(&Filter::is_null("email")),
+            Err(Error::UnknownColumn(_))
+        ));
+        assert!(matches!(
+            table.filter(&Filter::eq("name", Value::Integer(1))),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_aggregates_match_group_by() {
+        let db = people();
+        let table = db.to_columnar();
+        let mut aggregates = vec![Aggregate::count_all()];
+        for column in ["id", "name", "score", "active", "visits"] {
+            aggregates.push(Aggregate::count(column));
+            aggregates.push(Aggregate::count_distinct(column));
+            aggregates.push(Aggregate::min(column));
+            aggregates.push(Aggregate::max(column));
+        }
+        for column in ["id", "score", "visits"] {
+            aggregates.push(Aggregate::sum(column));
+            aggregates.push(Aggregate::avg(column));
+        }
+
+        for filter in [
+            Filter::gt("id", Value::Integer(0)),
+            Filter::gt("id", Value::Integer(1)),
+            Filter::gt("id", Value::Integer(9)),
+        ] {
+            let selection = table.filter(&filter).unwrap();
+            for aggregate in &aggregates {
+                let grouped = db
+                    .group_by(&[])
+                    .filter(filter.clone())
+                    .aggregate(aggregate.clone())
+                    .execute()
+                    .unwrap();
+                let expected = &grouped.get_rows()[0].get_values()[0];
+                let found = table.aggregate(aggregate, Some(&selection)).unwrap();
+                assert_eq!(
+                    format!("{:?}", found),
+                    format!("{:?}", expected),
+                    "{}",
+                    aggregate.get_name()
+                );
+            }
+        }
+
+        assert_eq!(
+            table.aggregate(&Aggregate::sum("visits"), None).unwrap(),
+            Value::Integer(9)
+        );
+        assert!(matches!(
+            table.aggregate(&Aggregate::avg("name"), None),
+            Err(Error::TypeMismatch { .. })
+        ));
+
+        let mut big = Database::new(Schema::from_columns(vec![Column::new(
+            "n",
+            DataType::Integer,
+        )]));
+        insert(&mut big, Row::new(vec![Value::Integer(i64::MAX)])).unwrap();
+        insert(&mut big, Row::new(vec![Value::Integer(1)])).unwrap();
+        assert!(matches!(
+            big.to_columnar().aggregate(&Aggregate::sum("n"), None),
+            Err(Error::Overflow(column)) if column == "n"
+        ));
+    }
+}
@@ -1,5 +1,90 @@
+use std::io::BufRead;
+
 use super::database::Database;
-use super::types::Value;
+use super::error::{Error, Result};
+use super::row::Row;
+use super::schema::{Column, Schema};
+use super::types::{DataType, Value};
+
+/// How quote characters are escaped inside quoted fields.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EscapeStyle {
+    /// A quote is escaped by doubling it, as in RFC 4180 (`"say ""hi"""`).
+    Doubled,
+    /// A quote or backslash is escaped with a preceding backslash (`"say \"hi\""`).
+    Backslash,
+}
+
+/// The line terminator written after each record.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineTerminator {
+    Lf,
+    CrLf,
+}
+
+impl LineTerminator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LineTerminator::Lf => "\n",
+            LineTerminator::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Options describing the flavour of a CSV file.
+///
+/// The reader always accepts both `\n` and `\r\n` line endings; `line_terminator` only
+/// affects the writer, and so does `typed_header`.
+#[derive(Clone, Debug)]
+pub struct CsvDialect {
+    pub delimiter: char,
+    pub quote: char,
+    pub escape: EscapeStyle,
+    pub line_terminator: LineTerminator,
+    pub has_header: bool,
+    pub typed_header: bool,
+    pub trim: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect {
+            delimiter: ',',
+            quote: '"',
+            escape: EscapeStyle::Doubled,
+            line_terminator: LineTerminator::Lf,
+            has_header: true,
+            typed_header: false,
+            trim: true,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// The tab-separated values dialect.
+    pub fn tsv() -> Self {
+        CsvDialect {
+            delimiter: '\t',
+            ..CsvDialect::default()
+        }
+    }
+
+    fn is_blank(&self, char: char) -> bool {
+        (char == ' ' || char == '\t') && char != self.delimiter
+    }
+}
+
+/// A field of a CSV record and whether it was quoted.
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+/// A CSV record together with the 1-based line it starts on.
+pub(super) struct Record {
+    line: usize,
+    fields: Vec<Field>,
+}
 
 /// Convert the database to a CSV string.
 ///
@@ -9,29 +94,981 @@ use super::types::Value;
 /// # Returns
 /// A CSV string representation of the database.
 pub fn to_csv(db: &Database) -> String {
+    to_csv_with_dialect(db, &CsvDialect::default())
+}
+
+/// Convert the database to a CSV string with a typed header row.
+///
+/// Every header is written as `name:type` (e.g. `id:int,name:text,score:float,active:bool`),
+/// with a trailing `?` for nullable columns (e.g. `score:float?`), so `Database::from_csv`
+/// restores the exact schema and values without guessing.
+///
+/// Only the name, type and nullability of a column are written: the PRIMARY KEY, UNIQUE,
+/// DEFAULT and CHECK constraints are lost, so `Database::from_csv` restores plain columns.
+/// `Database::to_bytes` keeps all of them but CHECK.
+///
+/// # Arguments
+/// * `db` - The database to convert to a CSV string
+///
+/// # Returns
+/// A CSV string representation of the database with a typed header row.
+pub fn to_csv_typed(db: &Database) -> String {
+    let dialect = CsvDialect {
+        typed_header: true,
+        ..CsvDialect::default()
+    };
+    to_csv_with_dialect(db, &dialect)
+}
+
+/// Convert the database to a CSV string in the given dialect.
+///
+/// # Arguments
+/// * `db` - The database to convert to a CSV string
+/// * `dialect` - The CSV dialect to write
+///
+/// # Returns
+/// A CSV string representation of the database.
+pub fn to_csv_with_dialect(db: &Database, dialect: &CsvDialect) -> String {
     let mut csv = String::new();
 
-    for (column, _) in &db.schema.columns {
-        csv.push_str(column);
-        csv.push(',');
+    if dialect.has_header {
+        push_header(&mut csv, &db.schema, dialect);
     }
 
-    csv.pop();
-    csv.push('\n');
-
     for row in &db.rows {
-        for value in &row.values {
-            match value {
-                Value::Text(char) => csv.push_str(&char),
-                Value::Integer(num) => csv.push_str(&num.to_string()),
-                Value::Float(num) => csv.push_str(&num.to_string()),
-                Value::Boolean(bool) => csv.push_str(&bool.to_string()),
+        push_row(&mut csv, row, dialect);
+    }
+
+    csv
+}
+
+/// Push the header row of the schema, including the line terminator.
+pub(super) fn push_header(csv: &mut String, schema: &Schema, dialect: &CsvDialect) {
+    for (index, column) in schema.columns.iter().enumerate() {
+        if index > 0 {
+            csv.push(dialect.delimiter);
+        }
+        if dialect.typed_header {
+            let nullable = if column.nullable { "?" } else { "" };
+            let header = format!("{}:{}{}", column.name, column.data_type.name(), nullable);
+            push_field(csv, &header, dialect);
+        } else {
+            push_field(csv, &column.name, dialect);
+        }
+    }
+    csv.push_str(dialect.line_terminator.as_str());
+}
+
+/// Push a data row, including the line terminator.
+///
+/// `Value::Null` is written as an empty field, and empty text as an empty quoted field.
+pub(super) fn push_row(csv: &mut String, row: &Row, dialect: &CsvDialect) {
+    for (index, value) in row.values.iter().enumerate() {
+        if index > 0 {
+            csv.push(dialect.delimiter);
+        }
+        match value {
+            Value::Text(text) if text.is_empty() => {
+                csv.push(dialect.quote);
+                csv.push(dialect.quote);
             }
-            csv.push(',');
+            Value::Text(text) => push_field(csv, text, dialect),
+            Value::Integer(num) => csv.push_str(&num.to_string()),
+            Value::Float(num) => csv.push_str(&num.to_string()),
+            Value::Boolean(bool) => csv.push_str(&bool.to_string()),
+            Value::Null => {}
         }
-        csv.pop();
-        csv.push('\n');
     }
+    csv.push_str(dialect.line_terminator.as_str());
+}
 
-    csv
+/// Push a field, quoting it if it would not survive parsing unquoted.
+fn push_field(csv: &mut String, text: &str, dialect: &CsvDialect) {
+    let needs_quotes = text
+        .chars()
+        .any(|c| c == dialect.delimiter || c == dialect.quote || c == '\r' || c == '\n')
+        || (dialect.escape == EscapeStyle::Backslash && text.contains('\\'))
+        || (dialect.trim && text.trim_matches(|c| dialect.is_blank(c)) != text);
+
+    if !needs_quotes {
+        csv.push_str(text);
+        return;
+    }
+
+    csv.push(dialect.quote);
+    for char in text.chars() {
+        match dialect.escape {
+            EscapeStyle::Doubled if char == dialect.quote => csv.push(dialect.quote),
+            EscapeStyle::Backslash if char == dialect.quote || char == '\\' => csv.push('\\'),
+            _ => {}
+        }
+        csv.push(char);
+    }
+    csv.push(dialect.quote);
+}
+
+/// Reads CSV records from a buffered reader one record at a time.
+///
+/// Only the physical lines of the current record are kept in memory, so a record spanning
+/// several lines through quoted line breaks is buffered until its closing quote. Blank lines
+/// between records are skipped unless records have a single field, where a blank line is a
+/// row holding one empty field.
+pub(super) struct RecordReader<R> {
+    reader: R,
+    dialect: CsvDialect,
+    line: usize,
+    buffer: String,
+    skip_blank_lines: bool,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    pub(super) fn new(reader: R, dialect: &CsvDialect) -> Self {
+        RecordReader {
+            reader,
+            dialect: dialect.clone(),
+            line: 0,
+            buffer: String::new(),
+            skip_blank_lines: true,
+        }
+    }
+
+    /// Read the next record.
+    ///
+    /// # Returns
+    /// The next record, or `None` at the end of the input.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if reading fails and `Error::MalformedCsv` if the record is malformed.
+    pub(super) fn next_record(&mut self) -> Result<Option<Record>> {
+        self.buffer.clear();
+        let mut start_line = self.line + 1;
+        let mut in_quotes = false;
+
+        loop {
+            let start = self.buffer.len();
+
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                break;
+            }
+
+            self.line += 1;
+
+            if start == 0 && self.skip_blank_lines && self.is_blank_line() {
+                self.buffer.clear();
+                start_line = self.line + 1;
+                continue;
+            }
+
+            in_quotes = self.scan_quotes(start, in_quotes);
+
+            if !in_quotes {
+                break;
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        parse_record(&self.buffer, start_line, &self.dialect).map(Some)
+    }
+
+    /// Whether the buffer holds a line without any field text.
+    fn is_blank_line(&self) -> bool {
+        self.buffer
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .all(|c| self.dialect.trim && self.dialect.is_blank(c))
+    }
+
+    /// Track whether the buffer from `start` on leaves a quoted field open.
+    ///
+    /// A quote only opens a field at its start, or right after a closing quote when quotes
+    /// are escaped by doubling. A stray quote inside an unquoted field is left for
+    /// `parse_record` to reject instead of buffering the rest of the input.
+    fn scan_quotes(&self, start: usize, mut in_quotes: bool) -> bool {
+        let mut chars = self.buffer[start..].chars();
+        let mut field_start = !in_quotes && start == 0;
+        let mut closed = false;
+
+        while let Some(char) = chars.next() {
+            if in_quotes {
+                if char == '\\' && self.dialect.escape == EscapeStyle::Backslash {
+                    chars.next();
+                } else if char == self.dialect.quote {
+                    in_quotes = false;
+                    closed = true;
+                }
+                continue;
+            }
+
+            if char == self.dialect.quote
+                && (field_start || (closed && self.dialect.escape == EscapeStyle::Doubled))
+            {
+                in_quotes = true;
+            } else if char == self.dialect.delimiter || char == '\n' {
+                field_start = true;
+            } else if !(field_start && self.dialect.trim && self.dialect.is_blank(char)) {
+                field_start = false;
+            }
+            closed = false;
+        }
+
+        in_quotes
+    }
+}
+
+/// Parse a single CSV record following RFC 4180 and the given dialect.
+///
+/// The record ends with `\n` or `\r\n`, fields may be enclosed in quotes to contain
+/// delimiters, line breaks and escaped quotes, and unquoted fields are trimmed if the
+/// dialect asks for it.
+///
+/// # Arguments
+/// * `input` - The text of the record
+/// * `line` - The 1-based line the record starts on
+/// * `dialect` - The CSV dialect to read
+///
+/// # Returns
+/// The parsed record.
+///
+/// # Errors
+/// Returns `Error::MalformedCsv` if a quoted field is not terminated, has characters after
+/// its closing quote, or a quote appears inside an unquoted field.
+fn parse_record(input: &str, mut line: usize, dialect: &CsvDialect) -> Result<Record> {
+    let start_line = line;
+    let mut chars = input.chars().peekable();
+    let mut fields = Vec::new();
+
+    loop {
+        let column = fields.len() + 1;
+        let malformed = |line: usize, message: &str| Error::MalformedCsv {
+            line,
+            column,
+            message: message.to_string(),
+        };
+
+        if dialect.trim {
+            while chars.next_if(|c| dialect.is_blank(*c)).is_some() {}
+        }
+
+        let mut text = String::new();
+        let quoted = chars.next_if_eq(&dialect.quote).is_some();
+
+        if quoted {
+            let quote_line = line;
+            loop {
+                match chars.next() {
+                    Some('\\') if dialect.escape == EscapeStyle::Backslash => match chars.next() {
+                        Some(char) => text.push(char),
+                        None => return Err(malformed(quote_line, "unterminated quoted field")),
+                    },
+                    Some(char)
+                        if char == dialect.quote
+                            && dialect.escape == EscapeStyle::Doubled
+                            && chars.next_if_eq(&dialect.quote).is_some() =>
+                    {
+                        text.push(char)
+                    }
+                    Some(char) if char == dialect.quote => break,
+                    Some(char) => {
+                        if char == '\n' {
+                            line += 1;
+                        }
+                        text.push(char);
+                    }
+                    None => return Err(malformed(quote_line, "unterminated quoted field")),
+                }
+            }
+            if dialect.trim {
+                while chars.next_if(|c| dialect.is_blank(*c)).is_some() {}
+            }
+        } else {
+            while let Some(char) = chars.next_if(|c| *c != dialect.delimiter && *c != '\n') {
+                if char == dialect.quote {
+                    return Err(malformed(line, "unexpected quote in unquoted field"));
+                }
+                text.push(char);
+            }
+            if text.ends_with('\r') {
+                text.pop();
+            }
+            if dialect.trim {
+                let trimmed = text.trim_end_matches(|c| dialect.is_blank(c));
+                text.truncate(trimmed.len());
+            }
+        }
+
+        chars.next_if_eq(&'\r');
+        fields.push(Field { text, quoted });
+
+        match chars.next() {
+            Some(char) if char == dialect.delimiter => continue,
+            Some('\n') | None => break,
+            Some(_) => return Err(malformed(line, "unexpected character after closing quote")),
+        }
+    }
+
+    Ok(Record {
+        line: start_line,
+        fields,
+    })
+}
+
+/// An iterator over the rows of a CSV input that parses one row at a time.
+///
+/// The header row, if the dialect has one, is read when the iterator is created, and each
+/// following record is parsed according to the data types of the schema.
+pub struct CsvRows<R> {
+    records: RecordReader<R>,
+    schema: Schema,
+    positions: Vec<usize>,
+    done: bool,
+}
+
+impl<R: BufRead> CsvRows<R> {
+    /// Create a row iterator over a CSV reader.
+    ///
+    /// Without a schema the columns are taken from the header row as in `Database::from_csv`.
+    /// If the dialect has no header row, the fields of each row follow the schema order.
+    ///
+    /// # Arguments
+    /// * `reader` - The reader to read CSV from
+    /// * `schema` - The schema describing the columns, or `None` to read it from the header row
+    /// * `dialect` - The CSV dialect of the input
+    ///
+    /// # Returns
+    /// A row iterator positioned after the header row.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedCsv` if the header row is missing, malformed, repeats or lacks
+    /// a column or neither a schema nor a header row is available, `Error::UnknownColumn` if a
+    /// header is not a known column and `Error::Io` if reading fails.
+    pub fn new(reader: R, schema: Option<&Schema>, dialect: &CsvDialect) -> Result<Self> {
+        let mut records = RecordReader::new(reader, dialect);
+
+        if !dialect.has_header {
+            let schema = schema.cloned().ok_or_else(|| Error::MalformedCsv {
+                line: 1,
+                column: 1,
+                message: "a schema is required to read CSV without a header row".to_string(),
+            })?;
+
+            records.skip_blank_lines = schema.columns.len() != 1;
+
+            return Ok(CsvRows {
+                records,
+                positions: (0..schema.columns.len()).collect(),
+                schema,
+                done: false,
+            });
+        }
+
+        let Record { line, fields } =
+            records.next_record()?.ok_or_else(|| Error::MalformedCsv {
+                line: 1,
+                column: 1,
+                message: "missing header row".to_string(),
+            })?;
+        let header = fields
+            .into_iter()
+            .map(|field| field.text)
+            .collect::<Vec<String>>();
+        let schema = match schema {
+            Some(schema) => schema.clone(),
+            None => schema_from_header(&header, line)?,
+        };
+        let mut positions: Vec<Option<usize>> = vec![None; schema.columns.len()];
+
+        for (field, header) in header.iter().enumerate() {
+            let name = header
+                .rsplit_once(':')
+                .map_or(header.as_str(), |(name, _)| name);
+            let index = schema.column_index(name)?;
+
+            if positions[index].is_some() {
+                return Err(Error::MalformedCsv {
+                    line,
+                    column: field + 1,
+                    message: format!("duplicate column `{}`", name),
+                });
+            }
+
+            positions[index] = Some(field);
+        }
+
+        if let Some(index) = positions.iter().position(Option::is_none) {
+            return Err(Error::MalformedCsv {
+                line,
+                column: header.len() + 1,
+                message: format!("missing column `{}`", schema.columns[index].name),
+            });
+        }
+
+        records.skip_blank_lines = header.len() != 1;
+
+        Ok(CsvRows {
+            records,
+            schema,
+            positions: positions.into_iter().flatten().collect(),
+            done: false,
+        })
+    }
+
+    /// Get the schema the rows are parsed with.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn row_from_record(&self, Record { line, fields }: Record) -> Result<Row> {
+        if fields.len() != self.positions.len() {
+            return Err(Error::MalformedCsv {
+                line,
+                column: fields.len().min(self.positions.len()) + 1,
+                message: format!(
+                    "expected {} fields, found {}",
+                    self.positions.len(),
+                    fields.len()
+                ),
+            });
+        }
+
+        let mut values = Vec::with_capacity(self.schema.columns.len());
+
+        for (column, &field) in self.schema.columns.iter().zip(&self.positions) {
+            let Field { text, quoted } = &fields[field];
+
+            if text.is_empty() && !quoted && (column.nullable || column.default.is_some()) {
+                values.push(Value::Null);
+                continue;
+            }
+
+            values.push(Value::parse(text, column.data_type).ok_or_else(|| {
+                Error::InvalidCell {
+                    line,
+                    column: field + 1,
+                    name: column.name.clone(),
+                    expected: column.data_type,
+                    text: text.clone(),
+                }
+            })?);
+        }
+
+        Ok(Row { values })
+    }
+}
+
+impl<R: BufRead> Iterator for CsvRows<R> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let row = match self.records.next_record() {
+            Ok(Some(record)) => self.row_from_record(record),
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(error) => Err(error),
+        };
+
+        self.done = row.is_err();
+        Some(row)
+    }
+}
+
+/// Build a schema from a header row of typed `name:type` or demo column headers, found on
+/// the given line.
+fn schema_from_header(header: &[String], line: usize) -> Result<Schema> {
+    let mut columns: Vec<Column> = Vec::new();
+
+    for (field, header) in header.iter().enumerate() {
+        if let Some((name, type_name)) = header.rsplit_once(':') {
+            let (type_name, nullable) = match type_name.strip_suffix('?') {
+                Some(type_name) => (type_name, true),
+                None => (type_name, false),
+            };
+            let data_type = DataType::from_name(type_name).ok_or_else(|| Error::MalformedCsv {
+                line,
+                column: field + 1,
+                message: format!("unknown data type `{}`", type_name),
+            })?;
+            let column = Column::new(name, data_type);
+            columns.push(if nullable { column.nullable() } else { column });
+            continue;
+        }
+
+        match header.as_str() {
+            "id" => columns.push(Column::new("id", DataType::Integer)),
+            "name" => columns.push(Column::new("name", DataType::Text)),
+            "score" => columns.push(Column::new("score", DataType::Float)),
+            "active" => columns.push(Column::new("active", DataType::Boolean)),
+            _ => return Err(Error::UnknownColumn(header.to_string())),
+        }
+    }
+
+    Ok(Schema::from_columns(columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{DataType, Row, Schema, insert};
+
+    fn mixed() -> Database {
+        let row = |id, label, ratio, flag| {
+            vec![
+                Value::Integer(id),
+                text(label),
+                Value::Float(ratio),
+                Value::Boolean(flag),
+            ]
+        };
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("label", DataType::Text),
+                Column::new("ratio", DataType::Float),
+                Column::new("flag", DataType::Boolean),
+            ],
+            vec![
+                row(1, "true", 1.0, false),
+                row(-2, "42", 0.1 + 0.2, true),
+                row(3, "1e3", -1.5e-300, false),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_to_csv_typed_header() {
+        let csv = to_csv_typed(&mixed());
+
+        assert!(csv.starts_with("id:int,label:text,ratio:float,flag:bool\n"));
+    }
+
+    #[test]
+    fn test_to_csv_typed_round_trip() {
+        let db = mixed();
+        let restored = Database::from_csv(&to_csv_typed(&db)).unwrap();
+
+        assert_eq!(restored.get_schema(), db.get_schema());
+        assert_eq!(restored.get_rows(), db.get_rows());
+    }
+
+    #[test]
+    fn test_to_csv_typed_drops_constraints() {
+        let db = Database::new(Schema::from_columns(vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("name", DataType::Text).nullable().unique(),
+            Column::new("score", DataType::Float).default(Value::Float(0.0)),
+        ]));
+        let restored = Database::from_csv(&to_csv_typed(&db)).unwrap();
+
+        assert_eq!(
+            restored.get_schema(),
+            &Schema::from_columns(vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text).nullable(),
+                Column::new("score", DataType::Float),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_to_csv_untyped_header() {
+        let csv = to_csv(&mixed());
+
+        assert!(csv.starts_with("id,label,ratio,flag\n"));
+    }
+
+    fn parse_records(input: &str, dialect: &CsvDialect) -> Result<Vec<Record>> {
+        let mut reader = RecordReader::new(input.as_bytes(), dialect);
+        let mut records = Vec::new();
+
+        while let Some(record) = reader.next_record()? {
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    fn fields(input: &str) -> Vec<Vec<String>> {
+        parse_records(input, &CsvDialect::default())
+            .unwrap()
+            .into_iter()
+            .map(|record| texts(&record))
+            .collect()
+    }
+
+    fn texts(record: &Record) -> Vec<String> {
+        record
+            .fields
+            .iter()
+            .map(|field| field.text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_records_quoted_fields() {
+        let records = fields("a,\"b,c\",\"say \"\"hi\"\"\"\n");
+
+        assert_eq!(records, vec![vec!["a", "b,c", "say \"hi\""]]);
+    }
+
+    #[test]
+    fn test_parse_records_embedded_newline_and_crlf() {
+        let records = parse_records(
+            "id,note\r\n1,\"two\r\nlines\"\r\n2,x\r\n",
+            &CsvDialect::default(),
+        )
+        .unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(texts(&records[1]), vec!["1", "two\r\nlines"]);
+        assert_eq!(records[2].line, 4);
+        assert_eq!(texts(&records[2]), vec!["2", "x"]);
+    }
+
+    #[test]
+    fn test_parse_records_trims_only_unquoted_fields() {
+        let records = fields(" a , \"  b  \" \n");
+
+        assert_eq!(records, vec![vec!["a", "  b  "]]);
+    }
+
+    #[test]
+    fn test_parse_records_unterminated_quote() {
+        let result = parse_records("id,note\n1,\"open\n", &CsvDialect::default());
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_records_text_after_closing_quote() {
+        let result = parse_records("\"a\"b,c\n", &CsvDialect::default());
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 1,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_records_quote_in_unquoted_field() {
+        let result = parse_records("a,b\"c\n", &CsvDialect::default());
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_stray_quote_does_not_open_a_field() {
+        let input = "a,b\"c\n1,2\n\"x\"\"y\",\"\"\n";
+        let mut reader = RecordReader::new(input.as_bytes(), &CsvDialect::default());
+
+        assert!(matches!(
+            reader.next_record(),
+            Err(Error::MalformedCsv { line: 1, .. })
+        ));
+        assert_eq!(
+            texts(&reader.next_record().unwrap().unwrap()),
+            vec!["1", "2"]
+        );
+        assert_eq!(
+            texts(&reader.next_record().unwrap().unwrap()),
+            vec!["x\"y", ""]
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_are_skipped() {
+        let schema = Schema::new(vec![
+            ("id".to_string(), DataType::Integer),
+            ("name".to_string(), DataType::Text),
+        ]);
+        let input = "\nid,name\r\n\r\n1,a\n\n\n2,b\n\n";
+        let db = Database::from_csv_with_schema(input, &schema).unwrap();
+
+        assert_eq!(db.get_rows().len(), 2);
+        assert_eq!(
+            parse_records(input, &CsvDialect::default()).unwrap()[2].line,
+            7
+        );
+        assert!(matches!(
+            Database::from_csv("\n\nid:int,name:string\n1,a\n"),
+            Err(Error::MalformedCsv {
+                line: 3,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_to_csv_quotes_special_text() {
+        let mut db = Database::new(Schema::new(vec![("note".to_string(), DataType::Text)]));
+        for note in ["a,b", "say \"hi\"", "two\nlines", " padded "] {
+            insert(&mut db, Row::new(vec![Value::Text(note.to_string())])).unwrap();
+        }
+
+        assert_eq!(
+            to_csv(&db),
+            "note\n\"a,b\"\n\"say \"\"hi\"\"\"\n\"two\nlines\"\n\" padded \"\n"
+        );
+    }
+
+    /// A small xorshift generator, so the property test is reproducible without extra crates.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn text(&mut self) -> String {
+            const ALPHABET: [char; 12] = [
+                'a', 'Z', '0', ',', '"', '\n', '\r', ' ', '\t', ':', 'é', '🦀',
+            ];
+            (0..self.below(12))
+                .map(|_| ALPHABET[self.below(ALPHABET.len())])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_arbitrary_text() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..500 {
+            let mut db = Database::new(Schema::from_columns(vec![
+                Column::new("id", DataType::Integer),
+                Column::new("a", DataType::Text),
+                Column::new("b", DataType::Text).nullable(),
+                Column::new("ratio", DataType::Float).nullable(),
+            ]));
+            for id in 0..rng.below(5) {
+                let ratio = f64::from_bits(rng.next());
+                let ratio = if ratio.is_nan() { 0.0 } else { ratio };
+                let b = match rng.below(3) {
+                    0 => Value::Null,
+                    _ => Value::Text(rng.text()),
+                };
+                let ratio = match rng.below(3) {
+                    0 => Value::Null,
+                    _ => Value::Float(ratio),
+                };
+                insert(
+                    &mut db,
+                    Row::new(vec![
+                        Value::Integer(id as i64),
+                        Value::Text(rng.text()),
+                        b,
+                        ratio,
+                    ]),
+                )
+                .unwrap();
+            }
+
+            for dialect in dialects() {
+                let csv = to_csv_with_dialect(&db, &dialect);
+                let restored = Database::from_csv_with_dialect(&csv, None, &dialect).unwrap();
+
+                assert_eq!(restored.get_rows().len(), db.get_rows().len(), "{:?}", csv);
+                for (restored, original) in restored.get_rows().iter().zip(db.get_rows()) {
+                    assert_eq!(restored.get_values(), original.get_values(), "{:?}", csv);
+                }
+            }
+        }
+    }
+
+    fn dialects() -> Vec<CsvDialect> {
+        let typed = CsvDialect {
+            typed_header: true,
+            ..CsvDialect::default()
+        };
+        vec![
+            typed.clone(),
+            CsvDialect {
+                delimiter: ';',
+                line_terminator: LineTerminator::CrLf,
+                ..typed.clone()
+            },
+            CsvDialect {
+                typed_header: true,
+                ..CsvDialect::tsv()
+            },
+            CsvDialect {
+                quote: '\'',
+                escape: EscapeStyle::Backslash,
+                trim: false,
+                ..typed
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_csv_with_dialect() {
+        let mut db = Database::new(Schema::new(vec![
+            ("id".to_string(), DataType::Integer),
+            ("note".to_string(), DataType::Text),
+        ]));
+        insert(
+            &mut db,
+            Row::new(vec![
+                Value::Integer(1),
+                Value::Text("a;\"b\"\\".to_string()),
+            ]),
+        )
+        .unwrap();
+
+        let dialect = CsvDialect {
+            delimiter: ';',
+            escape: EscapeStyle::Backslash,
+            line_terminator: LineTerminator::CrLf,
+            has_header: false,
+            ..CsvDialect::default()
+        };
+
+        assert_eq!(
+            to_csv_with_dialect(&db, &dialect),
+            "1;\"a;\\\"b\\\"\\\\\"\r\n"
+        );
+    }
+
+    /// A reader producing an endless CSV file, so a test can only pass by streaming.
+    struct EndlessCsv {
+        next_id: i64,
+        buffer: Vec<u8>,
+        position: usize,
+    }
+
+    impl std::io::Read for EndlessCsv {
+        fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+            if self.position == self.buffer.len() {
+                self.buffer = format!("{},\"row\n{}\"\n", self.next_id, self.next_id).into_bytes();
+                self.position = 0;
+                self.next_id += 1;
+            }
+            let count = out.len().min(self.buffer.len() - self.position);
+            out[..count].copy_from_slice(&self.buffer[self.position..self.position + count]);
+            self.position += count;
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn test_csv_rows_streams_endless_input() {
+        let input = EndlessCsv {
+            next_id: 0,
+            buffer: b"id:int,note:text\n".to_vec(),
+            position: 0,
+        };
+        let rows =
+            CsvRows::new(std::io::BufReader::new(input), None, &CsvDialect::default()).unwrap();
+
+        let last = rows.take(10_000).last().unwrap().unwrap();
+
+        assert_eq!(
+            last.get_values(),
+            &vec![Value::Integer(9_999), Value::Text("row\n9999".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_csv_rows_stops_after_error() {
+        let input = "id:int\n1\nx\n3\n";
+        let mut rows = CsvRows::new(input.as_bytes(), None, &CsvDialect::default()).unwrap();
+
+        assert_eq!(rows.schema().columns[0].name, "id");
+        assert_eq!(rows.schema().columns[0].data_type, DataType::Integer);
+        assert!(rows.next().unwrap().is_ok());
+        assert!(matches!(
+            rows.next(),
+            Some(Err(Error::InvalidCell { line: 3, .. }))
+        ));
+        assert!(rows.next().is_none());
+    }
+
+    fn nullable_names() -> Schema {
+        Schema::from_columns(vec![
+            Column::new("id", DataType::Integer).nullable(),
+            Column::new("name", DataType::Text).nullable(),
+            Column::new("note", DataType::Text),
+        ])
+    }
+
+    #[test]
+    fn test_empty_fields_read_as_null() {
+        let input = "id,name,note\n,,\n2,\"\",\"\"\n";
+        let db = Database::from_csv_with_schema(input, &nullable_names()).unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![Value::Null, Value::Null, Value::Text(String::new())]
+        );
+        assert_eq!(
+            db.get_rows()[1].get_values(),
+            &vec![
+                Value::Integer(2),
+                Value::Text(String::new()),
+                Value::Text(String::new())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_field_in_non_nullable_column() {
+        let schema = Schema::new(vec![("id".to_string(), DataType::Integer)]);
+        let result = Database::from_csv_with_schema("id\n1\n\n", &schema);
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCell {
+                line: 3,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_null_written_as_empty_field() {
+        let mut db = Database::new(nullable_names());
+        insert(
+            &mut db,
+            Row::new(vec![Value::Null, Value::Null, Value::Text(String::new())]),
+        )
+        .unwrap();
+
+        assert_eq!(to_csv(&db), "id,name,note\n,,\"\"\n");
+        assert!(to_csv_typed(&db).starts_with("id:int?,name:text?,note:text\n"));
+    }
 }
@@ -1,17 +1,62 @@
+use std::io::{BufRead, Write};
+
+use super::csv::{CsvDialect, CsvRows, push_header, push_row};
+use super::error::{Error, Result};
+use super::fulltext::TextIndex;
+use super::fuzzy::TrigramIndex;
+use super::index::{Index, IndexKind, UniqueIndex};
+use super::operations::insert;
 use super::row::Row;
 use super::schema::Schema;
-use super::types::{DataType, Value};
+use super::types::{Coercion, DataType};
+use super::wal::Log;
 
+/// A table of rows with a schema, held in memory and optionally stored in a file with
+/// `Database::create` and `Database::open`.
+///
+/// A clone is an in-memory copy: changes to it are not written to the file.
 pub struct Database {
     pub(crate) schema: Schema,
     pub(super) rows: Vec<Row>,
+    pub(super) coercion: Coercion,
+    pub(super) unique: Vec<UniqueIndex>,
+    pub(super) indexes: Vec<Index>,
+    pub(super) text_indexes: Vec<TextIndex>,
+    pub(super) trigram_indexes: Vec<TrigramIndex>,
+    pub(super) log: Option<Log>,
+}
+
+impl Clone for Database {
+    fn clone(&self) -> Self {
+        Database {
+            schema: self.schema.clone(),
+            rows: self.rows.clone(),
+            coercion: self.coercion,
+            unique: self.unique.clone(),
+            indexes: self.indexes.clone(),
+            text_indexes: self.text_indexes.clone(),
+            trigram_indexes: self.trigram_indexes.clone(),
+            log: None,
+        }
+    }
 }
 
 impl Database {
     pub fn new(schema: Schema) -> Self {
+        let unique = (schema.columns.iter().enumerate())
+            .filter(|(_, column)| column.unique)
+            .map(|(index, _)| UniqueIndex::new(index))
+            .collect();
+
         Database {
             schema,
             rows: Vec::new(),
+            coercion: Coercion::Strict,
+            unique,
+            indexes: Vec::new(),
+            text_indexes: Vec::new(),
+            trigram_indexes: Vec::new(),
+            log: None,
         }
     }
 
@@ -23,57 +68,592 @@ impl Database {
         &self.rows
     }
 
+    pub fn get_coercion(&self) -> Coercion {
+        self.coercion
+    }
+
+    /// Set how values of a different type are handled when rows are inserted.
+    ///
+    /// # Arguments
+    /// * `coercion` - The coercion mode used by `insert`
+    pub fn set_coercion(&mut self, coercion: Coercion) {
+        self.coercion = coercion;
+    }
+
+    /// Index a column so that `find_exact`, `find_where` and queries look up matching rows
+    /// instead of scanning all of them; a `IndexKind::BTree` index also serves range filters.
+    ///
+    /// The index is kept up to date by `insert`, `delete` and `update`. Creating an index on
+    /// an indexed column replaces the previous one.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column to index
+    /// * `kind` - The kind of index to build
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist.
+    pub fn create_index(&mut self, column_name: &str, kind: IndexKind) -> Result<()> {
+        let column = self.schema.column_index(column_name)?;
+        let index = Index::new(column, kind, &self.rows);
+
+        self.indexes.retain(|index| index.column != column);
+        self.indexes.push(index);
+        Ok(())
+    }
+
+    /// Remove the index of a column.
+    ///
+    /// # Returns
+    /// Whether the column had an index.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist.
+    pub fn drop_index(&mut self, column_name: &str) -> Result<bool> {
+        let column = self.schema.column_index(column_name)?;
+        let count = self.indexes.len();
+
+        self.indexes.retain(|index| index.column != column);
+        Ok(self.indexes.len() != count)
+    }
+
+    /// The kind of index on a column, if it has one.
+    pub fn get_index_kind(&self, column_name: &str) -> Option<IndexKind> {
+        let column = self.schema.column_index(column_name).ok()?;
+        (self.indexes.iter())
+            .find(|index| index.column == column)
+            .map(Index::kind)
+    }
+
+    /// Build an inverted index of the words of a text column, used by `find_words`,
+    /// `find_prefix` and `search` instead of tokenizing every row on each call.
+    ///
+    /// The index is kept up to date by `insert`, `delete` and `update`. Creating an index on
+    /// an indexed column replaces the previous one.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+    /// if it is not a text column.
+    pub fn create_text_index(&mut self, column_name: &str) -> Result<()> {
+        let column = self.schema.column_index(column_name)?;
+        let found = self.schema.columns[column].data_type;
+
+        if found != DataType::Text {
+            return Err(Error::TypeMismatch {
+                column: column_name.to_string(),
+                expected: DataType::Text,
+                found,
+            });
+        }
+
+        let index = TextIndex::new(column, &self.rows);
+        self.text_indexes.retain(|index| index.column != column);
+        self.text_indexes.push(index);
+        Ok(())
+    }
+
+    /// Remove the text index of a column.
+    ///
+    /// # Returns
+    /// Whether the column had a text index.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist.
+    pub fn drop_text_index(&mut self, column_name: &str) -> Result<bool> {
+        let column = self.schema.column_index(column_name)?;
+        let count = self.text_indexes.len();
+
+        self.text_indexes.retain(|index| index.column != column);
+        Ok(self.text_indexes.len() != count)
+    }
+
+    /// Build an index of the trigrams of a text column, used by `find_similar` instead of
+    /// comparing the query with every row.
+    ///
+    /// The index is kept up to date by `insert`, `delete` and `update`. Creating an index on
+    /// an indexed column replaces the previous one.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+    /// if it is not a text column.
+    pub fn create_trigram_index(&mut self, column_name: &str) -> Result<()> {
+        let column = self.schema.column_index(column_name)?;
+        let found = self.schema.columns[column].data_type;
+
+        if found != DataType::Text {
+            return Err(Error::TypeMismatch {
+                column: column_name.to_string(),
+                expected: DataType::Text,
+                found,
+            });
+        }
+
+        let index = TrigramIndex::new(column, &self.rows);
+        self.trigram_indexes.retain(|index| index.column != column);
+        self.trigram_indexes.push(index);
+        Ok(())
+    }
+
+    /// Remove the trigram index of a column.
+    ///
+    /// # Returns
+    /// Whether the column had a trigram index.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if the column does not exist.
+    pub fn drop_trigram_index(&mut self, column_name: &str) -> Result<bool> {
+        let column = self.schema.column_index(column_name)?;
+        let count = self.trigram_indexes.len();
+
+        self.trigram_indexes.retain(|index| index.column != column);
+        Ok(self.trigram_indexes.len() != count)
+    }
+
     /// Restore database from CSV string.
     ///
+    /// Each header is either typed as `name:type` (see `to_csv_typed`) or one of the demo
+    /// columns `id`, `name`, `score` and `active`; use `Database::from_csv_with_schema` to
+    /// load other untyped columns.
+    ///
     /// # Arguments
     /// * `input` - CSV string to restore database from
     ///
     /// # Returns
     /// A new database restored from the CSV string.
     ///
-    /// # Panics
-    /// Panics if the CSV string is invalid.
-    pub fn from_csv(input: &str) -> Database {
-        let mut columns: Vec<(String, DataType)> = Vec::new();
-        let mut rows: Vec<Row> = Vec::new();
-
-        let headers = input.lines().next().unwrap();
-
-        for header in headers.split(',') {
-            match header {
-                "id" => columns.push(("id".to_string(), DataType::Integer)),
-                "name" => columns.push(("name".to_string(), DataType::Text)),
-                "score" => columns.push(("score".to_string(), DataType::Float)),
-                "active" => columns.push(("active".to_string(), DataType::Boolean)),
-                _ => panic!("Unknown field, {}", header),
-            }
+    /// # Errors
+    /// Returns `Error::MalformedCsv` if the input is not valid CSV, the header row is missing,
+    /// a row has the wrong number of fields or a typed header names an unknown type,
+    /// `Error::UnknownColumn` if an untyped header is not a known column and
+    /// `Error::InvalidCell` if a cell does not match the type of its column.
+    pub fn from_csv(input: &str) -> Result<Database> {
+        Database::from_csv_with_dialect(input, None, &CsvDialect::default())
+    }
+
+    /// Restore database from CSV string using the given schema.
+    ///
+    /// The header row may list the schema columns in any order, and each cell is parsed
+    /// according to the data type of its column.
+    ///
+    /// # Arguments
+    /// * `input` - CSV string to restore database from
+    /// * `schema` - The schema describing the columns of the CSV string
+    ///
+    /// # Returns
+    /// A new database with the given schema restored from the CSV string.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedCsv` if the input is not valid CSV, the header row is missing,
+    /// repeats or lacks a column, or a row has the wrong number of fields,
+    /// `Error::UnknownColumn` if a header is not in the schema and `Error::InvalidCell` if a
+    /// cell does not match the type of its column.
+    pub fn from_csv_with_schema(input: &str, schema: &Schema) -> Result<Database> {
+        Database::from_csv_with_dialect(input, Some(schema), &CsvDialect::default())
+    }
+
+    /// Restore database from CSV string written in the given dialect.
+    ///
+    /// Without a schema the columns are taken from the header row as in `Database::from_csv`.
+    /// If the dialect has no header row, the fields of each row follow the schema order.
+    ///
+    /// # Arguments
+    /// * `input` - CSV string to restore database from
+    /// * `schema` - The schema describing the columns, or `None` to read it from the header row
+    /// * `dialect` - The CSV dialect of the input
+    ///
+    /// # Returns
+    /// A new database restored from the CSV string.
+    ///
+    /// # Errors
+    /// Returns the errors of `Database::from_csv` and `Database::from_csv_with_schema`, and
+    /// `Error::MalformedCsv` if neither a schema nor a header row is available.
+    pub fn from_csv_with_dialect(
+        input: &str,
+        schema: Option<&Schema>,
+        dialect: &CsvDialect,
+    ) -> Result<Database> {
+        Database::read_csv(input.as_bytes(), schema, dialect)
+    }
+
+    /// Read a database from a buffered CSV reader, parsing one row at a time.
+    ///
+    /// # Arguments
+    /// * `reader` - The reader to read CSV from
+    /// * `schema` - The schema describing the columns, or `None` to read it from the header row
+    /// * `dialect` - The CSV dialect of the input
+    ///
+    /// # Returns
+    /// A new database restored from the CSV input.
+    ///
+    /// # Errors
+    /// Returns the errors of `Database::from_csv_with_dialect`, `Error::Io` if reading fails and
+    /// the errors of `insert` if a row violates a constraint of the schema.
+    pub fn read_csv<R: BufRead>(
+        reader: R,
+        schema: Option<&Schema>,
+        dialect: &CsvDialect,
+    ) -> Result<Database> {
+        let rows = CsvRows::new(reader, schema, dialect)?;
+        let mut db = Database::new(rows.schema().clone());
+
+        for row in rows {
+            insert(&mut db, row?)?;
         }
 
-        for row in input.lines().skip(1) {
-            rows.push(Row {
-                values: row
-                    .split(',')
-                    .map(|value| detect_value_type(value.trim()))
-                    .collect(),
-            });
+        Ok(db)
+    }
+
+    /// Write the database as CSV to a writer, one row at a time.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer to write CSV to
+    /// * `dialect` - The CSV dialect to write
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if writing fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W, dialect: &CsvDialect) -> Result<()> {
+        let mut line = String::new();
+
+        if dialect.has_header {
+            push_header(&mut line, &self.schema, dialect);
+            writer.write_all(line.as_bytes())?;
         }
 
-        Database {
-            schema: Schema { columns },
-            rows,
+        for row in &self.rows {
+            line.clear();
+            push_row(&mut line, row, dialect);
+            writer.write_all(line.as_bytes())?;
         }
+
+        writer.flush()?;
+        Ok(())
     }
 }
 
-fn detect_value_type(value: &str) -> Value {
-    if let Ok(b) = value.parse::<bool>() {
-        return Value::Boolean(b);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::{DataType, Error, Value};
+
+    #[test]
+    fn test_from_csv() {
+        let db = Database::from_csv("id,name,score,active\n1,Alice,95.5,true\n").unwrap();
+
+        assert_eq!(db.get_rows().len(), 1);
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![
+                Value::Integer(1),
+                Value::Text("Alice".to_string()),
+                Value::Float(95.5),
+                Value::Boolean(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_parses_by_column_type() {
+        let db = Database::from_csv("id,name,score\n1,true,2\n").unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![
+                Value::Integer(1),
+                Value::Text("true".to_string()),
+                Value::Float(2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_typed_header() {
+        let db = Database::from_csv("id:int,title:text,active\n1,true,true\n").unwrap();
+
+        assert_eq!(
+            db.get_schema()
+                .columns
+                .iter()
+                .map(|column| (column.name.clone(), column.data_type))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id".to_string(), DataType::Integer),
+                ("title".to_string(), DataType::Text),
+                ("active".to_string(), DataType::Boolean),
+            ]
+        );
+        assert_eq!(
+            db.get_rows()[0].get_value(1),
+            Some(&Value::Text("true".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_csv_typed_header_unknown_type() {
+        let result = Database::from_csv("id:int,title:string\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
     }
-    if let Ok(i) = value.parse::<i64>() {
-        return Value::Integer(i);
+
+    #[test]
+    fn test_from_csv_missing_header() {
+        let result = Database::from_csv("");
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 1,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_unknown_header() {
+        let result = Database::from_csv("id,nmae\n1,Alice\n");
+
+        assert!(matches!(result, Err(Error::UnknownColumn(column)) if column == "nmae"));
+    }
+
+    #[test]
+    fn test_from_csv_too_few_fields() {
+        let result = Database::from_csv("id,name,score\n1,Alice,1.5\n2,Bob\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 3,
+                column: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_too_many_fields() {
+        let result = Database::from_csv("id,name\n1,Alice,extra\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
     }
-    if let Ok(f) = value.parse::<f64>() {
-        return Value::Float(f);
+
+    fn orders() -> Schema {
+        Schema::new(vec![
+            ("order_no".to_string(), DataType::Integer),
+            ("customer".to_string(), DataType::Text),
+            ("total".to_string(), DataType::Float),
+            ("paid".to_string(), DataType::Boolean),
+        ])
+    }
+
+    #[test]
+    fn test_from_csv_with_schema() {
+        let input = "order_no,customer,total,paid\n7,ACME,12.5,false\n";
+        let db = Database::from_csv_with_schema(input, &orders()).unwrap();
+
+        assert_eq!(db.get_schema().columns.len(), 4);
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![
+                Value::Integer(7),
+                Value::Text("ACME".to_string()),
+                Value::Float(12.5),
+                Value::Boolean(false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_with_schema_reorders_columns() {
+        let input = "paid,total,customer,order_no\ntrue,3,Initech,8\n";
+        let db = Database::from_csv_with_schema(input, &orders()).unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![
+                Value::Integer(8),
+                Value::Text("Initech".to_string()),
+                Value::Float(3.0),
+                Value::Boolean(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_with_schema_invalid_cell() {
+        let input = "order_no,customer,total,paid\n7,ACME,12.5,false\n8,Initech,lots,true\n";
+        let result = Database::from_csv_with_schema(input, &orders());
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCell {
+                line: 3,
+                column: 3,
+                expected: DataType::Float,
+                ref name,
+                ref text,
+            }) if name == "total" && text == "lots"
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_with_schema_unknown_column() {
+        let input = "order_no,client,total,paid\n";
+        let result = Database::from_csv_with_schema(input, &orders());
+
+        assert!(matches!(result, Err(Error::UnknownColumn(column)) if column == "client"));
+    }
+
+    #[test]
+    fn test_from_csv_with_schema_missing_column() {
+        let input = "order_no,customer,total\n";
+        let result = Database::from_csv_with_schema(input, &orders());
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv { line: 1, message, .. }) if message.contains("paid")
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_with_schema_duplicate_column() {
+        let input = "order_no,customer,total,paid,total\n";
+        let result = Database::from_csv_with_schema(input, &orders());
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 1,
+                column: 5,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_with_dialect_semicolon() {
+        let dialect = CsvDialect {
+            delimiter: ';',
+            ..CsvDialect::default()
+        };
+        let input = "order_no;customer;total;paid\n7;\"ACME; Inc\";12,5;true\n";
+        let result = Database::from_csv_with_dialect(input, Some(&orders()), &dialect);
+
+        assert!(matches!(result, Err(Error::InvalidCell { column: 3, .. })));
+
+        let input = "order_no;customer;total;paid\n7;\"ACME; Inc\";12.5;true\n";
+        let db = Database::from_csv_with_dialect(input, Some(&orders()), &dialect).unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_value(1),
+            Some(&Value::Text("ACME; Inc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_csv_with_dialect_tsv_without_header() {
+        let dialect = CsvDialect {
+            has_header: false,
+            ..CsvDialect::tsv()
+        };
+        let input = "7\tACME, Inc\t12.5\tfalse\n8\t\tx\t0\ttrue\n";
+        let result = Database::from_csv_with_dialect(input, Some(&orders()), &dialect);
+
+        assert!(matches!(
+            result,
+            Err(Error::MalformedCsv {
+                line: 2,
+                column: 5,
+                ..
+            })
+        ));
+
+        let input = "7\tACME, Inc\t12.5\tfalse\n8\t Initech \t0\ttrue\n";
+        let db = Database::from_csv_with_dialect(input, Some(&orders()), &dialect).unwrap();
+
+        assert_eq!(db.get_rows().len(), 2);
+        assert_eq!(
+            db.get_rows()[1].get_value(1),
+            Some(&Value::Text("Initech".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_csv_with_dialect_without_trimming() {
+        let dialect = CsvDialect {
+            trim: false,
+            ..CsvDialect::default()
+        };
+        let db = Database::from_csv_with_dialect("name\n  Alice \n", None, &dialect).unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_value(0),
+            Some(&Value::Text("  Alice ".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_csv_with_dialect_requires_schema_without_header() {
+        let dialect = CsvDialect {
+            has_header: false,
+            ..CsvDialect::default()
+        };
+        let result = Database::from_csv_with_dialect("1,Alice\n", None, &dialect);
+
+        assert!(matches!(result, Err(Error::MalformedCsv { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_read_csv_and_write_csv() {
+        let input = "order_no,customer,total,paid\n7,\"ACME, Inc\",12.5,false\n";
+        let db =
+            Database::read_csv(input.as_bytes(), Some(&orders()), &CsvDialect::default()).unwrap();
+        let mut output = Vec::new();
+
+        db.write_csv(&mut output, &CsvDialect::default()).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), input);
+    }
+
+    struct BrokenPipe;
+
+    impl Write for BrokenPipe {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_csv_io_error() {
+        let db = Database::new(orders());
+        let result = db.write_csv(BrokenPipe, &CsvDialect::default());
+
+        assert!(
+            matches!(result, Err(Error::Io(error)) if error.kind() == std::io::ErrorKind::BrokenPipe)
+        );
+    }
+
+    #[test]
+    fn test_read_csv_invalid_utf8() {
+        let input: &[u8] = b"id,name\n1,\xff\n";
+        let result = Database::read_csv(input, None, &CsvDialect::default());
+
+        assert!(matches!(result, Err(Error::Io(_))));
     }
-    Value::Text(value.to_string())
 }
@@ -0,0 +1,180 @@
+use std::{fmt, io};
+
+use super::types::{DataType, Value};
+
+/// Errors returned by the database operations.
+#[derive(Debug)]
+pub enum Error {
+    /// The column does not exist in the schema.
+    UnknownColumn(String),
+    /// The value does not match the data type of the column.
+    TypeMismatch {
+        column: String,
+        expected: DataType,
+        found: DataType,
+    },
+    /// The column does not accept `Value::Null`.
+    NullViolation(String),
+    /// The value already exists in a UNIQUE or primary key column.
+    UniqueViolation { column: String, value: Value },
+    /// The value fails a CHECK constraint of the column.
+    CheckViolation { column: String, check: String },
+    /// The row has a different number of values than the schema has columns.
+    ArityMismatch { expected: usize, found: usize },
+    /// A CSV cell at the given 1-based line and column is not a valid value of its column type.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        name: String,
+        expected: DataType,
+        text: String,
+    },
+    /// The CSV input is malformed at the given 1-based line and column.
+    MalformedCsv {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A JSON value at the given 1-based line and column does not fit the type of its column.
+    InvalidJsonValue {
+        line: usize,
+        column: usize,
+        name: String,
+        expected: DataType,
+        text: String,
+    },
+    /// The JSON input is malformed at the given 1-based line and column.
+    MalformedJson {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The catalog has no table with the given name.
+    UnknownTable(String),
+    /// The catalog already has a table with the given name.
+    TableExists(String),
+    /// The table name cannot be used as a file name.
+    InvalidTableName(String),
+    /// An integer aggregate over a column does not fit in an `i64`.
+    Overflow(String),
+    /// A SQL statement is invalid at the given 1-based line and column.
+    Sql {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A regular expression is invalid at the given 0-based character position.
+    InvalidRegex {
+        pattern: String,
+        position: usize,
+        message: String,
+    },
+    /// A transaction has no savepoint with the given name.
+    UnknownSavepoint(String),
+    /// Stored data is damaged or not in the expected format.
+    Corrupt(String),
+    /// Reading or writing failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownColumn(column) => write!(f, "unknown column `{}`", column),
+            Error::TypeMismatch {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "type mismatch in column `{}`: expected {:?}, found {:?}",
+                column, expected, found
+            ),
+            Error::NullViolation(column) => write!(f, "column `{}` cannot be null", column),
+            Error::UniqueViolation { column, value } => {
+                write!(
+                    f,
+                    "duplicate value {:?} in unique column `{}`",
+                    value, column
+                )
+            }
+            Error::CheckViolation { column, check } => {
+                write!(f, "check `{}` failed for column `{}`", check, column)
+            }
+            Error::ArityMismatch { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+            Error::InvalidCell {
+                line,
+                column,
+                name,
+                expected,
+                text,
+            } => write!(
+                f,
+                "invalid value `{}` at {}:{} in column `{}`: expected {:?}",
+                text, line, column, name, expected
+            ),
+            Error::MalformedCsv {
+                line,
+                column,
+                message,
+            } => write!(f, "malformed CSV at {}:{}: {}", line, column, message),
+            Error::InvalidJsonValue {
+                line,
+                column,
+                name,
+                expected,
+                text,
+            } => write!(
+                f,
+                "invalid JSON value `{}` at {}:{} in column `{}`: expected {:?}",
+                text, line, column, name, expected
+            ),
+            Error::MalformedJson {
+                line,
+                column,
+                message,
+            } => write!(f, "malformed JSON at {}:{}: {}", line, column, message),
+            Error::UnknownTable(table) => write!(f, "unknown table `{}`", table),
+            Error::TableExists(table) => write!(f, "table `{}` already exists", table),
+            Error::InvalidTableName(table) => write!(f, "invalid table name `{}`", table),
+            Error::Overflow(column) => write!(f, "integer overflow in column `{}`", column),
+            Error::Sql {
+                line,
+                column,
+                message,
+            } => write!(f, "SQL error at {}:{}: {}", line, column, message),
+            Error::InvalidRegex {
+                pattern,
+                position,
+                message,
+            } => write!(
+                f,
+                "invalid regular expression `{}` at {}: {}",
+                pattern, position, message
+            ),
+            Error::UnknownSavepoint(name) => write!(f, "unknown savepoint `{}`", name),
+            Error::Corrupt(message) => write!(f, "corrupt data: {}", message),
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// A result type for the database operations.
+pub type Result<T> = std::result::Result<T, Error>;
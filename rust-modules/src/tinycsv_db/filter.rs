@@ -0,0 +1,587 @@
+use std::cmp::Ordering;
+use std::ops;
+
+use super::error::{Error, Result};
+use super::regex::Regex;
+use super::row::Row;
+use super::schema::Schema;
+use super::types::{DataType, Value};
+
+/// A comparison operator between a column and a value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    pub(crate) fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::Ne => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Le => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// A predicate on the text of a column, used by `Filter::Text`.
+#[derive(Clone, Debug)]
+pub enum TextMatch {
+    /// The text contains the string, ignoring case.
+    ContainsIgnoreCase(String),
+    /// The text starts with the string.
+    StartsWith(String),
+    /// The text ends with the string.
+    EndsWith(String),
+    /// The text matches a SQL `LIKE` pattern, where `%` stands for any sequence of
+    /// characters, `_` for any one character, and `\` escapes the next character.
+    Like(String),
+    /// The text contains a match of a regular expression, see `Regex`.
+    Regex(String),
+}
+
+/// A composable row filter over named columns, evaluated with SQL three-valued logic:
+/// a comparison involving `Value::Null` is unknown, and only rows for which the whole
+/// filter is true match.
+#[derive(Clone, Debug)]
+pub enum Filter {
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: Value,
+    },
+    In {
+        column: String,
+        values: Vec<Value>,
+    },
+    Between {
+        column: String,
+        low: Value,
+        high: Value,
+    },
+    IsNull(String),
+    Text {
+        column: String,
+        pattern: TextMatch,
+    },
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn compare(column: &str, op: CompareOp, value: Value) -> Self {
+        Filter::Compare {
+            column: column.to_string(),
+            op,
+            value,
+        }
+    }
+
+    pub fn eq(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Eq, value)
+    }
+
+    pub fn ne(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Ne, value)
+    }
+
+    pub fn lt(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Lt, value)
+    }
+
+    pub fn le(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Le, value)
+    }
+
+    pub fn gt(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Gt, value)
+    }
+
+    pub fn ge(column: &str, value: Value) -> Self {
+        Filter::compare(column, CompareOp::Ge, value)
+    }
+
+    /// Match rows whose value in the column is one of the given values.
+    pub fn in_list(column: &str, values: Vec<Value>) -> Self {
+        Filter::In {
+            column: column.to_string(),
+            values,
+        }
+    }
+
+    /// Match rows whose value in the column lies in the inclusive range `low..=high`.
+    pub fn between(column: &str, low: Value, high: Value) -> Self {
+        Filter::Between {
+            column: column.to_string(),
+            low,
+            high,
+        }
+    }
+
+    pub fn is_null(column: &str) -> Self {
+        Filter::IsNull(column.to_string())
+    }
+
+    pub fn text(column: &str, pattern: TextMatch) -> Self {
+        Filter::Text {
+            column: column.to_string(),
+            pattern,
+        }
+    }
+
+    pub fn contains_ignore_case(column: &str, text: &str) -> Self {
+        Filter::text(column, TextMatch::ContainsIgnoreCase(text.to_string()))
+    }
+
+    pub fn starts_with(column: &str, prefix: &str) -> Self {
+        Filter::text(column, TextMatch::StartsWith(prefix.to_string()))
+    }
+
+    pub fn ends_with(column: &str, suffix: &str) -> Self {
+        Filter::text(column, TextMatch::EndsWith(suffix.to_string()))
+    }
+
+    /// Match rows whose text in the column matches a SQL `LIKE` pattern, case-sensitively.
+    pub fn like(column: &str, pattern: &str) -> Self {
+        Filter::text(column, TextMatch::Like(pattern.to_string()))
+    }
+
+    /// Match rows whose text in the column contains a match of a regular expression.
+    pub fn regex(column: &str, pattern: &str) -> Self {
+        Filter::text(column, TextMatch::Regex(pattern.to_string()))
+    }
+
+    pub fn and(self, other: Filter) -> Self {
+        Filter::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Filter) -> Self {
+        Filter::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Resolve the columns of the filter against a schema.
+    ///
+    /// # Arguments
+    /// * `schema` - The schema of the rows the filter will be applied to
+    ///
+    /// # Returns
+    /// A predicate closure that tells whether a row matches the filter, usable with
+    /// `delete`, `update` and any other function taking a row predicate.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if a column does not exist, `Error::TypeMismatch`
+    /// if a value cannot be compared with the values of its column or a text predicate is
+    /// applied to a column that is not a text column, and `Error::InvalidRegex` if a
+    /// regular expression is invalid.
+    pub fn compile(&self, schema: &Schema) -> Result<impl Fn(&Row) -> bool + use<>> {
+        let bound = Bound::new(self, schema)?;
+        Ok(move |row: &Row| bound.eval(row) == Some(true))
+    }
+}
+
+impl ops::Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+}
+
+/// A filter with column names resolved to positions.
+enum Bound {
+    Compare(usize, CompareOp, Value),
+    In(usize, Vec<Value>),
+    Between(usize, Value, Value),
+    IsNull(usize),
+    Text(usize, Matcher),
+    And(Box<Bound>, Box<Bound>),
+    Or(Box<Bound>, Box<Bound>),
+    Not(Box<Bound>),
+}
+
+impl Bound {
+    fn new(filter: &Filter, schema: &Schema) -> Result<Bound> {
+        let column = |name: &str, values: &[&Value]| -> Result<usize> {
+            let index = schema.column_index(name)?;
+            let expected = schema.columns[index].data_type;
+
+            for found in values.iter().filter_map(|value| value.data_type()) {
+                if !comparable(expected, found) {
+                    return Err(Error::TypeMismatch {
+                        column: name.to_string(),
+                        expected,
+                        found,
+                    });
+                }
+            }
+
+            Ok(index)
+        };
+
+        Ok(match filter {
+            Filter::Compare {
+                column: name,
+                op,
+                value,
+            } => Bound::Compare(column(name, &[value])?, *op, value.clone()),
+            Filter::In {
+                column: name,
+                values,
+            } => Bound::In(
+                column(name, &values.iter().collect::<Vec<_>>())?,
+                values.clone(),
+            ),
+            Filter::Between {
+                column: name,
+                low,
+                high,
+            } => Bound::Between(column(name, &[low, high])?, low.clone(), high.clone()),
+            Filter::IsNull(name) => Bound::IsNull(column(name, &[])?),
+            Filter::Text {
+                column: name,
+                pattern,
+            } => {
+                let text = Value::Text(String::new());
+                Bound::Text(column(name, &[&text])?, Matcher::new(pattern)?)
+            }
+            Filter::And(a, b) => Bound::And(
+                Box::new(Bound::new(a, schema)?),
+                Box::new(Bound::new(b, schema)?),
+            ),
+            Filter::Or(a, b) => Bound::Or(
+                Box::new(Bound::new(a, schema)?),
+                Box::new(Bound::new(b, schema)?),
+            ),
+            Filter::Not(a) => Bound::Not(Box::new(Bound::new(a, schema)?)),
+        })
+    }
+
+    /// Evaluate the filter on a row, where `None` stands for unknown.
+    fn eval(&self, row: &Row) -> Option<bool> {
+        match self {
+            Bound::Compare(index, op, value) => row.values[*index]
+                .sql_cmp(value)
+                .map(|ordering| op.accepts(ordering)),
+            Bound::In(index, values) => {
+                let value = &row.values[*index];
+                let mut result = Some(false);
+
+                for candidate in values {
+                    match value.sql_cmp(candidate) {
+                        Some(Ordering::Equal) => return Some(true),
+                        Some(_) => {}
+                        None => result = None,
+                    }
+                }
+
+                result
+            }
+            Bound::Between(index, low, high) => {
+                let value = &row.values[*index];
+                let above = value
+                    .sql_cmp(low)
+                    .map(|ordering| ordering != Ordering::Less);
+                let below = value
+                    .sql_cmp(high)
+                    .map(|ordering| ordering != Ordering::Greater);
+                and(above, below)
+            }
+            Bound::IsNull(index) => Some(row.values[*index].is_null()),
+            Bound::Text(index, matcher) => match &row.values[*index] {
+                Value::Text(text) => Some(matcher.matches(text)),
+                _ => None,
+            },
+            Bound::And(a, b) => and(a.eval(row), b.eval(row)),
+            Bound::Or(a, b) => match (a.eval(row), b.eval(row)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Bound::Not(a) => a.eval(row).map(|result| !result),
+        }
+    }
+}
+
+/// A `TextMatch` prepared for matching many texts.
+pub(crate) enum Matcher {
+    /// The lowercased string to find in the lowercased text.
+    ContainsIgnoreCase(String),
+    StartsWith(String),
+    EndsWith(String),
+    Like(Vec<LikeToken>),
+    Regex(Regex),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum LikeToken {
+    Char(char),
+    /// `_`, any one character.
+    One,
+    /// `%`, any sequence of characters.
+    Many,
+}
+
+impl Matcher {
+    pub(crate) fn new(pattern: &TextMatch) -> Result<Matcher> {
+        Ok(match pattern {
+            TextMatch::ContainsIgnoreCase(text) => Matcher::ContainsIgnoreCase(text.to_lowercase()),
+            TextMatch::StartsWith(prefix) => Matcher::StartsWith(prefix.clone()),
+            TextMatch::EndsWith(suffix) => Matcher::EndsWith(suffix.clone()),
+            TextMatch::Like(pattern) => {
+                let mut chars = pattern.chars();
+                let mut tokens = Vec::new();
+                while let Some(char) = chars.next() {
+                    tokens.push(match char {
+                        '%' => LikeToken::Many,
+                        '_' => LikeToken::One,
+                        // A trailing backslash stands for itself.
+                        '\\' => LikeToken::Char(chars.next().unwrap_or('\\')),
+                        char => LikeToken::Char(char),
+                    });
+                }
+                Matcher::Like(tokens)
+            }
+            TextMatch::Regex(pattern) => Matcher::Regex(Regex::new(pattern)?),
+        })
+    }
+
+    pub(crate) fn matches(&self, text: &str) -> bool {
+        match self {
+            Matcher::ContainsIgnoreCase(needle) => text.to_lowercase().contains(needle.as_str()),
+            Matcher::StartsWith(prefix) => text.starts_with(prefix.as_str()),
+            Matcher::EndsWith(suffix) => text.ends_with(suffix.as_str()),
+            Matcher::Like(tokens) => like(tokens, &text.chars().collect::<Vec<_>>()),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Match a whole text against a `LIKE` pattern, backtracking only to the last `%`.
+fn like(tokens: &[LikeToken], text: &[char]) -> bool {
+    let (mut token, mut char) = (0, 0);
+    // The position after the last `%` and the text position it was last tried at.
+    let mut retry: Option<(usize, usize)> = None;
+
+    while char < text.len() {
+        match tokens.get(token) {
+            Some(LikeToken::Many) => {
+                token += 1;
+                retry = Some((token, char));
+            }
+            Some(LikeToken::One) => (token, char) = (token + 1, char + 1),
+            Some(LikeToken::Char(expected)) if *expected == text[char] => {
+                (token, char) = (token + 1, char + 1)
+            }
+            _ => match retry {
+                Some((after, start)) => {
+                    (token, char) = (after, start + 1);
+                    retry = Some((after, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[token..]
+        .iter()
+        .all(|token| *token == LikeToken::Many)
+}
+
+fn and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+fn comparable(a: DataType, b: DataType) -> bool {
+    let numeric = |data_type| matches!(data_type, DataType::Integer | DataType::Float);
+    a == b || (numeric(a) && numeric(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::table;
+    use crate::tinycsv_db::{Column, Database, insert};
+
+    fn scores() -> Database {
+        let score = |id, score| vec![Value::Integer(id), score];
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("score", DataType::Float).nullable(),
+            ],
+            vec![
+                score(1, Value::Float(50.0)),
+                score(2, Value::Float(75.5)),
+                score(3, Value::Null),
+                score(4, Value::Float(99.0)),
+            ],
+        )
+    }
+
+    fn ids(db: &Database, filter: &Filter) -> Vec<i64> {
+        let matches = filter.compile(db.get_schema()).unwrap();
+        db.get_rows()
+            .iter()
+            .filter(|row| matches(row))
+            .map(|row| match row.get_value(0) {
+                Some(Value::Integer(id)) => *id,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let db = scores();
+
+        assert_eq!(
+            ids(&db, &Filter::gt("score", Value::Integer(60))),
+            vec![2, 4]
+        );
+        assert_eq!(
+            ids(&db, &Filter::le("score", Value::Float(75.5))),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(&db, &Filter::ne("score", Value::Float(50.0))),
+            vec![2, 4]
+        );
+        assert_eq!(
+            ids(
+                &db,
+                &Filter::between("id", Value::Integer(2), Value::Integer(3))
+            ),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn test_in_list() {
+        let db = scores();
+        let filter = Filter::in_list("id", vec![Value::Integer(1), Value::Integer(3)]);
+
+        assert_eq!(ids(&db, &filter), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_three_valued_logic() {
+        let db = scores();
+        let low = Filter::lt("score", Value::Float(60.0));
+
+        // NOT (score < 60) is unknown for the NULL score, so row 3 matches neither side.
+        assert_eq!(ids(&db, &low), vec![1]);
+        assert_eq!(ids(&db, &!low.clone()), vec![2, 4]);
+        assert_eq!(
+            ids(&db, &low.clone().or(Filter::is_null("score"))),
+            vec![1, 3]
+        );
+        assert_eq!(
+            ids(
+                &db,
+                &Filter::in_list("id", vec![Value::Integer(9), Value::Null])
+            ),
+            Vec::<i64>::new()
+        );
+        assert_eq!(
+            ids(
+                &db,
+                &!Filter::in_list("id", vec![Value::Integer(1), Value::Null])
+            ),
+            Vec::<i64>::new()
+        );
+    }
+
+    #[test]
+    fn test_and() {
+        let db = scores();
+        let filter =
+            Filter::gt("id", Value::Integer(1)).and(Filter::lt("score", Value::Float(90.0)));
+
+        assert_eq!(ids(&db, &filter), vec![2]);
+    }
+
+    #[test]
+    fn test_text_predicates() {
+        let mut db = Database::new(Schema::from_columns(vec![
+            Column::new("id", DataType::Integer),
+            Column::new("name", DataType::Text).nullable(),
+        ]));
+        let names = [
+            Value::Text("Alice Smith".to_string()),
+            Value::Text("bob_jones".to_string()),
+            Value::Null,
+            Value::Text("ALICIA KEYS".to_string()),
+            Value::Text("50% off".to_string()),
+        ];
+        for (id, name) in (1..).zip(names) {
+            insert(&mut db, Row::new(vec![Value::Integer(id), name])).unwrap();
+        }
+
+        let cases = [
+            (Filter::contains_ignore_case("name", "ALI"), vec![1, 4]),
+            (Filter::starts_with("name", "bob"), vec![2]),
+            (Filter::ends_with("name", "off"), vec![5]),
+            (Filter::like("name", "%Smith"), vec![1]),
+            (Filter::like("name", "A_i%"), vec![1]),
+            (Filter::like("name", "A_I%"), vec![4]),
+            (Filter::like("name", "%s"), vec![2]),
+            (Filter::like("name", "%_%"), vec![1, 2, 4, 5]),
+            (Filter::like("name", "%\\_%"), vec![2]),
+            (Filter::like("name", "%\\%%"), vec![5]),
+            (Filter::like("name", "%o%o%"), vec![2]),
+            (Filter::regex("name", r"^[a-z]+_\w+$"), vec![2]),
+            (Filter::regex("name", r"(?i)ali(ce|cia)\s"), vec![1, 4]),
+            (Filter::regex("name", r"\d+%"), vec![5]),
+            // NOT of an unknown stays unknown, so the NULL name never matches.
+            (!Filter::starts_with("name", "A"), vec![2, 5]),
+        ];
+        for (filter, expected) in cases {
+            assert_eq!(ids(&db, &filter), expected, "{:?}", filter);
+        }
+
+        assert!(matches!(
+            Filter::like("id", "1%").compile(db.get_schema()),
+            Err(Error::TypeMismatch {
+                expected: DataType::Integer,
+                found: DataType::Text,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Filter::regex("name", "a(b").compile(db.get_schema()),
+            Err(Error::InvalidRegex { position: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let db = scores();
+        let unknown = Filter::eq("id", Value::Integer(1)).and(Filter::is_null("grade"));
+        let mismatch = Filter::in_list("id", vec![Value::Integer(1), Value::Text("2".to_string())]);
+
+        assert!(matches!(
+            unknown.compile(db.get_schema()),
+            Err(Error::UnknownColumn(column)) if column == "grade"
+        ));
+        assert!(matches!(
+            mismatch.compile(db.get_schema()),
+            Err(Error::TypeMismatch {
+                expected: DataType::Integer,
+                found: DataType::Text,
+                ..
+            })
+        ));
+    }
+}
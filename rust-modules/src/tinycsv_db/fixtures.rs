@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+
+use super::database::Database;
+use super::operations::insert;
+use super::row::Row;
+use super::schema::{Column, Schema};
+use super::types::{DataType, Value};
+
+/// A fresh, empty directory under the system temporary directory, removed when dropped.
+pub(crate) struct TempDir(pub(crate) PathBuf);
+
+impl TempDir {
+    pub(crate) fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("tinycsv_db_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+pub(crate) fn text(value: &str) -> Value {
+    Value::Text(value.to_string())
+}
+
+/// Build a database with the given columns and insert the rows into it.
+pub(crate) fn table(columns: Vec<Column>, rows: Vec<Vec<Value>>) -> Database {
+    let mut db = Database::new(Schema::from_columns(columns));
+    for values in rows {
+        insert(&mut db, Row::new(values)).unwrap();
+    }
+    db
+}
+
+/// A table with a column of every type, holding the extreme integers, a negative zero and
+/// text that needs escaping in CSV and JSON.
+pub(crate) fn sample() -> Database {
+    let row = |id, name, score, active| vec![Value::Integer(id), text(name), score, active];
+    table(
+        vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("name", DataType::Text),
+            Column::new("score", DataType::Float).nullable(),
+            Column::new("active", DataType::Boolean).unique().nullable(),
+        ],
+        vec![
+            row(i64::MIN, "", Value::Float(1.0), Value::Boolean(true)),
+            row(
+                0,
+                "say \"hi\"\\\n\ttab\u{1}",
+                Value::Float(-0.0),
+                Value::Null,
+            ),
+            row(
+                i64::MAX,
+                "ünïcödé 🦀",
+                Value::Float(1e-300),
+                Value::Boolean(false),
+            ),
+            row(7, "null", Value::Null, Value::Null),
+        ],
+    )
+}
+
+/// A table of three users: Carol has no score, Bob is not active.
+pub(crate) fn users() -> Database {
+    let user = |id, name, score, active| {
+        vec![
+            Value::Integer(id),
+            text(name),
+            score,
+            Value::Boolean(active),
+        ]
+    };
+    table(
+        vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("name", DataType::Text),
+            Column::new("score", DataType::Float).nullable(),
+            Column::new("active", DataType::Boolean).default(Value::Boolean(true)),
+        ],
+        vec![
+            user(1, "Alice", Value::Float(95.5), true),
+            user(2, "Bob", Value::Float(80.0), false),
+            user(3, "Carol", Value::Null, true),
+        ],
+    )
+}
@@ -0,0 +1,545 @@
+use std::collections::BTreeMap;
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::row::Row;
+use super::types::{DataType, Value};
+use super::unicode::base_char;
+
+/// How often a term occurs in the text of one row.
+#[derive(Clone, Copy, Debug)]
+struct Posting {
+    position: usize,
+    count: u32,
+}
+
+/// An inverted index of the words of a text column.
+///
+/// Terms are kept in a `BTreeMap` so prefix lookups are a range scan, and each term lists
+/// its rows in ascending position.
+#[derive(Clone, Debug)]
+pub(crate) struct TextIndex {
+    pub(crate) column: usize,
+    postings: BTreeMap<String, Vec<Posting>>,
+    /// The number of terms of each row, `0` for `Value::Null`.
+    lengths: Vec<u32>,
+    /// The number of rows with at least one term.
+    documents: usize,
+}
+
+impl TextIndex {
+    pub(crate) fn new(column: usize, rows: &[Row]) -> Self {
+        let mut index = TextIndex {
+            column,
+            postings: BTreeMap::new(),
+            lengths: Vec::with_capacity(rows.len()),
+            documents: 0,
+        };
+        for (position, row) in rows.iter().enumerate() {
+            index.push(row, position);
+        }
+        index
+    }
+
+    /// Add a row stored after every indexed row.
+    pub(crate) fn push(&mut self, row: &Row, position: usize) {
+        let terms = self.terms(row);
+        self.lengths.push(terms.values().sum());
+        self.documents += usize::from(!terms.is_empty());
+
+        for (term, count) in terms {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push(Posting { position, count });
+        }
+    }
+
+    /// Forget the rows at the given ascending positions and shift the later rows down.
+    pub(crate) fn remove(&mut self, deleted: &[usize]) {
+        if deleted.is_empty() {
+            return;
+        }
+        let shift = |position: usize| position - deleted.partition_point(|&old| old < position);
+
+        for postings in self.postings.values_mut() {
+            postings.retain_mut(|posting| {
+                if deleted.binary_search(&posting.position).is_ok() {
+                    return false;
+                }
+                posting.position = shift(posting.position);
+                true
+            });
+        }
+        self.postings.retain(|_, postings| !postings.is_empty());
+
+        let mut position = 0;
+        self.lengths.retain(|&length| {
+            let kept = deleted.binary_search(&position).is_err();
+            self.documents -= usize::from(!kept && length > 0);
+            position += 1;
+            kept
+        });
+    }
+
+    /// Put back rows forgotten by `remove` at their former ascending positions `restored`
+    /// and shift the later rows up.
+    pub(crate) fn restore(&mut self, restored: &[usize], rows: &[Row]) {
+        if restored.is_empty() {
+            return;
+        }
+        let gaps: Vec<usize> = (restored.iter().enumerate())
+            .map(|(index, &position)| position - index)
+            .collect();
+
+        for postings in self.postings.values_mut() {
+            for posting in postings {
+                posting.position += gaps.partition_point(|&gap| gap <= posting.position);
+            }
+        }
+
+        let mut lengths = Vec::with_capacity(self.lengths.len() + restored.len());
+        let mut kept = std::mem::take(&mut self.lengths).into_iter();
+        for (&position, row) in restored.iter().zip(rows) {
+            lengths.extend(kept.by_ref().take(position - lengths.len()));
+            let terms = self.terms(row);
+            lengths.push(terms.values().sum());
+            self.documents += usize::from(!terms.is_empty());
+
+            for (term, count) in terms {
+                let postings = self.postings.entry(term).or_default();
+                let at = postings.partition_point(|posting| posting.position < position);
+                postings.insert(at, Posting { position, count });
+            }
+        }
+        lengths.extend(kept);
+        self.lengths = lengths;
+    }
+
+    /// Re-index the row at a position whose text changed from `old` to `new`.
+    pub(crate) fn replace(&mut self, position: usize, old: &Row, new: &Row) {
+        for term in self.terms(old).into_keys() {
+            if let Some(postings) = self.postings.get_mut(&term) {
+                postings.retain(|posting| posting.position != position);
+                if postings.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+
+        let terms = self.terms(new);
+        let length = terms.values().sum();
+        self.documents =
+            self.documents + usize::from(length > 0) - usize::from(self.lengths[position] > 0);
+        self.lengths[position] = length;
+
+        for (term, count) in terms {
+            let postings = self.postings.entry(term).or_default();
+            let at = postings.partition_point(|posting| posting.position < position);
+            postings.insert(at, Posting { position, count });
+        }
+    }
+
+    fn terms(&self, row: &Row) -> BTreeMap<String, u32> {
+        let mut terms = BTreeMap::new();
+        if let Value::Text(text) = &row.values[self.column] {
+            for term in tokenize(text) {
+                *terms.entry(term).or_insert(0) += 1;
+            }
+        }
+        terms
+    }
+
+    /// The postings of every term starting with `prefix`, or only of `prefix` itself.
+    fn lookup(&self, term: &str, prefix: bool) -> Vec<&[Posting]> {
+        match prefix {
+            true => self
+                .postings
+                .range(term.to_string()..)
+                .take_while(|(key, _)| key.starts_with(term))
+                .map(|(_, postings)| postings.as_slice())
+                .collect(),
+            false => self
+                .postings
+                .get(term)
+                .map(Vec::as_slice)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// The ascending positions of the rows with a term matching every query term.
+    fn positions(&self, terms: &[String], prefix: bool) -> Vec<usize> {
+        let mut result: Option<Vec<usize>> = None;
+
+        for term in terms {
+            let mut positions: Vec<usize> = (self.lookup(term, prefix).into_iter())
+                .flat_map(|postings| postings.iter().map(|posting| posting.position))
+                .collect();
+            positions.sort_unstable();
+            positions.dedup();
+
+            result = Some(match result {
+                Some(result) => result
+                    .into_iter()
+                    .filter(|position| positions.binary_search(position).is_ok())
+                    .collect(),
+                None => positions,
+            });
+        }
+
+        result.unwrap_or_default()
+    }
+
+    /// The TF-IDF score of every row with at least one query term.
+    fn scores(&self, terms: &[String]) -> BTreeMap<usize, f64> {
+        let mut scores = BTreeMap::new();
+
+        for term in terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let idf = ((self.documents as f64 + 1.0) / (postings.len() as f64 + 1.0)).ln() + 1.0;
+            for posting in postings {
+                let tf = posting.count as f64 / self.lengths[posting.position] as f64;
+                *scores.entry(posting.position).or_insert(0.0) += tf * idf;
+            }
+        }
+
+        scores
+    }
+}
+
+/// Split text into search terms: runs of letters and digits, lower-cased, with diacritics
+/// removed and ligatures such as `ß` and `æ` spelled out.
+///
+/// Precomposed letters are decomposed canonically and their combining diacritical marks
+/// dropped, as are marks written separately, so `"Việt"` gives the same term `"viet"`
+/// whether it is encoded precomposed or decomposed.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+
+    for char in text.chars() {
+        if is_combining_mark(char) {
+            continue;
+        }
+        if !char.is_alphanumeric() {
+            if !term.is_empty() {
+                terms.push(std::mem::take(&mut term));
+            }
+            continue;
+        }
+        let char = base_char(char);
+        match fold(char) {
+            Some(folded) => term.push_str(folded),
+            None => term.extend(char.to_lowercase()),
+        }
+    }
+
+    if !term.is_empty() {
+        terms.push(term);
+    }
+    terms
+}
+
+/// The combining diacritical marks dropped from terms, in the blocks listed at
+/// `unicode::DECOMPOSITIONS`.
+fn is_combining_mark(char: char) -> bool {
+    matches!(
+        char,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// The folded spelling of a Latin letter with a stroke or a ligature, which have no
+/// canonical decomposition.
+fn fold(char: char) -> Option<&'static str> {
+    let folded = match char {
+        'Æ' | 'æ' => "ae",
+        'Ð' | 'ð' | 'Đ' | 'đ' => "d",
+        'Ħ' | 'ħ' => "h",
+        'ı' => "i",
+        'Ĳ' | 'ĳ' => "ij",
+        'Ŀ' | 'ŀ' | 'Ł' | 'ł' => "l",
+        'ŉ' => "n",
+        'Ø' | 'ø' => "o",
+        'Œ' | 'œ' => "oe",
+        'ß' => "ss",
+        'Ŧ' | 'ŧ' => "t",
+        'Þ' | 'þ' => "th",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Use the text index of a column, or index the rows on the fly if the column has none.
+fn with_index<T>(
+    db: &Database,
+    column_name: &str,
+    search: impl FnOnce(&TextIndex) -> T,
+) -> Result<T> {
+    let column = db.schema.column_index(column_name)?;
+    let found = db.schema.columns[column].data_type;
+
+    if found != DataType::Text {
+        return Err(Error::TypeMismatch {
+            column: column_name.to_string(),
+            expected: DataType::Text,
+            found,
+        });
+    }
+
+    Ok(
+        match db.text_indexes.iter().find(|index| index.column == column) {
+            Some(index) => search(index),
+            None => search(&TextIndex::new(column, &db.rows)),
+        },
+    )
+}
+
+/// Find rows whose text contains every word of a query, ignoring case and diacritics, and
+/// treating precomposed and decomposed letters alike.
+///
+/// Words are compared whole, so `"run"` does not match `"running"`; see `find_prefix`.
+/// A text index created with `Database::create_text_index` answers the query without
+/// scanning the rows.
+///
+/// # Arguments
+/// * `db` - The database to search in
+/// * `column_name` - The name of the text column to search in
+/// * `query` - The words to search for
+///
+/// # Returns
+/// The matching rows in database order; none if the query has no words.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+/// if the column is not a text column.
+pub fn find_words<'a>(db: &'a Database, column_name: &str, query: &str) -> Result<Vec<&'a Row>> {
+    let positions = with_index(db, column_name, |index| {
+        index.positions(&tokenize(query), false)
+    })?;
+
+    Ok(positions
+        .into_iter()
+        .map(|position| &db.rows[position])
+        .collect())
+}
+
+/// Find rows with a word starting with each word of a query, compared like in `find_words`,
+/// for search-as-you-type.
+///
+/// # Errors
+/// Returns the errors of `find_words`.
+pub fn find_prefix<'a>(db: &'a Database, column_name: &str, query: &str) -> Result<Vec<&'a Row>> {
+    let positions = with_index(db, column_name, |index| {
+        index.positions(&tokenize(query), true)
+    })?;
+
+    Ok(positions
+        .into_iter()
+        .map(|position| &db.rows[position])
+        .collect())
+}
+
+/// Rank rows by how well their text matches a query, using TF-IDF.
+///
+/// A row scores the sum over the query words it contains of the share of its words that
+/// are that word, times `ln((n + 1) / (m + 1)) + 1`, where `n` counts the rows with text
+/// and `m` those containing the word. Rare words therefore weigh more than common ones.
+///
+/// # Returns
+/// The rows containing at least one query word with their scores, best first; rows with
+/// equal scores keep database order.
+///
+/// # Errors
+/// Returns the errors of `find_words`.
+pub fn search<'a>(db: &'a Database, column_name: &str, query: &str) -> Result<Vec<(&'a Row, f64)>> {
+    let scores = with_index(db, column_name, |index| index.scores(&tokenize(query)))?;
+    let mut ranked: Vec<(&Row, f64)> = scores
+        .into_iter()
+        .map(|(position, score)| (&db.rows[position], score))
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{Column, delete, insert, update};
+
+    fn articles() -> Database {
+        let bodies = [
+            text("Crème brûlée recipe"),
+            text("The CAFÉ serves creme brulee and coffee"),
+            Value::Null,
+            text("Coffee, coffee and more coffee"),
+            text("Straße und Strasse"),
+        ];
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("body", DataType::Text).nullable(),
+            ],
+            (0..)
+                .zip(bodies)
+                .map(|(id, body)| vec![Value::Integer(id), body])
+                .collect(),
+        )
+    }
+
+    fn ids(rows: Vec<&Row>) -> Vec<i64> {
+        rows.iter()
+            .map(|row| match row.get_value(0) {
+                Some(Value::Integer(id)) => *id,
+                other => panic!("unexpected id {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Crème BRÛLÉE, naïve-café! Straße Œuvre ÆON 42"),
+            vec![
+                "creme", "brulee", "naive", "cafe", "strasse", "oeuvre", "aeon", "42"
+            ]
+        );
+        assert_eq!(tokenize("cafe\u{301} Ångström"), vec!["cafe", "angstrom"]);
+        assert_eq!(tokenize(" ,;- "), Vec::<String>::new());
+        assert_eq!(tokenize("Hạ Nội ș Ώρα"), vec!["ha", "noi", "s", "ωρα"]);
+        for viet in ["Việt", "Vie\u{323}\u{302}t", "Vi\u{1EB9}\u{302}t", "VIỆT"] {
+            assert_eq!(tokenize(viet), vec!["viet"]);
+        }
+    }
+
+    #[test]
+    fn test_find_words_and_prefix() {
+        let mut db = articles();
+
+        for indexed in [false, true] {
+            if indexed {
+                db.create_text_index("body").unwrap();
+            }
+            assert_eq!(
+                ids(find_words(&db, "body", "creme BRULEE").unwrap()),
+                vec![0, 1]
+            );
+            assert_eq!(
+                ids(find_words(&db, "body", "coffee cafe").unwrap()),
+                vec![1]
+            );
+            assert_eq!(ids(find_words(&db, "body", "strasse").unwrap()), vec![4]);
+            assert_eq!(
+                ids(find_words(&db, "body", "coff").unwrap()),
+                Vec::<i64>::new()
+            );
+            assert_eq!(ids(find_words(&db, "body", "").unwrap()), Vec::<i64>::new());
+            assert_eq!(ids(find_prefix(&db, "body", "coff").unwrap()), vec![1, 3]);
+            assert_eq!(ids(find_prefix(&db, "body", "c br").unwrap()), vec![0, 1]);
+        }
+    }
+
+    #[test]
+    fn test_search_ranks_by_tf_idf() {
+        let db = articles();
+        let ranked = search(&db, "body", "coffee recipe").unwrap();
+        let ranked: Vec<(i64, f64)> = ranked
+            .into_iter()
+            .map(|(row, score)| (ids(vec![row])[0], score))
+            .collect();
+
+        assert_eq!(
+            ranked.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![3, 0, 1]
+        );
+        assert!(ranked.windows(2).all(|pair| pair[0].1 > pair[1].1));
+
+        // "recipe" is in 1 of 4 documents, 3 words long: 1/3 * (ln(5/2) + 1).
+        let expected = ((5.0f64 / 2.0).ln() + 1.0) / 3.0;
+        assert!((ranked[1].1 - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_index_is_maintained() {
+        let mut indexed = articles();
+        let mut scanned = articles();
+        indexed.create_text_index("body").unwrap();
+
+        let check = |indexed: &Database, scanned: &Database| {
+            for query in ["coffee", "creme", "strasse", "tea", "c"] {
+                assert_eq!(
+                    find_words(indexed, "body", query).unwrap(),
+                    find_words(scanned, "body", query).unwrap()
+                );
+                assert_eq!(
+                    find_prefix(indexed, "body", query).unwrap(),
+                    find_prefix(scanned, "body", query).unwrap()
+                );
+                assert_eq!(
+                    search(indexed, "body", query).unwrap(),
+                    search(scanned, "body", query).unwrap()
+                );
+            }
+        };
+
+        for db in [&mut indexed, &mut scanned] {
+            delete(db, |row| row.get_value(0) == Some(&Value::Integer(1))).unwrap();
+            insert(
+                db,
+                Row::new(vec![
+                    Value::Integer(5),
+                    Value::Text("Tea or coffee?".to_string()),
+                ]),
+            )
+            .unwrap();
+        }
+        check(&indexed, &scanned);
+
+        for db in [&mut indexed, &mut scanned] {
+            update(
+                db,
+                |row| row.get_value(0) == Some(&Value::Integer(3)),
+                &[("body", Value::Text("Green tea".to_string()))],
+            )
+            .unwrap();
+            update(
+                db,
+                |row| row.get_value(0) == Some(&Value::Integer(2)),
+                &[("body", Value::Text("Crème".to_string()))],
+            )
+            .unwrap();
+        }
+        check(&indexed, &scanned);
+        assert_eq!(
+            ids(find_words(&indexed, "body", "tea").unwrap()),
+            vec![3, 5]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut db = articles();
+
+        assert!(matches!(
+            find_words(&db, "id", "1"),
+            Err(Error::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            search(&db, "title", "x"),
+            Err(Error::UnknownColumn(_))
+        ));
+        assert!(matches!(
+            db.create_text_index("id"),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::HashMap;
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::fulltext::tokenize;
+use super::row::Row;
+use super::types::{DataType, Value};
+
+/// How `find_fuzzy` counts the edits between two texts.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EditDistance {
+    /// Insertions, deletions and substitutions of one character.
+    Levenshtein,
+    /// Like `Levenshtein`, also counting a swap of two adjacent characters as one edit.
+    Damerau,
+}
+
+/// The Levenshtein distance between two texts: the fewest insertions, deletions and
+/// substitutions of one character turning one into the other.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// The Damerau-Levenshtein distance between two texts, where swapping two adjacent
+/// characters also counts as one edit.
+///
+/// This is the optimal string alignment variant: a swapped pair is not edited again, so
+/// `"ca"` is 3 edits away from `"abc"` rather than 2.
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// The trigram similarity of two texts, from `0.0` for nothing in common to `1.0`.
+///
+/// Each word, lower-cased and folded like in `find_words`, is padded with two spaces in
+/// front and one behind and cut into its runs of three characters. The similarity is the
+/// number of trigrams the texts share divided by the number of distinct trigrams of both, so
+/// it is forgiving of typos and of words in a different order.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::trigram_similarity;
+///
+/// assert!(trigram_similarity("Alise", "Alice") > 0.3);
+/// assert_eq!(trigram_similarity("Smith John", "john smith"), 1.0);
+/// ```
+pub fn trigram_similarity(a: &str, b: &str) -> f64 {
+    similarity(&trigrams(a), &trigrams(b))
+}
+
+type Trigram = [char; 3];
+
+/// The sorted distinct trigrams of a text.
+fn trigrams(text: &str) -> Vec<Trigram> {
+    let mut trigrams = Vec::new();
+
+    for word in tokenize(text) {
+        let chars: Vec<char> = ("  ".chars()).chain(word.chars()).chain([' ']).collect();
+        trigrams.extend(
+            chars
+                .windows(3)
+                .map(|window| [window[0], window[1], window[2]]),
+        );
+    }
+
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+fn similarity(a: &[Trigram], b: &[Trigram]) -> f64 {
+    let shared = a
+        .iter()
+        .filter(|trigram| b.binary_search(trigram).is_ok())
+        .count();
+    ratio(shared, a.len() + b.len() - shared)
+}
+
+fn ratio(shared: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => shared as f64 / total as f64,
+    }
+}
+
+/// An index of the trigrams of a text column, so similar texts are found by counting
+/// shared trigrams instead of comparing the query with every row.
+#[derive(Clone, Debug)]
+pub(crate) struct TrigramIndex {
+    pub(crate) column: usize,
+    /// The ascending positions of the rows containing each trigram.
+    postings: HashMap<Trigram, Vec<usize>>,
+    /// The number of distinct trigrams of each row.
+    counts: Vec<usize>,
+}
+
+impl TrigramIndex {
+    pub(crate) fn new(column: usize, rows: &[Row]) -> Self {
+        let mut index = TrigramIndex {
+            column,
+            postings: HashMap::new(),
+            counts: Vec::with_capacity(rows.len()),
+        };
+        for (position, row) in rows.iter().enumerate() {
+            index.push(row, position);
+        }
+        index
+    }
+
+    fn trigrams(&self, row: &Row) -> Vec<Trigram> {
+        match &row.values[self.column] {
+            Value::Text(text) => trigrams(text),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Add a row stored after every indexed row.
+    pub(crate) fn push(&mut self, row: &Row, position: usize) {
+        let trigrams = self.trigrams(row);
+        self.counts.push(trigrams.len());
+
+        for trigram in trigrams {
+            self.postings.entry(trigram).or_default().push(position);
+        }
+    }
+
+    /// Forget the rows at the given ascending positions and shift the later rows down.
+    pub(crate) fn remove(&mut self, deleted: &[usize]) {
+        if deleted.is_empty() {
+            return;
+        }
+        let shift = |position: usize| position - deleted.partition_point(|&old| old < position);
+
+        for positions in self.postings.values_mut() {
+            positions.retain_mut(|position| {
+                if deleted.binary_search(position).is_ok() {
+                    return false;
+                }
+                *position = shift(*position);
+                true
+            });
+        }
+        self.postings.retain(|_, positions| !positions.is_empty());
+
+        let mut position = 0;
+        self.counts.retain(|_| {
+            position += 1;
+            deleted.binary_search(&(position - 1)).is_err()
+        });
+    }
+
+    /// Put back rows forgotten by `remove` at their former ascending positions `restored`
+    /// and shift the later rows up.
+    pub(crate) fn restore(&mut self, restored: &[usize], rows: &[Row]) {
+        if restored.is_empty() {
+            return;
+        }
+        let gaps: Vec<usize> = (restored.iter().enumerate())
+            .map(|(index, &position)| position - index)
+            .collect();
+
+        for positions in self.postings.values_mut() {
+            for position in positions {
+                *position += gaps.partition_point(|&gap| gap <= *position);
+            }
+        }
+
+        let mut counts = Vec::with_capacity(self.counts.len() + restored.len());
+        let mut kept = std::mem::take(&mut self.counts).into_iter();
+        for (&position, row) in restored.iter().zip(rows) {
+            counts.extend(kept.by_ref().take(position - counts.len()));
+            let trigrams = self.trigrams(row);
+            counts.push(trigrams.len());
+
+            for trigram in trigrams {
+                let positions = self.postings.entry(trigram).or_default();
+                let at = positions.partition_point(|&found| found < position);
+                positions.insert(at, position);
+            }
+        }
+        counts.extend(kept);
+        self.counts = counts;
+    }
+
+    /// Re-index the row at a position whose text changed from `old` to `new`.
+    pub(crate) fn replace(&mut self, position: usize, old: &Row, new: &Row) {
+        for trigram in self.trigrams(old) {
+            if let Some(positions) = self.postings.get_mut(&trigram) {
+                positions.retain(|&found| found != position);
+                if positions.is_empty() {
+                    self.postings.remove(&trigram);
+                }
+            }
+        }
+
+        let trigrams = self.trigrams(new);
+        self.counts[position] = trigrams.len();
+
+        for trigram in trigrams {
+            let positions = self.postings.entry(trigram).or_default();
+            let at = positions.partition_point(|&found| found < position);
+            positions.insert(at, position);
+        }
+    }
+
+    /// The position and similarity of every row sharing a trigram with the query and at
+    /// least `threshold` similar to it, in ascending position.
+    fn similar(&self, query: &str, threshold: f64) -> Vec<(usize, f64)> {
+        let query = trigrams(query);
+        let mut shared: HashMap<usize, usize> = HashMap::new();
+
+        for trigram in &query {
+            for &position in self.postings.get(trigram).into_iter().flatten() {
+                *shared.entry(position).or_insert(0) += 1;
+            }
+        }
+
+        let mut similar: Vec<(usize, f64)> = shared
+            .into_iter()
+            .map(|(position, shared)| {
+                let total = query.len() + self.counts[position] - shared;
+                (position, ratio(shared, total))
+            })
+            .filter(|(_, similarity)| *similarity >= threshold)
+            .collect();
+        similar.sort_unstable_by_key(|(position, _)| *position);
+        similar
+    }
+}
+
+/// The position of a column, which must hold text.
+fn text_column(db: &Database, column_name: &str) -> Result<usize> {
+    let column = db.schema.column_index(column_name)?;
+    let found = db.schema.columns[column].data_type;
+
+    if found != DataType::Text {
+        return Err(Error::TypeMismatch {
+            column: column_name.to_string(),
+            expected: DataType::Text,
+            found,
+        });
+    }
+
+    Ok(column)
+}
+
+/// Find rows whose text is within a number of edits of a query, to forgive misspellings
+/// such as `"Alise"` for `"Alice"`.
+///
+/// The whole text of each row is compared with the query, both lower-cased and folded like
+/// in `find_words`, with punctuation and repeated spaces removed.
+///
+/// # Arguments
+/// * `db` - The database to search in
+/// * `column_name` - The name of the text column to search in
+/// * `query` - The text to search for
+/// * `metric` - How edits are counted
+/// * `max_distance` - The largest number of edits a matching row may be away
+///
+/// # Returns
+/// The matching rows with their distances, closest first; rows at equal distance keep
+/// database order.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+/// if the column is not a text column.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::{
+///     Column, DataType, Database, EditDistance, Row, Schema, Value, find_fuzzy, insert,
+/// };
+///
+/// let mut db = Database::new(Schema::from_columns(vec![Column::new("name", DataType::Text)]));
+/// for name in ["Alice", "Alicia", "Bob"] {
+///     insert(&mut db, Row::new(vec![Value::Text(name.to_string())])).unwrap();
+/// }
+///
+/// let found = find_fuzzy(&db, "name", "alise", EditDistance::Levenshtein, 3).unwrap();
+/// let found: Vec<_> = found.iter().map(|(row, distance)| (row.get_value(0), *distance)).collect();
+/// assert_eq!(
+///     found,
+///     vec![
+///         (Some(&Value::Text("Alice".to_string())), 1),
+///         (Some(&Value::Text("Alicia".to_string())), 3),
+///     ]
+/// );
+/// ```
+pub fn find_fuzzy<'a>(
+    db: &'a Database,
+    column_name: &str,
+    query: &str,
+    metric: EditDistance,
+    max_distance: usize,
+) -> Result<Vec<(&'a Row, usize)>> {
+    let column = text_column(db, column_name)?;
+    let query = tokenize(query).join(" ");
+    let length = query.chars().count();
+
+    let mut found: Vec<(&Row, usize)> = (db.rows.iter())
+        .filter_map(|row| {
+            let Value::Text(text) = &row.values[column] else {
+                return None;
+            };
+            let text = tokenize(text).join(" ");
+            // Every edit changes the length by at most one.
+            if text.chars().count().abs_diff(length) > max_distance {
+                return None;
+            }
+            let distance = match metric {
+                EditDistance::Levenshtein => levenshtein(&query, &text),
+                EditDistance::Damerau => damerau_levenshtein(&query, &text),
+            };
+            (distance <= max_distance).then_some((row, distance))
+        })
+        .collect();
+
+    found.sort_by_key(|(_, distance)| *distance);
+    Ok(found)
+}
+
+/// Rank rows by the trigram similarity of their text to a query, see `trigram_similarity`.
+///
+/// A trigram index created with `Database::create_trigram_index` answers the query by
+/// counting shared trigrams instead of comparing the query with every row.
+///
+/// # Arguments
+/// * `db` - The database to search in
+/// * `column_name` - The name of the text column to search in
+/// * `query` - The text to search for
+/// * `threshold` - The least similarity of a matching row; rows sharing no trigram with the
+///   query never match
+///
+/// # Returns
+/// The matching rows with their similarities, best first; rows with equal similarities
+/// keep database order.
+///
+/// # Errors
+/// Returns the errors of `find_fuzzy`.
+pub fn find_similar<'a>(
+    db: &'a Database,
+    column_name: &str,
+    query: &str,
+    threshold: f64,
+) -> Result<Vec<(&'a Row, f64)>> {
+    let column = text_column(db, column_name)?;
+    let similar = match db
+        .trigram_indexes
+        .iter()
+        .find(|index| index.column == column)
+    {
+        Some(index) => index.similar(query, threshold),
+        None => TrigramIndex::new(column, &db.rows).similar(query, threshold),
+    };
+
+    let mut ranked: Vec<(&Row, f64)> = similar
+        .into_iter()
+        .map(|(position, similarity)| (&db.rows[position], similarity))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{Column, delete, insert, update};
+
+    fn people() -> Database {
+        let names = [
+            text("Alice Smith"),
+            text("Alicia Smyth"),
+            Value::Null,
+            text("Bob Jones"),
+            text("Zoë Saldaña"),
+        ];
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text).nullable(),
+            ],
+            (1..)
+                .zip(names)
+                .map(|(id, name)| vec![Value::Integer(id), name])
+                .collect(),
+        )
+    }
+
+    fn ids<T: Copy>(found: &[(&Row, T)]) -> Vec<(i64, T)> {
+        found
+            .iter()
+            .map(|(row, score)| match row.get_value(0) {
+                Some(Value::Integer(id)) => (*id, *score),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_distances() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("café", "cafe"), 1);
+        assert_eq!(levenshtein("abcd", "acbd"), 2);
+        assert_eq!(damerau_levenshtein("abcd", "acbd"), 1);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein("", ""), 0);
+    }
+
+    #[test]
+    fn test_trigram_similarity() {
+        assert_eq!(trigram_similarity("word", "word"), 1.0);
+        assert_eq!(trigram_similarity("abc", "xyz"), 0.0);
+        assert_eq!(trigram_similarity("", ""), 0.0);
+        // "  a", " ab", "ab " against "  a", " ac", "ac ".
+        assert_eq!(trigram_similarity("ab", "ac"), 0.2);
+    }
+
+    #[test]
+    fn test_find_fuzzy() {
+        let db = people();
+
+        assert_eq!(
+            ids(&find_fuzzy(&db, "name", "alise smith", EditDistance::Levenshtein, 4).unwrap()),
+            vec![(1, 1), (2, 4)]
+        );
+        assert_eq!(
+            ids(&find_fuzzy(&db, "name", "Bob Jnoes", EditDistance::Levenshtein, 1).unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            ids(&find_fuzzy(&db, "name", "Bob Jnoes", EditDistance::Damerau, 1).unwrap()),
+            vec![(4, 1)]
+        );
+        assert_eq!(
+            ids(&find_fuzzy(&db, "name", "zoe, SALDANA", EditDistance::Damerau, 0).unwrap()),
+            vec![(5, 0)]
+        );
+        assert!(matches!(
+            find_fuzzy(&db, "id", "1", EditDistance::Levenshtein, 1),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_similar() {
+        let db = people();
+
+        let found = ids(&find_similar(&db, "name", "Smith Alise", 0.3).unwrap());
+        assert_eq!(
+            found.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(found[0].1 > found[1].1);
+        assert_eq!(find_similar(&db, "name", "qqq", 0.0).unwrap().len(), 0);
+        assert!(matches!(
+            find_similar(&db, "nickname", "x", 0.5),
+            Err(Error::UnknownColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_trigram_index_matches_scan() {
+        let mut db = people();
+        db.create_trigram_index("name").unwrap();
+
+        delete(&mut db, |row| row.values[0] == Value::Integer(1)).unwrap();
+        update(
+            &mut db,
+            |row| row.values[0] == Value::Integer(4),
+            &[("name", Value::Text("Alyce Smith".to_string()))],
+        )
+        .unwrap();
+        insert(
+            &mut db,
+            Row::new(vec![
+                Value::Integer(6),
+                Value::Text("Alice Smithe".to_string()),
+            ]),
+        )
+        .unwrap();
+
+        for (query, threshold) in [("alice smith", 0.2), ("saldana", 0.1), ("smyth", 0.0)] {
+            let indexed = ids(&find_similar(&db, "name", query, threshold).unwrap());
+            db.drop_trigram_index("name").unwrap();
+            let scanned = ids(&find_similar(&db, "name", query, threshold).unwrap());
+            db.create_trigram_index("name").unwrap();
+
+            assert!(!indexed.is_empty());
+            assert_eq!(indexed, scanned, "{}", query);
+        }
+    }
+}
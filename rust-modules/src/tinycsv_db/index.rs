@@ -0,0 +1,441 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::ops::Bound;
+
+use super::error::{Error, Result};
+use super::row::Row;
+use super::types::Value;
+
+/// A non-null value that can be hashed and ordered, used as an index key.
+///
+/// Floats are compared by their bits after folding `-0.0` into `0.0` and every NaN into
+/// one NaN, so equal keys always hash alike and are totally ordered.
+#[derive(Clone, Debug)]
+pub(crate) struct Key(Value);
+
+impl Key {
+    /// Make a key from a value.
+    ///
+    /// # Returns
+    /// The key, or `None` for `Value::Null`, which is never indexed.
+    pub(crate) fn new(value: &Value) -> Option<Key> {
+        match value {
+            Value::Null => None,
+            Value::Float(num) if *num == 0.0 => Some(Key(Value::Float(0.0))),
+            Value::Float(num) if num.is_nan() => Some(Key(Value::Float(f64::NAN))),
+            value => Some(Key(value.clone())),
+        }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.0 {
+            Value::Integer(num) => num.hash(state),
+            Value::Text(text) => text.hash(state),
+            Value::Float(num) => num.to_bits().hash(state),
+            Value::Boolean(bool) => bool.hash(state),
+            Value::Null => {}
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// The set of values of a UNIQUE column, so uniqueness is checked without scanning rows.
+#[derive(Clone, Debug)]
+pub(crate) struct UniqueIndex {
+    pub(crate) column: usize,
+    keys: HashSet<Key>,
+}
+
+impl UniqueIndex {
+    pub(crate) fn new(column: usize) -> Self {
+        UniqueIndex {
+            column,
+            keys: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, row: &Row) {
+        if let Some(key) = Key::new(&row.values[self.column]) {
+            self.keys.insert(key);
+        }
+    }
+
+    pub(crate) fn remove(&mut self, row: &Row) {
+        if let Some(key) = Key::new(&row.values[self.column]) {
+            self.keys.remove(&key);
+        }
+    }
+}
+
+/// The kind of a secondary index created with `Database::create_index`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IndexKind {
+    /// A hash table, answering equality lookups.
+    Hash,
+    /// An ordered tree, answering equality and range lookups.
+    BTree,
+}
+
+#[derive(Clone, Debug)]
+enum Entries {
+    Hash(HashMap<Key, Vec<usize>>),
+    BTree(BTreeMap<Key, Vec<usize>>),
+}
+
+/// A secondary index mapping the values of a column to the ascending positions of their rows.
+#[derive(Clone, Debug)]
+pub(crate) struct Index {
+    pub(crate) column: usize,
+    entries: Entries,
+}
+
+impl Index {
+    pub(crate) fn new(column: usize, kind: IndexKind, rows: &[Row]) -> Self {
+        let entries = match kind {
+            IndexKind::Hash => Entries::Hash(HashMap::new()),
+            IndexKind::BTree => Entries::BTree(BTreeMap::new()),
+        };
+        let mut index = Index { column, entries };
+        index.rebuild(rows);
+        index
+    }
+
+    pub(crate) fn kind(&self) -> IndexKind {
+        match self.entries {
+            Entries::Hash(_) => IndexKind::Hash,
+            Entries::BTree(_) => IndexKind::BTree,
+        }
+    }
+
+    fn positions(&mut self, key: Key) -> &mut Vec<usize> {
+        match &mut self.entries {
+            Entries::Hash(map) => map.entry(key).or_default(),
+            Entries::BTree(map) => map.entry(key).or_default(),
+        }
+    }
+
+    /// Drop a position from the entry of a key, and the entry once it has no positions left.
+    fn forget(&mut self, key: &Key, position: usize) {
+        let positions = match &mut self.entries {
+            Entries::Hash(map) => map.get_mut(key),
+            Entries::BTree(map) => map.get_mut(key),
+        };
+        let Some(positions) = positions else {
+            return;
+        };
+        if let Ok(found) = positions.binary_search(&position) {
+            positions.remove(found);
+        }
+        if positions.is_empty() {
+            match &mut self.entries {
+                Entries::Hash(map) => map.remove(key),
+                Entries::BTree(map) => map.remove(key),
+            };
+        }
+    }
+
+    /// Add a row stored after every indexed row.
+    pub(crate) fn push(&mut self, row: &Row, position: usize) {
+        if let Some(key) = Key::new(&row.values[self.column]) {
+            self.positions(key).push(position);
+        }
+    }
+
+    /// Forget the rows that were at the ascending positions `deleted`, whose values are
+    /// `rows`, and shift down the positions of the rows after them.
+    pub(crate) fn remove(&mut self, deleted: &[usize], rows: &[Row]) {
+        let Some(&first) = deleted.first() else {
+            return;
+        };
+        for (&position, row) in deleted.iter().zip(rows) {
+            if let Some(key) = Key::new(&row.values[self.column]) {
+                self.forget(&key, position);
+            }
+        }
+
+        let shift = |positions: &mut Vec<usize>| {
+            if positions.last() > Some(&first) {
+                for position in positions {
+                    *position -= deleted.partition_point(|&old| old < *position);
+                }
+            }
+        };
+        match &mut self.entries {
+            Entries::Hash(map) => map.values_mut().for_each(shift),
+            Entries::BTree(map) => map.values_mut().for_each(shift),
+        }
+    }
+
+    /// Put back rows forgotten by `remove` at their former ascending positions `restored`,
+    /// and shift up the positions of the rows after them.
+    pub(crate) fn restore(&mut self, restored: &[usize], rows: &[Row]) {
+        let Some(&first) = restored.first() else {
+            return;
+        };
+        // The rows kept before the restored row at `restored[index]`, which is where it
+        // was removed from.
+        let gaps: Vec<usize> = (restored.iter().enumerate())
+            .map(|(index, &position)| position - index)
+            .collect();
+        let shift = |positions: &mut Vec<usize>| {
+            if positions.last() >= Some(&first) {
+                for position in positions {
+                    *position += gaps.partition_point(|&gap| gap <= *position);
+                }
+            }
+        };
+        match &mut self.entries {
+            Entries::Hash(map) => map.values_mut().for_each(shift),
+            Entries::BTree(map) => map.values_mut().for_each(shift),
+        }
+
+        for (&position, row) in restored.iter().zip(rows) {
+            if let Some(key) = Key::new(&row.values[self.column]) {
+                let positions = self.positions(key);
+                let at = positions.partition_point(|&other| other < position);
+                positions.insert(at, position);
+            }
+        }
+    }
+
+    /// Move the row at `position` from the entry of its old value to that of its new value.
+    pub(crate) fn replace(&mut self, position: usize, old: &Row, new: &Row) {
+        if let Some(key) = Key::new(&old.values[self.column]) {
+            self.forget(&key, position);
+        }
+        if let Some(key) = Key::new(&new.values[self.column]) {
+            let positions = self.positions(key);
+            let at = positions.partition_point(|&other| other < position);
+            positions.insert(at, position);
+        }
+    }
+
+    /// Index the rows from scratch.
+    pub(crate) fn rebuild(&mut self, rows: &[Row]) {
+        match &mut self.entries {
+            Entries::Hash(map) => map.clear(),
+            Entries::BTree(map) => map.clear(),
+        }
+        for (position, row) in rows.iter().enumerate() {
+            self.push(row, position);
+        }
+    }
+
+    /// The positions of the rows holding a value equal to the key of `value`.
+    pub(crate) fn get(&self, value: &Value) -> Vec<usize> {
+        let Some(key) = Key::new(value) else {
+            return Vec::new();
+        };
+        let positions = match &self.entries {
+            Entries::Hash(map) => map.get(&key),
+            Entries::BTree(map) => map.get(&key),
+        };
+        positions.cloned().unwrap_or_default()
+    }
+
+    /// The ascending positions of the rows with a value between the bounds, or `None` for a
+    /// hash index. A `Value::Null` bound matches no row.
+    pub(crate) fn range(&self, low: Bound<&Value>, high: Bound<&Value>) -> Option<Vec<usize>> {
+        let Entries::BTree(map) = &self.entries else {
+            return None;
+        };
+        let key = |bound: Bound<&Value>| match bound {
+            Bound::Included(value) => Key::new(value).map(Bound::Included),
+            Bound::Excluded(value) => Key::new(value).map(Bound::Excluded),
+            Bound::Unbounded => Some(Bound::Unbounded),
+        };
+        let (Some(low), Some(high)) = (key(low), key(high)) else {
+            return Some(Vec::new());
+        };
+        // `BTreeMap::range` panics on an empty range instead of returning nothing.
+        let empty = match (&low, &high) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        };
+        if empty {
+            return Some(Vec::new());
+        }
+
+        let mut positions: Vec<usize> = map
+            .range((low, high))
+            .flat_map(|(_, positions)| positions.iter().copied())
+            .collect();
+        positions.sort_unstable();
+        Some(positions)
+    }
+}
+
+/// Check that rows about to be added do not repeat a value of a unique column, neither
+/// among themselves nor with the rows already in the indexes.
+///
+/// # Arguments
+/// * `indexes` - The unique indexes of the database, with the rows being replaced removed
+/// * `rows` - The rows to add
+/// * `column_name` - Maps a column position to its name for the error message
+///
+/// # Errors
+/// Returns `Error::UniqueViolation` for the first repeated value.
+pub(crate) fn check_unique<'a>(
+    indexes: &[UniqueIndex],
+    rows: impl IntoIterator<Item = &'a Row>,
+    column_name: impl Fn(usize) -> String,
+) -> Result<()> {
+    let mut seen: Vec<HashSet<Key>> = vec![HashSet::new(); indexes.len()];
+
+    for row in rows {
+        for (index, seen) in indexes.iter().zip(&mut seen) {
+            let value = &row.values[index.column];
+            let Some(key) = Key::new(value) else {
+                continue;
+            };
+
+            if index.keys.contains(&key) || !seen.insert(key) {
+                return Err(Error::UniqueViolation {
+                    column: column_name(index.column),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float_keys_fold_zero_and_nan() {
+        assert_eq!(Key::new(&Value::Float(-0.0)), Key::new(&Value::Float(0.0)));
+        assert_eq!(
+            Key::new(&Value::Float(f64::NAN)),
+            Key::new(&Value::Float(-f64::NAN))
+        );
+        assert!(Key::new(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_keys_are_ordered() {
+        let mut keys: Vec<Key> = [2.5, -1.0, f64::INFINITY, 0.0]
+            .iter()
+            .map(|num| Key::new(&Value::Float(*num)).unwrap())
+            .collect();
+        keys.sort();
+
+        let sorted: Vec<&Value> = keys.iter().map(|key| &key.0).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                &Value::Float(-1.0),
+                &Value::Float(0.0),
+                &Value::Float(2.5),
+                &Value::Float(f64::INFINITY),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_unique_within_new_rows() {
+        let rows = vec![
+            Row::new(vec![Value::Integer(1)]),
+            Row::new(vec![Value::Null]),
+            Row::new(vec![Value::Null]),
+            Row::new(vec![Value::Integer(1)]),
+        ];
+        let result = check_unique(&[UniqueIndex::new(0)], &rows, |_| "id".to_string());
+
+        assert!(matches!(
+            result,
+            Err(Error::UniqueViolation {
+                value: Value::Integer(1),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_index_remove_and_replace() {
+        let value = |num: i64| Row::new(vec![Value::Integer(num)]);
+        let mut rows: Vec<Row> = [3, 1, 4, 1, 5, 9, 2, 6].into_iter().map(value).collect();
+        let mut index = Index::new(0, IndexKind::BTree, &rows);
+
+        let (nine, one) = (rows.remove(5), rows.remove(1));
+        index.remove(&[1, 5], &[one, nine]);
+        index.replace(2, &rows[2], &value(6));
+        rows[2] = value(6);
+
+        let rebuilt = Index::new(0, IndexKind::BTree, &rows);
+        for num in 0..10 {
+            assert_eq!(
+                index.get(&Value::Integer(num)),
+                rebuilt.get(&Value::Integer(num))
+            );
+        }
+        assert_eq!(index.get(&Value::Integer(6)), vec![2, 5]);
+        assert_eq!(
+            index.range(Bound::Unbounded, Bound::Unbounded),
+            Some(vec![0, 1, 2, 3, 4, 5])
+        );
+    }
+
+    #[test]
+    fn test_index_range() {
+        let rows: Vec<Row> = [3, 1, 4, 1, 5]
+            .iter()
+            .map(|num| Row::new(vec![Value::Integer(*num)]))
+            .chain([Row::new(vec![Value::Null])])
+            .collect();
+        let index = Index::new(0, IndexKind::BTree, &rows);
+        let (one, four) = (Value::Integer(1), Value::Integer(4));
+
+        assert_eq!(index.get(&one), vec![1, 3]);
+        assert_eq!(index.get(&Value::Null), Vec::<usize>::new());
+        assert_eq!(
+            index.range(Bound::Included(&one), Bound::Excluded(&four)),
+            Some(vec![0, 1, 3])
+        );
+        assert_eq!(
+            index.range(Bound::Excluded(&four), Bound::Unbounded),
+            Some(vec![4])
+        );
+        assert_eq!(
+            index.range(Bound::Included(&four), Bound::Excluded(&four)),
+            Some(vec![])
+        );
+        assert_eq!(
+            index.range(Bound::Included(&four), Bound::Included(&one)),
+            Some(vec![])
+        );
+        assert_eq!(
+            Index::new(0, IndexKind::Hash, &rows).range(Bound::Unbounded, Bound::Unbounded),
+            None
+        );
+    }
+}
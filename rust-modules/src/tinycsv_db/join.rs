@@ -0,0 +1,557 @@
+use std::collections::{HashMap, HashSet};
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::index::Key;
+use super::row::Row;
+use super::schema::{Column, Schema};
+use super::types::{DataType, Value};
+
+/// Inputs with at most this many row pairs are joined with a nested loop under
+/// `JoinStrategy::Auto`, since building a hash table does not pay off for them.
+const NESTED_LOOP_PAIRS: usize = 1024;
+
+/// Which rows a join keeps.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum JoinKind {
+    /// Only pairs of matching rows.
+    Inner,
+    /// Every left row, with `NULL` right columns when nothing matches.
+    Left,
+}
+
+/// How a join finds matching rows.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum JoinStrategy {
+    /// A nested loop for small inputs and a hash join otherwise.
+    #[default]
+    Auto,
+    /// Build a hash table over the right rows and probe it with each left row.
+    Hash,
+    /// Compare every left row with every right row.
+    NestedLoop,
+}
+
+/// A join of two databases, built step by step and run with `Join::execute`.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let users = Database::from_csv("id,name\n1,Alice\n2,Bob\n").unwrap();
+/// let scores = Database::from_csv("id:int,score:float\n1,95.5\n1,80\n").unwrap();
+/// let joined = users
+///     .join(&scores, JoinKind::Left)
+///     .on("id", "id")
+///     .execute()
+///     .unwrap();
+///
+/// let names: Vec<&str> = joined.get_schema().get_columns().iter().map(Column::get_name).collect();
+/// assert_eq!(names, vec!["left.id", "name", "right.id", "score"]);
+/// assert_eq!(joined.get_rows().len(), 3);
+/// assert_eq!(joined.get_rows()[2].get_value(3), Some(&Value::Null));
+/// ```
+pub struct Join<'a> {
+    left: &'a Database,
+    right: &'a Database,
+    kind: JoinKind,
+    on: Vec<(String, String)>,
+    prefixes: (String, String),
+    strategy: JoinStrategy,
+}
+
+impl Database {
+    /// Start a join of this database with another one.
+    pub fn join<'a>(&'a self, right: &'a Database, kind: JoinKind) -> Join<'a> {
+        Join {
+            left: self,
+            right,
+            kind,
+            on: Vec::new(),
+            prefixes: ("left".to_string(), "right".to_string()),
+            strategy: JoinStrategy::default(),
+        }
+    }
+}
+
+impl<'a> Join<'a> {
+    /// Match rows whose left column equals their right column; several calls must all match.
+    ///
+    /// Without any `on` columns every left row matches every right row.
+    pub fn on(mut self, left_column: &str, right_column: &str) -> Self {
+        self.on
+            .push((left_column.to_string(), right_column.to_string()));
+        self
+    }
+
+    /// The prefixes that tell apart columns with the same name on both sides, which are
+    /// named `<prefix>.<column>`; `left` and `right` unless set. A name that still repeats
+    /// another one after prefixing gets the suffix `_2`, `_3` and so on.
+    pub fn prefixes(mut self, left: &str, right: &str) -> Self {
+        self.prefixes = (left.to_string(), right.to_string());
+        self
+    }
+
+    pub fn strategy(mut self, strategy: JoinStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Run the join.
+    ///
+    /// `Value::Null` matches nothing, an integer only matches a float of exactly its value, and
+    /// floats are compared like index keys, so `-0.0` matches `0.0` and NaN matches NaN. Rows
+    /// come in the order of the left rows, and the matches of a left row in the order of the
+    /// right rows, whatever the strategy.
+    ///
+    /// # Returns
+    /// A new database with the left columns followed by the right columns. Columns keep their
+    /// type and nullability, except that right columns are nullable in a left join, and lose
+    /// their other constraints.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if a join column does not exist and
+    /// `Error::TypeMismatch` if the two columns of a pair cannot be equal, i.e. have different
+    /// types that are not both numeric.
+    pub fn execute(&self) -> Result<Database> {
+        let (left_schema, right_schema) = (&self.left.schema, &self.right.schema);
+        let mut keys = Vec::new();
+
+        for (left_name, right_name) in &self.on {
+            let left = left_schema.column_index(left_name)?;
+            let right = right_schema.column_index(right_name)?;
+            let (left_type, right_type) = (
+                left_schema.columns[left].data_type,
+                right_schema.columns[right].data_type,
+            );
+            let numeric = |data_type| matches!(data_type, DataType::Integer | DataType::Float);
+
+            if left_type != right_type && !(numeric(left_type) && numeric(right_type)) {
+                return Err(Error::TypeMismatch {
+                    column: right_name.clone(),
+                    expected: left_type,
+                    found: right_type,
+                });
+            }
+            keys.push(KeyColumns {
+                left,
+                right,
+                mixed: left_type != right_type,
+            });
+        }
+
+        let strategy = match self.strategy {
+            JoinStrategy::Auto
+                if self.left.rows.len().saturating_mul(self.right.rows.len())
+                    <= NESTED_LOOP_PAIRS =>
+            {
+                JoinStrategy::NestedLoop
+            }
+            JoinStrategy::Auto => JoinStrategy::Hash,
+            strategy => strategy,
+        };
+        let matches = match strategy {
+            JoinStrategy::Hash => self.hash_join(&keys),
+            _ => self.nested_loop_join(&keys),
+        };
+
+        let mut db = Database::new(self.schema());
+        let right_width = right_schema.columns.len();
+
+        for (left, right) in self.left.rows.iter().zip(matches) {
+            if right.is_empty() && self.kind == JoinKind::Left {
+                let mut values = left.values.clone();
+                values.extend(std::iter::repeat_n(Value::Null, right_width));
+                db.rows.push(Row { values });
+            }
+            for index in right {
+                let mut values = left.values.clone();
+                values.extend_from_slice(&self.right.rows[index].values);
+                db.rows.push(Row { values });
+            }
+        }
+
+        Ok(db)
+    }
+
+    /// The combined schema, with colliding names prefixed and then numbered until unique.
+    fn schema(&self) -> Schema {
+        let (left, right) = (&self.left.schema.columns, &self.right.schema.columns);
+        let collides =
+            |name: &str, other: &[Column]| other.iter().any(|column| column.name == name);
+        let copy = |column: &Column, other: &[Column], prefix: &str, nullable: bool| {
+            let name = match collides(&column.name, other) {
+                true => format!("{}.{}", prefix, column.name),
+                false => column.name.clone(),
+            };
+            let result = Column::new(&name, column.data_type);
+            match column.nullable || nullable {
+                true => result.nullable(),
+                false => result,
+            }
+        };
+
+        let mut columns: Vec<Column> = left
+            .iter()
+            .map(|column| copy(column, right, &self.prefixes.0, false))
+            .collect();
+        columns.extend(
+            right
+                .iter()
+                .map(|column| copy(column, left, &self.prefixes.1, self.kind == JoinKind::Left)),
+        );
+
+        let original: HashSet<String> = columns.iter().map(|column| column.name.clone()).collect();
+        let mut taken = HashSet::new();
+        for column in &mut columns {
+            if taken.contains(&column.name) {
+                column.name = (2..)
+                    .map(|suffix| format!("{}_{}", column.name, suffix))
+                    .find(|name| !original.contains(name) && !taken.contains(name))
+                    .unwrap();
+            }
+            taken.insert(column.name.clone());
+        }
+        Schema::from_columns(columns)
+    }
+
+    /// For each left row, the positions of the matching right rows.
+    fn nested_loop_join(&self, keys: &[KeyColumns]) -> Vec<Vec<usize>> {
+        let right_keys: Vec<Option<Vec<Key>>> = (self.right.rows.iter())
+            .map(|row| join_key(row, keys.iter().map(|key| (key.right, key.mixed))))
+            .collect();
+
+        self.left
+            .rows
+            .iter()
+            .map(|row| {
+                let Some(left_key) = join_key(row, keys.iter().map(|key| (key.left, key.mixed)))
+                else {
+                    return Vec::new();
+                };
+                (right_keys.iter().enumerate())
+                    .filter(|(_, right_key)| right_key.as_ref() == Some(&left_key))
+                    .map(|(index, _)| index)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// For each left row, the positions of the matching right rows.
+    fn hash_join(&self, keys: &[KeyColumns]) -> Vec<Vec<usize>> {
+        let mut table: HashMap<Vec<Key>, Vec<usize>> = HashMap::new();
+
+        for (index, row) in self.right.rows.iter().enumerate() {
+            if let Some(key) = join_key(row, keys.iter().map(|key| (key.right, key.mixed))) {
+                table.entry(key).or_default().push(index);
+            }
+        }
+
+        self.left
+            .rows
+            .iter()
+            .map(|row| {
+                join_key(row, keys.iter().map(|key| (key.left, key.mixed)))
+                    .and_then(|key| table.get(&key).cloned())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+/// A pair of join columns; `mixed` is set when one is an integer and the other a float
+/// column, so integral float keys must be hashed as integers.
+struct KeyColumns {
+    left: usize,
+    right: usize,
+    mixed: bool,
+}
+
+/// The hash key of a row, or `None` if a join column is `Value::Null`.
+fn join_key(row: &Row, columns: impl Iterator<Item = (usize, bool)>) -> Option<Vec<Key>> {
+    columns
+        .map(|(index, mixed)| match &row.values[index] {
+            Value::Float(num) if mixed => match exact_integer(*num) {
+                Some(num) => Key::new(&Value::Integer(num)),
+                None => Key::new(&Value::Float(*num)),
+            },
+            value => Key::new(value),
+        })
+        .collect()
+}
+
+/// The integer a float is exactly equal to, if there is one.
+///
+/// Converting the integer to a float instead would round integers beyond 2^53, so that
+/// `9007199254740993` would match `9007199254740992.0`.
+fn exact_integer(num: f64) -> Option<i64> {
+    // `i64::MIN` is a power of two, so both bounds are exact floats.
+    let bound = -(i64::MIN as f64);
+    (num.fract() == 0.0 && (-bound..bound).contains(&num)).then_some(num as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text, users};
+    use crate::tinycsv_db::operations::insert;
+
+    fn scores() -> Database {
+        let score = |user_id, points| vec![user_id, Value::Boolean(true), Value::Float(points)];
+        table(
+            vec![
+                Column::new("user_id", DataType::Integer).nullable(),
+                Column::new("active", DataType::Boolean),
+                Column::new("points", DataType::Float),
+            ],
+            vec![
+                score(Value::Integer(1), 9.5),
+                score(Value::Integer(2), 80.0),
+                score(Value::Integer(1), 8.0),
+                vec![Value::Null, Value::Boolean(false), Value::Float(1.0)],
+            ],
+        )
+    }
+
+    fn rows(db: &Database) -> Vec<Vec<Value>> {
+        db.get_rows()
+            .iter()
+            .map(|row| row.get_values().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_inner_join() {
+        let (users, scores) = (users(), scores());
+
+        for strategy in [JoinStrategy::Hash, JoinStrategy::NestedLoop] {
+            let joined = users
+                .join(&scores, JoinKind::Inner)
+                .on("id", "user_id")
+                .strategy(strategy)
+                .execute()
+                .unwrap();
+
+            assert_eq!(
+                rows(&joined)
+                    .iter()
+                    .map(|row| (row[1].clone(), row[6].clone()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (text("Alice"), Value::Float(9.5)),
+                    (text("Alice"), Value::Float(8.0)),
+                    (text("Bob"), Value::Float(80.0)),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_left_join() {
+        let (users, scores) = (users(), scores());
+
+        for strategy in [JoinStrategy::Hash, JoinStrategy::NestedLoop] {
+            let joined = users
+                .join(&scores, JoinKind::Left)
+                .on("id", "user_id")
+                .on("active", "active")
+                .strategy(strategy)
+                .execute()
+                .unwrap();
+
+            let alice = |points| {
+                vec![
+                    Value::Integer(1),
+                    text("Alice"),
+                    Value::Float(95.5),
+                    Value::Boolean(true),
+                    Value::Integer(1),
+                    Value::Boolean(true),
+                    Value::Float(points),
+                ]
+            };
+            let unmatched = |id, name, score, active| {
+                let mut row = vec![
+                    Value::Integer(id),
+                    text(name),
+                    score,
+                    Value::Boolean(active),
+                ];
+                row.extend([Value::Null, Value::Null, Value::Null]);
+                row
+            };
+            assert_eq!(
+                rows(&joined),
+                vec![
+                    alice(9.5),
+                    alice(8.0),
+                    unmatched(2, "Bob", Value::Float(80.0), false),
+                    unmatched(3, "Carol", Value::Null, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_combined_schema() {
+        let (users, scores) = (users(), scores());
+        let columns = |kind| {
+            users
+                .join(&scores, kind)
+                .prefixes("users", "scores")
+                .on("id", "user_id")
+                .execute()
+                .unwrap()
+                .get_schema()
+                .get_columns()
+                .iter()
+                .map(|column| {
+                    (
+                        column.get_name().to_string(),
+                        column.is_nullable(),
+                        column.is_unique(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            columns(JoinKind::Inner),
+            vec![
+                ("id".to_string(), false, false),
+                ("name".to_string(), false, false),
+                ("score".to_string(), true, false),
+                ("users.active".to_string(), false, false),
+                ("user_id".to_string(), true, false),
+                ("scores.active".to_string(), false, false),
+                ("points".to_string(), false, false),
+            ]
+        );
+        assert!(
+            columns(JoinKind::Left)[4..]
+                .iter()
+                .all(|(_, nullable, _)| *nullable)
+        );
+    }
+
+    #[test]
+    fn test_prefixed_names_are_unique() {
+        let left = Database::from_csv("id:int,right.id:int,x_2:int\n1,2,3\n").unwrap();
+        let right = Database::from_csv("id:int,x:int,x_2:int\n1,4,5\n").unwrap();
+        let names = |join: Join| {
+            let db = join.on("id", "id").execute().unwrap();
+            db.get_schema()
+                .get_columns()
+                .iter()
+                .map(|column| column.get_name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(left.join(&right, JoinKind::Inner)),
+            vec![
+                "left.id",
+                "right.id",
+                "left.x_2",
+                "right.id_2",
+                "x",
+                "right.x_2"
+            ]
+        );
+        assert_eq!(
+            names(left.join(&right, JoinKind::Inner).prefixes("t", "t")),
+            vec!["t.id", "right.id", "t.x_2", "t.id_2", "x", "t.x_2_2"]
+        );
+    }
+
+    #[test]
+    fn test_nulls_never_match() {
+        let (users, scores) = (users(), scores());
+
+        for strategy in [JoinStrategy::Hash, JoinStrategy::NestedLoop] {
+            let joined = users
+                .join(&scores, JoinKind::Inner)
+                .on("score", "points")
+                .strategy(strategy)
+                .execute()
+                .unwrap();
+            assert_eq!(joined.get_rows().len(), 1);
+
+            let joined = scores
+                .join(&scores, JoinKind::Inner)
+                .on("user_id", "user_id")
+                .strategy(strategy)
+                .execute()
+                .unwrap();
+            assert_eq!(joined.get_rows().len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_integer_float_keys() {
+        let ints =
+            Database::from_csv("k:int\n1\n2\n9007199254740993\n9223372036854775807\n").unwrap();
+        let floats =
+            Database::from_csv("k:float\n2.0\n1.5\n1\n9007199254740992\n9223372036854775808\n")
+                .unwrap();
+
+        for strategy in [JoinStrategy::Hash, JoinStrategy::NestedLoop] {
+            let joined = ints
+                .join(&floats, JoinKind::Inner)
+                .on("k", "k")
+                .strategy(strategy)
+                .execute()
+                .unwrap();
+            assert_eq!(
+                rows(&joined),
+                vec![
+                    vec![Value::Integer(1), Value::Float(1.0)],
+                    vec![Value::Integer(2), Value::Float(2.0)],
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_strategies_agree_on_large_input() {
+        let mut left = Database::new(Schema::new(vec![("k".to_string(), DataType::Integer)]));
+        let mut right = Database::new(Schema::new(vec![("k".to_string(), DataType::Integer)]));
+        for num in 0..200 {
+            insert(&mut left, Row::new(vec![Value::Integer(num % 37)])).unwrap();
+            insert(&mut right, Row::new(vec![Value::Integer(num % 23)])).unwrap();
+        }
+
+        let join = |strategy| {
+            left.join(&right, JoinKind::Left)
+                .on("k", "k")
+                .strategy(strategy)
+                .execute()
+                .unwrap()
+        };
+        assert_eq!(
+            rows(&join(JoinStrategy::Hash)),
+            rows(&join(JoinStrategy::NestedLoop))
+        );
+        assert_eq!(
+            rows(&join(JoinStrategy::Auto)),
+            rows(&join(JoinStrategy::Hash))
+        );
+    }
+
+    #[test]
+    fn test_join_errors() {
+        let (users, scores) = (users(), scores());
+
+        assert!(matches!(
+            users.join(&scores, JoinKind::Inner).on("id", "uid").execute(),
+            Err(Error::UnknownColumn(column)) if column == "uid"
+        ));
+        assert!(matches!(
+            users
+                .join(&scores, JoinKind::Inner)
+                .on("name", "points")
+                .execute(),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+}
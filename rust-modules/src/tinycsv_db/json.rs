@@ -0,0 +1,847 @@
+use std::collections::HashMap;
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::operations::insert_all;
+use super::row::Row;
+use super::schema::{Column, Schema};
+use super::types::{DataType, Value};
+
+/// How deeply arrays and objects may nest inside a value before the input is rejected.
+const MAX_DEPTH: usize = 128;
+
+/// Convert the database to a JSON array with one object per row, keyed by column name.
+///
+/// Integers are written without and floats always with a fraction or exponent, so
+/// `Database::from_json` reads them back with the same types. Infinite and NaN floats,
+/// which JSON cannot represent, are written as `null`.
+///
+/// # Arguments
+/// * `db` - The database to convert to JSON
+///
+/// # Returns
+/// A JSON string with each row object on its own line.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let db = Database::from_csv("id:int,name:text,score:float?\n1,Alice,95\n2,Bob,\n").unwrap();
+/// let json = concat!(
+///     "[\n",
+///     "  {\"id\":1,\"name\":\"Alice\",\"score\":95.0},\n",
+///     "  {\"id\":2,\"name\":\"Bob\",\"score\":null}\n",
+///     "]\n",
+/// );
+/// assert_eq!(to_json(&db), json);
+/// ```
+pub fn to_json(db: &Database) -> String {
+    let mut json = String::from("[");
+    for (index, row) in db.rows.iter().enumerate() {
+        json.push_str(if index == 0 { "\n  " } else { ",\n  " });
+        push_object(&mut json, &db.schema, row);
+    }
+    if !db.rows.is_empty() {
+        json.push('\n');
+    }
+    json.push_str("]\n");
+    json
+}
+
+/// Convert the database to JSON Lines: one JSON object per row, each on its own line.
+///
+/// Values are written as by `to_json`.
+///
+/// # Arguments
+/// * `db` - The database to convert to JSON Lines
+///
+/// # Returns
+/// A JSON Lines string, empty if the database has no rows.
+pub fn to_jsonl(db: &Database) -> String {
+    let mut json = String::new();
+    for row in &db.rows {
+        push_object(&mut json, &db.schema, row);
+        json.push('\n');
+    }
+    json
+}
+
+fn push_object(json: &mut String, schema: &Schema, row: &Row) {
+    json.push('{');
+    for (index, (column, value)) in schema.columns.iter().zip(&row.values).enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        push_string(json, &column.name);
+        json.push(':');
+        match value {
+            Value::Integer(num) => json.push_str(&num.to_string()),
+            // The `Debug` format keeps a fraction or exponent, and the shortest exact digits.
+            Value::Float(num) if num.is_finite() => json.push_str(&format!("{:?}", num)),
+            Value::Boolean(bool) => json.push_str(&bool.to_string()),
+            Value::Text(text) => push_string(json, text),
+            Value::Float(_) | Value::Null => json.push_str("null"),
+        }
+    }
+    json.push('}');
+}
+
+fn push_string(json: &mut String, text: &str) {
+    json.push('"');
+    for char in text.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if char < ' ' => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+}
+
+impl Database {
+    /// Restore a database from a JSON array of row objects, such as written by `to_json`.
+    ///
+    /// The columns are the keys of the objects in order of first appearance. A column
+    /// holding only booleans is a boolean column, only strings a text column, only
+    /// integers an integer column and any other numbers a float column; a column holding
+    /// only `null` is a text column. A column is nullable if it holds `null` or is missing
+    /// from some object.
+    ///
+    /// # Arguments
+    /// * `input` - JSON string to restore the database from
+    ///
+    /// # Returns
+    /// A new database restored from the JSON string.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedJson` if the input is not valid JSON, is not an array of
+    /// objects or an object repeats a key, and `Error::InvalidJsonValue` if a column holds
+    /// values of different types, or an array or object.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_modules::tinycsv_db::*;
+    ///
+    /// let db = Database::from_json(r#"[{"id": 1, "score": 2}, {"id": 2, "score": 0.5}]"#)
+    ///     .unwrap();
+    /// let columns = db.get_schema().get_columns();
+    ///
+    /// assert_eq!(columns[0].get_data_type(), DataType::Integer);
+    /// assert_eq!(columns[1].get_data_type(), DataType::Float);
+    /// assert_eq!(db.get_rows()[0].get_values()[1], Value::Float(2.0));
+    ///
+    /// assert!(matches!(
+    ///     Database::from_json(r#"[{"id": 1}, {"id": "2"}]"#),
+    ///     Err(Error::InvalidJsonValue { line: 1, column: 20, .. })
+    /// ));
+    /// ```
+    pub fn from_json(input: &str) -> Result<Database> {
+        build(Parser::new(input, 1).rows()?, None)
+    }
+
+    /// Restore a database from a JSON array of row objects using the given schema.
+    ///
+    /// The objects may list the columns in any order, and a missing key stands for
+    /// `Value::Null`, which takes the default of the column if it has one. Integers are
+    /// accepted in float columns.
+    ///
+    /// # Arguments
+    /// * `input` - JSON string to restore the database from
+    /// * `schema` - The schema describing the columns of the objects
+    ///
+    /// # Returns
+    /// A new database with the given schema restored from the JSON string.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedJson` if the input is not valid JSON, is not an array of
+    /// objects or an object repeats a key, `Error::UnknownColumn` if a key is not in the
+    /// schema, `Error::InvalidJsonValue` if a value does not fit the type of its column and
+    /// the errors of `insert` if a row violates a constraint of the schema.
+    pub fn from_json_with_schema(input: &str, schema: &Schema) -> Result<Database> {
+        build(Parser::new(input, 1).rows()?, Some(schema))
+    }
+
+    /// Restore a database from JSON Lines, one row object per line, such as written by
+    /// `to_jsonl`. Blank lines are skipped.
+    ///
+    /// The columns are inferred as by `Database::from_json`.
+    ///
+    /// # Errors
+    /// Returns the errors of `Database::from_json`.
+    pub fn from_jsonl(input: &str) -> Result<Database> {
+        build(objects(input)?, None)
+    }
+
+    /// Restore a database from JSON Lines using the given schema, reading the objects as
+    /// `Database::from_json_with_schema` does.
+    ///
+    /// # Errors
+    /// Returns the errors of `Database::from_json_with_schema`.
+    pub fn from_jsonl_with_schema(input: &str, schema: &Schema) -> Result<Database> {
+        build(objects(input)?, Some(schema))
+    }
+}
+
+/// Parse the non-blank lines of JSON Lines input as objects.
+fn objects(input: &str) -> Result<Vec<Vec<Field<'_>>>> {
+    (input.lines().enumerate())
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let mut parser = Parser::new(line, index + 1);
+            let object = parser.object()?;
+            parser.end()?;
+            Ok(object)
+        })
+        .collect()
+}
+
+fn build(objects: Vec<Vec<Field>>, schema: Option<&Schema>) -> Result<Database> {
+    let schema = match schema {
+        Some(schema) => schema.clone(),
+        None => infer_schema(&objects)?,
+    };
+
+    let mut rows = Vec::with_capacity(objects.len());
+    for fields in objects {
+        let mut values = vec![Value::Null; schema.columns.len()];
+        for field in fields {
+            let index = schema.column_index(&field.key)?;
+            let column = &schema.columns[index];
+            values[index] = field
+                .to_value(column.data_type)
+                .ok_or_else(|| field.invalid(column.data_type))?;
+        }
+        rows.push(Row::new(values));
+    }
+
+    let mut db = Database::new(schema);
+    insert_all(&mut db, rows)?;
+    Ok(db)
+}
+
+/// Infer the columns of row objects, as described in `Database::from_json`.
+fn infer_schema(objects: &[Vec<Field>]) -> Result<Schema> {
+    // The name, the type of the values so far, whether a value is null and the number of
+    // objects with the column.
+    let mut columns: Vec<(&str, Option<DataType>, bool, usize)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+
+    for field in objects.iter().flatten() {
+        let position = *positions.entry(&field.key).or_insert_with(|| {
+            columns.push((&field.key, None, false, 0));
+            columns.len() - 1
+        });
+        let (_, data_type, null, count) = &mut columns[position];
+        *count += 1;
+
+        let found = match &field.value {
+            Json::Null => {
+                *null = true;
+                continue;
+            }
+            Json::Boolean(_) => DataType::Boolean,
+            Json::String(_) => DataType::Text,
+            Json::Number { integer: true } if field.text.parse::<i64>().is_ok() => {
+                DataType::Integer
+            }
+            Json::Number { .. } => DataType::Float,
+            Json::Array | Json::Object => {
+                return Err(field.invalid(data_type.unwrap_or(DataType::Text)));
+            }
+        };
+        *data_type = Some(match (*data_type, found) {
+            (None, found) => found,
+            (Some(DataType::Integer), DataType::Float) => DataType::Float,
+            (Some(DataType::Float), DataType::Integer) => DataType::Float,
+            (Some(expected), found) if expected == found => found,
+            (Some(expected), _) => return Err(field.invalid(expected)),
+        });
+    }
+
+    let columns = (columns.into_iter())
+        .map(|(name, data_type, null, count)| {
+            let column = Column::new(name, data_type.unwrap_or(DataType::Text));
+            match null || count < objects.len() {
+                true => column.nullable(),
+                false => column,
+            }
+        })
+        .collect();
+    Ok(Schema::from_columns(columns))
+}
+
+/// A value of a row object. Nested arrays and objects are checked for syntax but not kept.
+enum Json {
+    Null,
+    Boolean(bool),
+    /// A number, whose digits are the text of its field; `integer` if it has neither a
+    /// fraction nor an exponent.
+    Number {
+        integer: bool,
+    },
+    String(String),
+    Array,
+    Object,
+}
+
+/// A key and value of a row object, with the source text of the value and its location.
+struct Field<'a> {
+    key: String,
+    value: Json,
+    text: &'a str,
+    line: usize,
+    /// The text of the line before the value.
+    before: &'a str,
+}
+
+impl Field<'_> {
+    /// Convert the value to the given type, if it has that type or is null. Integers are
+    /// also floats.
+    fn to_value(&self, data_type: DataType) -> Option<Value> {
+        match (&self.value, data_type) {
+            (Json::Null, _) => Some(Value::Null),
+            (Json::Boolean(bool), DataType::Boolean) => Some(Value::Boolean(*bool)),
+            (Json::Number { integer: true }, DataType::Integer) => {
+                self.text.parse().ok().map(Value::Integer)
+            }
+            (Json::Number { .. }, DataType::Float) => self.text.parse().ok().map(Value::Float),
+            (Json::String(text), DataType::Text) => Some(Value::Text(text.clone())),
+            _ => None,
+        }
+    }
+
+    fn invalid(&self, expected: DataType) -> Error {
+        Error::InvalidJsonValue {
+            line: self.line,
+            column: self.before.chars().count() + 1,
+            name: self.key.clone(),
+            expected,
+            text: self.text.to_string(),
+        }
+    }
+}
+
+/// A recursive descent parser over JSON text, following RFC 8259.
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    /// The 1-based line of the current position, and the position its line starts at.
+    line: usize,
+    line_start: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, line: usize) -> Self {
+        Parser {
+            input,
+            position: 0,
+            line,
+            line_start: 0,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::MalformedJson {
+            line: self.line,
+            column: self.input[self.line_start..self.position].chars().count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte @ (b' ' | b'\t' | b'\n' | b'\r')) = self.peek() {
+            self.position += 1;
+            if byte == b'\n' {
+                self.line += 1;
+                self.line_start = self.position;
+            }
+        }
+    }
+
+    /// Skip whitespace and the given byte, which `what` describes in the error if it is
+    /// not there.
+    fn expect(&mut self, byte: u8, what: &str) -> Result<()> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected {}", what)));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Skip whitespace and the given byte if it is there.
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn end(&mut self) -> Result<()> {
+        self.skip_whitespace();
+        match self.position < self.input.len() {
+            true => Err(self.error("unexpected text after the end of the value")),
+            false => Ok(()),
+        }
+    }
+
+    /// Parse an array of objects making up the whole input.
+    fn rows(&mut self) -> Result<Vec<Vec<Field<'a>>>> {
+        self.expect(b'[', "an array of objects")?;
+        let mut rows = Vec::new();
+        if !self.eat(b']') {
+            loop {
+                rows.push(self.object()?);
+                if self.eat(b']') {
+                    break;
+                }
+                self.expect(b',', "`,` or `]`")?;
+            }
+        }
+        self.end()?;
+        Ok(rows)
+    }
+
+    fn object(&mut self) -> Result<Vec<Field<'a>>> {
+        self.expect(b'{', "an object")?;
+        let mut fields: Vec<Field> = Vec::new();
+        if self.eat(b'}') {
+            return Ok(fields);
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string key"));
+            }
+            let start = self.position;
+            let key = self.string()?;
+            if fields.iter().any(|field| field.key == key) {
+                self.position = start;
+                return Err(self.error(&format!("duplicate key `{}`", key)));
+            }
+            self.expect(b':', "`:`")?;
+
+            self.skip_whitespace();
+            let (line, start) = (self.line, self.position);
+            let before = &self.input[self.line_start..start];
+            let value = self.value()?;
+            fields.push(Field {
+                key,
+                value,
+                text: &self.input[start..self.position],
+                line,
+                before,
+            });
+
+            if self.eat(b'}') {
+                return Ok(fields);
+            }
+            self.expect(b',', "`,` or `}`")?;
+        }
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b't') => self.literal("true", Json::Boolean(true)),
+            Some(b'f') => self.literal("false", Json::Boolean(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'[' | b'{') if self.depth == MAX_DEPTH => Err(self.error("nested too deeply")),
+            Some(b'[') => {
+                self.depth += 1;
+                self.position += 1;
+                if !self.eat(b']') {
+                    loop {
+                        self.value()?;
+                        if self.eat(b']') {
+                            break;
+                        }
+                        self.expect(b',', "`,` or `]`")?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Array)
+            }
+            Some(b'{') => {
+                self.depth += 1;
+                self.object()?;
+                self.depth -= 1;
+                Ok(Json::Object)
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn literal(&mut self, text: &str, value: Json) -> Result<Json> {
+        if !self.input[self.position..].starts_with(text) {
+            return Err(self.error("expected a value"));
+        }
+        self.position += text.len();
+        Ok(value)
+    }
+
+    /// Parse a number as `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`.
+    fn number(&mut self) -> Result<Json> {
+        let digits = |parser: &mut Parser| {
+            let start = parser.position;
+            while let Some(b'0'..=b'9') = parser.peek() {
+                parser.position += 1;
+            }
+            parser.position > start
+        };
+
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => {
+                digits(self);
+            }
+            _ => return Err(self.error("expected a digit")),
+        }
+
+        let mut integer = true;
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            integer = false;
+            if !digits(self) {
+                return Err(self.error("expected a digit"));
+            }
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.position += 1;
+            integer = false;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("expected a digit"));
+            }
+        }
+        Ok(Json::Number { integer })
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.position += 1;
+        let mut text = String::new();
+
+        loop {
+            let Some(char) = self.input[self.position..].chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            match char {
+                '"' => {
+                    self.position += 1;
+                    return Ok(text);
+                }
+                '\\' => {
+                    let start = self.position;
+                    self.position += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            text.push(self.unicode_escape(start)?);
+                            continue;
+                        }
+                        _ => {
+                            self.position = start;
+                            return Err(self.error("invalid escape"));
+                        }
+                    };
+                    self.position += 1;
+                    text.push(escaped);
+                }
+                char if char < ' ' => return Err(self.error("control character in string")),
+                char => {
+                    self.position += char.len_utf8();
+                    text.push(char);
+                }
+            }
+        }
+    }
+
+    /// Parse the `uXXXX` of a `\u` escape starting at `start`, and a second escape if the
+    /// first is the high half of a UTF-16 surrogate pair.
+    fn unicode_escape(&mut self, start: usize) -> Result<char> {
+        let char = match self.code_unit() {
+            Some(high @ 0xD800..=0xDBFF) => {
+                let low = match self.input[self.position..].starts_with("\\u") {
+                    true => {
+                        self.position += 1;
+                        self.code_unit()
+                    }
+                    false => None,
+                };
+                match low {
+                    Some(low @ 0xDC00..=0xDFFF) => {
+                        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                    }
+                    _ => None,
+                }
+            }
+            Some(unit) => char::from_u32(unit),
+            None => None,
+        };
+
+        char.ok_or_else(|| {
+            self.position = start;
+            self.error("invalid unicode escape")
+        })
+    }
+
+    /// Parse the `uXXXX` of a `\u` escape at the current position as a UTF-16 code unit.
+    fn code_unit(&mut self) -> Option<u32> {
+        let hex = self.input.get(self.position + 1..self.position + 5)?;
+        // `from_str_radix` would accept a sign.
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.position += 5;
+        u32::from_str_radix(hex, 16).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::sample;
+    use crate::tinycsv_db::insert;
+
+    fn line_and_column(result: Result<Database>) -> (usize, usize, String) {
+        match result {
+            Err(Error::MalformedJson {
+                line,
+                column,
+                message,
+            }) => (line, column, message),
+            other => panic!("expected malformed JSON, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let db = sample();
+        let json = to_json(&db);
+        let jsonl = to_jsonl(&db);
+        assert_eq!(jsonl.lines().count(), 4);
+        assert!(jsonl.contains(r#""name":"say \"hi\"\\\n\ttab\u0001""#));
+
+        for restored in [
+            Database::from_json_with_schema(&json, db.get_schema()).unwrap(),
+            Database::from_jsonl_with_schema(&jsonl, db.get_schema()).unwrap(),
+            Database::from_json(&json).unwrap(),
+            Database::from_jsonl(&jsonl).unwrap(),
+        ] {
+            assert_eq!(
+                format!("{:?}", restored.get_rows()),
+                format!("{:?}", db.get_rows())
+            );
+            let types: Vec<_> = (restored.get_schema().get_columns().iter())
+                .map(|column| (column.get_data_type(), column.is_nullable()))
+                .collect();
+            assert_eq!(
+                types[1..],
+                [
+                    (DataType::Text, false),
+                    (DataType::Float, true),
+                    (DataType::Boolean, true)
+                ]
+            );
+        }
+
+        let empty = Database::new(db.get_schema().clone());
+        assert_eq!(to_json(&empty), "[]\n");
+        assert_eq!(to_jsonl(&empty), "");
+        assert!(Database::from_json("[]").unwrap().get_rows().is_empty());
+    }
+
+    #[test]
+    fn test_non_finite_floats_are_written_as_null() {
+        let mut db = Database::new(Schema::from_columns(vec![
+            Column::new("x", DataType::Float).nullable(),
+        ]));
+        for num in [f64::NAN, f64::INFINITY, 2.5] {
+            insert(&mut db, Row::new(vec![Value::Float(num)])).unwrap();
+        }
+        assert_eq!(to_jsonl(&db), "{\"x\":null}\n{\"x\":null}\n{\"x\":2.5}\n");
+    }
+
+    #[test]
+    fn test_inferred_schema() {
+        let db = Database::from_jsonl(concat!(
+            "{\"a\": 1, \"b\": \"x\"}\n",
+            "\n",
+            "{\"c\": null, \"a\": 2.5e0, \"b\": \"\\ud83e\\udd80\\u00e9\"}\r\n",
+        ))
+        .unwrap();
+
+        let columns: Vec<_> = (db.get_schema().get_columns().iter())
+            .map(|column| {
+                (
+                    column.get_name(),
+                    column.get_data_type(),
+                    column.is_nullable(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                ("a", DataType::Float, false),
+                ("b", DataType::Text, false),
+                ("c", DataType::Text, true),
+            ]
+        );
+        assert_eq!(
+            db.get_rows()[1].get_values(),
+            &vec![
+                Value::Float(2.5),
+                Value::Text("🦀é".to_string()),
+                Value::Null
+            ]
+        );
+        // Too large for an i64, so a float.
+        let db = Database::from_json(r#"[{"n": 9223372036854775808}]"#).unwrap();
+        assert_eq!(
+            db.get_rows()[0].get_values()[0],
+            Value::Float(9.223372036854776e18)
+        );
+    }
+
+    #[test]
+    fn test_type_mismatches() {
+        let invalid = |result: Result<Database>| match result {
+            Err(Error::InvalidJsonValue {
+                line,
+                column,
+                name,
+                expected,
+                text,
+            }) => (line, column, name, expected, text),
+            other => panic!("expected an invalid value, got {:?}", other.map(|_| ())),
+        };
+
+        assert_eq!(
+            invalid(Database::from_jsonl("{\"a\": true}\n{\"a\": \"true\"}")),
+            (
+                2,
+                7,
+                "a".to_string(),
+                DataType::Boolean,
+                "\"true\"".to_string()
+            )
+        );
+        assert_eq!(
+            invalid(Database::from_json("[\n  {\"a\": [1, {\"b\": 2}]}\n]")),
+            (
+                2,
+                9,
+                "a".to_string(),
+                DataType::Text,
+                "[1, {\"b\": 2}]".to_string()
+            )
+        );
+
+        let schema = sample().get_schema().clone();
+        assert_eq!(
+            invalid(Database::from_json_with_schema(
+                r#"[{"id": 1.0, "name": "a"}]"#,
+                &schema
+            )),
+            (1, 9, "id".to_string(), DataType::Integer, "1.0".to_string())
+        );
+        assert_eq!(
+            invalid(Database::from_jsonl_with_schema(
+                r#"{"id": 1, "name": 2}"#,
+                &schema
+            ))
+            .4,
+            "2"
+        );
+        assert!(matches!(
+            Database::from_json_with_schema(r#"[{"id": 1, "email": "a"}]"#, &schema),
+            Err(Error::UnknownColumn(column)) if column == "email"
+        ));
+        assert!(matches!(
+            Database::from_json_with_schema(r#"[{"id": 1}]"#, &schema),
+            Err(Error::NullViolation(column)) if column == "name"
+        ));
+        assert!(matches!(
+            Database::from_jsonl_with_schema(
+                "{\"id\": 1, \"name\": \"a\"}\n{\"id\": 1, \"name\": \"b\"}",
+                &schema
+            ),
+            Err(Error::UniqueViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        let cases = [
+            ("", 1, 1, "expected an array of objects"),
+            ("{}", 1, 1, "expected an array of objects"),
+            ("[1]", 1, 2, "expected an object"),
+            ("[{}, ]", 1, 6, "expected an object"),
+            ("[{} {}]", 1, 5, "expected `,` or `]`"),
+            ("[{\"a\": 1,}]", 1, 10, "expected a string key"),
+            ("[{\"a\" 1}]", 1, 7, "expected `:`"),
+            ("[{\"a\": 1, \"a\": 2}]", 1, 11, "duplicate key `a`"),
+            ("[{\"a\": 01}]", 1, 9, "expected `,` or `}`"),
+            ("[{\"a\": -}]", 1, 9, "expected a digit"),
+            ("[{\"a\": 1.}]", 1, 10, "expected a digit"),
+            ("[{\"a\": tru}]", 1, 8, "expected a value"),
+            ("[{\"a\": \"x\ty\"}]", 1, 10, "control character in string"),
+            ("[\n{\"é\": \"\\x\"}]", 2, 8, "invalid escape"),
+            ("[{\"a\": \"\\ud800\"}]", 1, 9, "invalid unicode escape"),
+            ("[{\"a\": \"\\u+123\"}]", 1, 9, "invalid unicode escape"),
+            ("[{\"a\": \"x}]", 1, 12, "unterminated string"),
+            (
+                "[{}] []",
+                1,
+                6,
+                "unexpected text after the end of the value",
+            ),
+        ];
+        for (input, line, column, message) in cases {
+            assert_eq!(
+                line_and_column(Database::from_json(input)),
+                (line, column, message.to_string()),
+                "{}",
+                input
+            );
+        }
+
+        let nested = format!("[{{\"a\": {}}}]", "[".repeat(MAX_DEPTH + 1));
+        assert_eq!(
+            line_and_column(Database::from_json(&nested)).2,
+            "nested too deeply"
+        );
+        assert_eq!(
+            line_and_column(Database::from_jsonl("{}\n\n[]")),
+            (3, 1, "expected an object".to_string())
+        );
+    }
+}
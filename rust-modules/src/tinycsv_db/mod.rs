@@ -1,17 +1,53 @@
 // Declare all submodules
+mod aggregate;
+mod binary;
+mod catalog;
+mod codec;
+mod columnar;
 mod csv;
 mod database;
+mod error;
+mod filter;
+#[cfg(test)]
+mod fixtures;
+mod fulltext;
+mod fuzzy;
+mod index;
+mod join;
+mod json;
 mod operations;
 mod query;
+mod regex;
 mod row;
 mod schema;
+mod sql;
+mod transaction;
 mod types;
+mod unicode;
+mod wal;
 
 // Re-export public API
-pub use csv::to_csv;
+pub use aggregate::{Aggregate, AggregateFn, GroupBy};
+pub use catalog::Catalog;
+pub use columnar::{Bitmap, ColumnData, ColumnVector, ColumnarTable};
+pub use csv::{
+    CsvDialect, CsvRows, EscapeStyle, LineTerminator, to_csv, to_csv_typed, to_csv_with_dialect,
+};
 pub use database::Database;
-pub use operations::{delete, insert};
-pub use query::{find_contains, find_exact};
+pub use error::{Error, Result};
+pub use filter::{CompareOp, Filter, TextMatch};
+pub use fulltext::{find_prefix, find_words, search};
+pub use fuzzy::{
+    EditDistance, damerau_levenshtein, find_fuzzy, find_similar, levenshtein, trigram_similarity,
+};
+pub use index::IndexKind;
+pub use join::{Join, JoinKind, JoinStrategy};
+pub use json::{to_json, to_jsonl};
+pub use operations::{delete, delete_where, insert, update, update_where};
+pub use query::{Order, Query, ResultSet, find_contains, find_exact, find_null, find_where};
+pub use regex::Regex;
 pub use row::Row;
-pub use schema::Schema;
-pub use types::{DataType, Value};
+pub use schema::{Check, Column, Schema};
+pub use sql::{SqlResult, execute};
+pub use transaction::Transaction;
+pub use types::{Coercion, DataType, Value};
@@ -1,20 +1,725 @@
+use std::borrow::Cow;
+
 use super::database::Database;
+use super::error::Result;
+use super::filter::Filter;
+use super::index::check_unique;
 use super::row::Row;
+use super::types::Value;
+use super::wal::Record;
 
 /// Insert a new row into the database.
 ///
+/// The row is validated against the schema and its constraints, and its values are
+/// converted to the column types if the database allows it (see `Database::set_coercion`).
+///
 /// # Arguments
 /// * `db` - The database to insert the row into
 /// * `row` - The row to insert
-pub fn insert(db: &mut Database, row: Row) {
-    db.rows.push(row);
+///
+/// # Errors
+/// Returns `Error::ArityMismatch` if the row does not have one value per column,
+/// `Error::NullViolation` if a value is `Value::Null` in a NOT NULL column without a default,
+/// `Error::TypeMismatch` if a value does not match the data type of its column,
+/// `Error::CheckViolation` if a value fails a CHECK constraint and `Error::UniqueViolation`
+/// if a value already exists in a UNIQUE or primary key column.
+pub fn insert(db: &mut Database, row: Row) -> Result<()> {
+    insert_all(db, vec![row]).map(|_| ())
+}
+
+/// Insert several rows at once, inserting none of them if any row is rejected.
+///
+/// Returns the number of inserted rows.
+pub(crate) fn insert_all(db: &mut Database, rows: Vec<Row>) -> Result<usize> {
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            Ok(Row {
+                values: db.schema.validate(row.values, db.coercion)?,
+            })
+        })
+        .collect::<Result<Vec<Row>>>()?;
+
+    check_unique(&db.unique, &rows, |column| {
+        db.schema.columns[column].name.clone()
+    })?;
+    db.log(Record::Insert(Cow::Borrowed(&rows)))?;
+
+    for index in &mut db.unique {
+        for row in &rows {
+            index.add(row);
+        }
+    }
+
+    let count = rows.len();
+    db.rows.reserve(count);
+    for row in rows {
+        for index in &mut db.indexes {
+            index.push(&row, db.rows.len());
+        }
+        for index in &mut db.text_indexes {
+            index.push(&row, db.rows.len());
+        }
+        for index in &mut db.trigram_indexes {
+            index.push(&row, db.rows.len());
+        }
+        db.rows.push(row);
+    }
+    Ok(count)
+}
+
+/// Fill an empty database with rows that already fit its schema, keeping them as they are:
+/// column defaults are not applied and nothing is logged.
+///
+/// # Errors
+/// Returns `Error::UniqueViolation` if a UNIQUE or primary key column repeats a value.
+pub(crate) fn load_rows(db: &mut Database, rows: Vec<Row>) -> Result<()> {
+    check_unique(&db.unique, &rows, |column| {
+        db.schema.columns[column].name.clone()
+    })?;
+    for index in &mut db.unique {
+        for row in &rows {
+            index.add(row);
+        }
+    }
+    db.rows = rows;
+    Ok(())
 }
 
 /// Delete rows from the database based on a predicate.
 ///
 /// # Arguments
 /// * `db` - The database to delete rows from
-/// * `predicate` - A predicate function to filter rows to delete
-pub fn delete(db: &mut Database, predicate: fn(&Row) -> bool) {
-    db.rows.retain(|row| !predicate(row));
+/// * `predicate` - A predicate closure selecting the rows to delete
+///
+/// # Returns
+/// The number of deleted rows.
+///
+/// # Errors
+/// Returns `Error::Io` if the database is stored in a file and the change cannot be logged.
+pub fn delete(db: &mut Database, predicate: impl FnMut(&Row) -> bool) -> Result<usize> {
+    delete_rows(db, predicate).map(|(deleted, _)| deleted.len())
+}
+
+/// Delete rows like `delete`, returning their former positions and the rows themselves.
+pub(crate) fn delete_rows(
+    db: &mut Database,
+    mut predicate: impl FnMut(&Row) -> bool,
+) -> Result<(Vec<usize>, Vec<Row>)> {
+    let deleted: Vec<usize> = (0..db.rows.len())
+        .filter(|&position| predicate(&db.rows[position]))
+        .collect();
+
+    if deleted.is_empty() {
+        return Ok((deleted, Vec::new()));
+    }
+    db.log(Record::Delete(Cow::Borrowed(&deleted)))?;
+    let removed = remove_rows(db, &deleted);
+    Ok((deleted, removed))
+}
+
+/// Remove the rows at the given ascending positions, keeping the indexes up to date.
+///
+/// Returns the removed rows.
+pub(crate) fn remove_rows(db: &mut Database, deleted: &[usize]) -> Vec<Row> {
+    let mut kept = Vec::with_capacity(db.rows.len() - deleted.len());
+    let mut removed = Vec::with_capacity(deleted.len());
+
+    for (position, row) in db.rows.drain(..).enumerate() {
+        if deleted.binary_search(&position).is_ok() {
+            for index in &mut db.unique {
+                index.remove(&row);
+            }
+            removed.push(row);
+        } else {
+            kept.push(row);
+        }
+    }
+
+    db.rows = kept;
+    for index in &mut db.indexes {
+        index.remove(deleted, &removed);
+    }
+    for index in &mut db.text_indexes {
+        index.remove(deleted);
+    }
+    for index in &mut db.trigram_indexes {
+        index.remove(deleted);
+    }
+    removed
+}
+
+/// Put rows removed by `remove_rows` back at their former ascending positions, keeping the
+/// indexes up to date.
+pub(crate) fn restore_rows(db: &mut Database, positions: &[usize], rows: Vec<Row>) {
+    for index in &mut db.unique {
+        for row in &rows {
+            index.add(row);
+        }
+    }
+    for index in &mut db.indexes {
+        index.restore(positions, &rows);
+    }
+    for index in &mut db.text_indexes {
+        index.restore(positions, &rows);
+    }
+    for index in &mut db.trigram_indexes {
+        index.restore(positions, &rows);
+    }
+
+    let mut merged = Vec::with_capacity(db.rows.len() + rows.len());
+    let mut kept = std::mem::take(&mut db.rows).into_iter();
+    for (&position, row) in positions.iter().zip(rows) {
+        merged.extend(kept.by_ref().take(position - merged.len()));
+        merged.push(row);
+    }
+    merged.extend(kept);
+    db.rows = merged;
+}
+
+/// Update rows in place that match a predicate, keeping their order.
+///
+/// The assigned values are validated like inserted values, and either every matching row
+/// is updated or, if any constraint fails, none is.
+///
+/// # Arguments
+/// * `db` - The database to update rows in
+/// * `predicate` - A predicate closure selecting the rows to update
+/// * `assignments` - Pairs of column name and the new value to store in that column
+///
+/// # Returns
+/// The number of updated rows.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if an assigned column does not exist,
+/// `Error::TypeMismatch` if a new value does not match the data type of its column,
+/// `Error::NullViolation` if it is `Value::Null` in a NOT NULL column,
+/// `Error::CheckViolation` if it fails a CHECK constraint, `Error::UniqueViolation` if the
+/// update would repeat a value of a UNIQUE or primary key column and `Error::Io` if the
+/// database is stored in a file and the change cannot be logged.
+pub fn update(
+    db: &mut Database,
+    predicate: impl FnMut(&Row) -> bool,
+    assignments: &[(&str, Value)],
+) -> Result<usize> {
+    update_rows(db, predicate, assignments).map(|(positions, _)| positions.len())
+}
+
+/// Update rows like `update`, returning their positions and the rows as they were before.
+pub(crate) fn update_rows(
+    db: &mut Database,
+    mut predicate: impl FnMut(&Row) -> bool,
+    assignments: &[(&str, Value)],
+) -> Result<(Vec<usize>, Vec<Row>)> {
+    let mut resolved = Vec::with_capacity(assignments.len());
+
+    for (column_name, value) in assignments {
+        let index = db.schema.column_index(column_name)?;
+        let value = db.schema.columns[index].validate(value.clone(), db.coercion)?;
+        resolved.push((index, value));
+    }
+
+    let positions: Vec<usize> = (0..db.rows.len())
+        .filter(|&position| predicate(&db.rows[position]))
+        .collect();
+    let updated: Vec<Row> = positions
+        .iter()
+        .map(|&position| {
+            let mut values = db.rows[position].values.clone();
+            for (index, value) in &resolved {
+                values[*index] = value.clone();
+            }
+            Row { values }
+        })
+        .collect();
+
+    for &position in &positions {
+        for index in &mut db.unique {
+            index.remove(&db.rows[position]);
+        }
+    }
+
+    let checked = check_unique(&db.unique, &updated, |column| {
+        db.schema.columns[column].name.clone()
+    });
+    let logged = checked.and_then(|()| match positions.is_empty() {
+        true => Ok(()),
+        false => db.log(Record::Update(
+            Cow::Borrowed(&positions),
+            Cow::Borrowed(&updated),
+        )),
+    });
+    if let Err(error) = logged {
+        for &position in &positions {
+            for index in &mut db.unique {
+                index.add(&db.rows[position]);
+            }
+        }
+        return Err(error);
+    }
+
+    let replaced = replace_rows(db, &positions, updated);
+    Ok((positions, replaced))
+}
+
+/// Store updated rows at their positions, keeping the indexes up to date.
+///
+/// The values of the rows being replaced must already be removed from the unique indexes.
+/// Returns the replaced rows.
+pub(crate) fn replace_rows(db: &mut Database, positions: &[usize], updated: Vec<Row>) -> Vec<Row> {
+    let mut replaced = Vec::with_capacity(positions.len());
+
+    for (&position, row) in positions.iter().zip(updated) {
+        let old = &db.rows[position];
+
+        for index in &mut db.unique {
+            index.add(&row);
+        }
+        for index in &mut db.indexes {
+            if old.values[index.column] != row.values[index.column] {
+                index.replace(position, old, &row);
+            }
+        }
+        for index in &mut db.text_indexes {
+            if old.values[index.column] != row.values[index.column] {
+                index.replace(position, old, &row);
+            }
+        }
+        for index in &mut db.trigram_indexes {
+            if old.values[index.column] != row.values[index.column] {
+                index.replace(position, old, &row);
+            }
+        }
+        replaced.push(std::mem::replace(&mut db.rows[position], row));
+    }
+    replaced
+}
+
+/// Delete rows from the database that match a filter.
+///
+/// # Arguments
+/// * `db` - The database to delete rows from
+/// * `filter` - The filter selecting the rows to delete
+///
+/// # Returns
+/// The number of deleted rows.
+///
+/// # Errors
+/// Returns the errors of `Filter::compile` if the filter does not fit the schema, and the
+/// errors of `delete`.
+pub fn delete_where(db: &mut Database, filter: &Filter) -> Result<usize> {
+    let predicate = filter.compile(&db.schema)?;
+    delete(db, predicate)
+}
+
+/// Update rows in place that match a filter, keeping their order.
+///
+/// # Arguments
+/// * `db` - The database to update rows in
+/// * `filter` - The filter selecting the rows to update
+/// * `assignments` - Pairs of column name and the new value to store in that column
+///
+/// # Returns
+/// The number of updated rows.
+///
+/// # Errors
+/// Returns the errors of `Filter::compile` if the filter does not fit the schema, and the
+/// errors of `update`.
+pub fn update_where(
+    db: &mut Database,
+    filter: &Filter,
+    assignments: &[(&str, Value)],
+) -> Result<usize> {
+    let predicate = filter.compile(&db.schema)?;
+    update(db, predicate, assignments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::table;
+    use crate::tinycsv_db::{Check, Coercion, Column, DataType, Error, Schema};
+
+    fn users() -> Database {
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_insert_accepts_matching_row() {
+        let mut db = users();
+        let row = Row::new(vec![Value::Integer(1), Value::Text("Alice".to_string())]);
+
+        assert!(insert(&mut db, row).is_ok());
+        assert_eq!(db.get_rows().len(), 1);
+    }
+
+    #[test]
+    fn test_insert_rejects_wrong_arity() {
+        let mut db = users();
+        let result = insert(&mut db, Row::new(vec![Value::Integer(1)]));
+
+        assert!(matches!(
+            result,
+            Err(Error::ArityMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert_eq!(db.get_rows().len(), 0);
+    }
+
+    #[test]
+    fn test_insert_rejects_wrong_type() {
+        let mut db = users();
+        let result = insert(
+            &mut db,
+            Row::new(vec![
+                Value::Text("1".to_string()),
+                Value::Text("Alice".to_string()),
+            ]),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::TypeMismatch {
+                ref column,
+                expected: DataType::Integer,
+                found: DataType::Text,
+            }) if column == "id"
+        ));
+        assert_eq!(db.get_rows().len(), 0);
+    }
+
+    #[test]
+    fn test_insert_rejects_null_in_non_nullable_column() {
+        let mut db = users();
+        let result = insert(&mut db, Row::new(vec![Value::Integer(1), Value::Null]));
+
+        assert!(matches!(result, Err(Error::NullViolation(column)) if column == "name"));
+    }
+
+    #[test]
+    fn test_insert_accepts_null_in_nullable_column() {
+        let mut db = Database::new(Schema::from_columns(vec![
+            Column::new("id", DataType::Integer),
+            Column::new("name", DataType::Text).nullable(),
+        ]));
+
+        assert!(insert(&mut db, Row::new(vec![Value::Integer(1), Value::Null])).is_ok());
+    }
+
+    #[test]
+    fn test_insert_with_lenient_coercion() {
+        let mut db = Database::new(Schema::new(vec![
+            ("id".to_string(), DataType::Integer),
+            ("score".to_string(), DataType::Float),
+            ("label".to_string(), DataType::Text),
+        ]));
+        db.set_coercion(Coercion::Lenient);
+
+        insert(
+            &mut db,
+            Row::new(vec![
+                Value::Text(" 7 ".to_string()),
+                Value::Integer(95),
+                Value::Boolean(true),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.get_rows()[0].get_values(),
+            &vec![
+                Value::Integer(7),
+                Value::Float(95.0),
+                Value::Text("true".to_string()),
+            ]
+        );
+
+        let result = insert(
+            &mut db,
+            Row::new(vec![
+                Value::Float(1.5),
+                Value::Float(1.5),
+                Value::Text(String::new()),
+            ]),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::TypeMismatch {
+                expected: DataType::Integer,
+                found: DataType::Float,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_delete_returns_deleted_count() {
+        let mut db = users();
+        for id in 1..=3 {
+            insert(
+                &mut db,
+                Row::new(vec![Value::Integer(id), Value::Text("x".to_string())]),
+            )
+            .unwrap();
+        }
+
+        let deleted = delete(&mut db, |row| row.get_value(0) != Some(&Value::Integer(2)));
+
+        assert_eq!(deleted.unwrap(), 2);
+        assert_eq!(db.get_rows().len(), 1);
+    }
+
+    fn accounts() -> Database {
+        filled_accounts(0)
+    }
+
+    fn account(id: i64, email: Value, balance: Value) -> Row {
+        Row::new(vec![Value::Integer(id), email, balance])
+    }
+
+    #[test]
+    fn test_insert_rejects_duplicate_primary_key() {
+        let mut db = accounts();
+        insert(&mut db, account(1, Value::Null, Value::Float(1.0))).unwrap();
+
+        let result = insert(&mut db, account(1, Value::Null, Value::Float(2.0)));
+
+        assert!(matches!(
+            result,
+            Err(Error::UniqueViolation {
+                ref column,
+                value: Value::Integer(1),
+            }) if column == "id"
+        ));
+        assert_eq!(db.get_rows().len(), 1);
+    }
+
+    #[test]
+    fn test_insert_rejects_null_primary_key() {
+        let mut db = accounts();
+        let result = insert(
+            &mut db,
+            Row::new(vec![Value::Null, Value::Null, Value::Float(1.0)]),
+        );
+
+        assert!(matches!(result, Err(Error::NullViolation(column)) if column == "id"));
+    }
+
+    #[test]
+    fn test_unique_allows_many_nulls_but_not_repeated_values() {
+        let mut db = accounts();
+        let email = || Value::Text("a@example.com".to_string());
+        insert(&mut db, account(1, Value::Null, Value::Float(0.0))).unwrap();
+        insert(&mut db, account(2, Value::Null, Value::Float(0.0))).unwrap();
+        insert(&mut db, account(3, email(), Value::Float(0.0))).unwrap();
+
+        let result = insert(&mut db, account(4, email(), Value::Float(0.0)));
+
+        assert!(
+            matches!(result, Err(Error::UniqueViolation { ref column, .. }) if column == "email")
+        );
+    }
+
+    #[test]
+    fn test_delete_frees_unique_values() {
+        let mut db = accounts();
+        insert(&mut db, account(1, Value::Null, Value::Float(0.0))).unwrap();
+        delete(&mut db, |row| row.get_value(0) == Some(&Value::Integer(1))).unwrap();
+
+        assert!(insert(&mut db, account(1, Value::Null, Value::Float(0.0))).is_ok());
+    }
+
+    #[test]
+    fn test_insert_applies_default() {
+        let mut db = accounts();
+        insert(&mut db, account(1, Value::Null, Value::Null)).unwrap();
+
+        assert_eq!(db.get_rows()[0].get_value(2), Some(&Value::Float(0.0)));
+    }
+
+    #[test]
+    fn test_insert_rejects_failed_check() {
+        let mut db = accounts();
+        let result = insert(&mut db, account(1, Value::Null, Value::Float(-5.0)));
+
+        assert!(matches!(
+            result,
+            Err(Error::CheckViolation { ref column, ref check })
+                if column == "balance" && check == "non_negative"
+        ));
+    }
+
+    /// Accounts 1 to `count`, each with its own email and its id as balance.
+    fn filled_accounts(count: i64) -> Database {
+        table(
+            vec![
+                Column::new("id", DataType::Integer).primary_key(),
+                Column::new("email", DataType::Text).nullable().unique(),
+                Column::new("balance", DataType::Float)
+                    .default(Value::Float(0.0))
+                    .check(Check::new(
+                        "non_negative",
+                        |value| matches!(value, Value::Float(num) if *num >= 0.0),
+                    )),
+            ],
+            (1..=count)
+                .map(|id| {
+                    let email = Value::Text(format!("user{}@example.com", id));
+                    account(id, email, Value::Float(id as f64)).values
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_update_in_place_with_capturing_closure() {
+        let mut db = filled_accounts(4);
+        let ids = [Value::Integer(2), Value::Integer(4)];
+
+        let updated = update(
+            &mut db,
+            |row| ids.contains(&row.values[0]),
+            &[("balance", Value::Float(100.0))],
+        )
+        .unwrap();
+
+        assert_eq!(updated, 2);
+        let balances: Vec<&Value> = db.get_rows().iter().map(|row| &row.values[2]).collect();
+        assert_eq!(
+            balances,
+            vec![
+                &Value::Float(1.0),
+                &Value::Float(100.0),
+                &Value::Float(3.0),
+                &Value::Float(100.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_unknown_column() {
+        let mut db = filled_accounts(4);
+        let result = update(&mut db, |_| true, &[("balanse", Value::Float(0.0))]);
+
+        assert!(matches!(result, Err(Error::UnknownColumn(column)) if column == "balanse"));
+    }
+
+    #[test]
+    fn test_update_validates_new_values() {
+        let mut db = filled_accounts(4);
+
+        let result = update(
+            &mut db,
+            |_| true,
+            &[("balance", Value::Text("0".to_string()))],
+        );
+        assert!(matches!(result, Err(Error::TypeMismatch { .. })));
+
+        let result = update(&mut db, |_| true, &[("balance", Value::Float(-1.0))]);
+        assert!(matches!(result, Err(Error::CheckViolation { .. })));
+    }
+
+    #[test]
+    fn test_update_to_null_ignores_default() {
+        let mut db = filled_accounts(4);
+        let before = db.get_rows().clone();
+
+        let result = update(&mut db, |_| true, &[("balance", Value::Null)]);
+
+        assert!(matches!(result, Err(Error::NullViolation(column)) if column == "balance"));
+        assert_eq!(db.get_rows(), &before);
+    }
+
+    #[test]
+    fn test_update_unique_violation_changes_nothing() {
+        let mut db = filled_accounts(4);
+        let email = Value::Text("shared@example.com".to_string());
+
+        let result = update(
+            &mut db,
+            |row| row.values[0] != Value::Integer(1),
+            &[("email", email.clone())],
+        );
+
+        assert!(
+            matches!(result, Err(Error::UniqueViolation { ref column, .. }) if column == "email")
+        );
+        assert_eq!(
+            db.get_rows()[1].get_value(1),
+            Some(&Value::Text("user2@example.com".to_string()))
+        );
+
+        // The original values are still indexed, and a single row may take the new value.
+        let duplicate = account(5, Value::Text("user2@example.com".to_string()), Value::Null);
+        assert!(matches!(
+            insert(&mut db, duplicate),
+            Err(Error::UniqueViolation { .. })
+        ));
+        assert_eq!(
+            update(
+                &mut db,
+                |row| row.values[0] == Value::Integer(1),
+                &[("email", email)]
+            )
+            .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_update_primary_key_to_own_value() {
+        let mut db = filled_accounts(4);
+        let updated = update(
+            &mut db,
+            |row| row.values[0] == Value::Integer(3),
+            &[("id", Value::Integer(3))],
+        );
+
+        assert_eq!(updated.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_delete_with_capturing_closure() {
+        let mut db = filled_accounts(4);
+        let doomed = std::collections::HashSet::from([1, 3]);
+
+        let deleted = delete(
+            &mut db,
+            |row| matches!(row.values[0], Value::Integer(id) if doomed.contains(&id)),
+        );
+
+        assert_eq!(deleted.unwrap(), 2);
+        assert_eq!(db.get_rows().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_where_and_update_where() {
+        let mut db = filled_accounts(4);
+        let rich = Filter::ge("balance", Value::Float(3.0));
+
+        assert_eq!(
+            update_where(&mut db, &rich, &[("email", Value::Null)]).unwrap(),
+            2
+        );
+        assert_eq!(delete_where(&mut db, &Filter::is_null("email")).unwrap(), 2);
+        assert_eq!(db.get_rows().len(), 2);
+        assert_eq!(
+            delete_where(&mut db, &Filter::regex("email", r"^user[2-9]@")).unwrap(),
+            1
+        );
+        assert_eq!(db.get_rows().len(), 1);
+        assert!(matches!(
+            delete_where(&mut db, &Filter::is_null("mail")),
+            Err(Error::UnknownColumn(_))
+        ));
+    }
 }
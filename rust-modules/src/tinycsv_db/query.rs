@@ -1,6 +1,11 @@
+use std::ops::Bound;
+
 use super::database::Database;
+use super::error::{Error, Result};
+use super::filter::{CompareOp, Filter};
+use super::index::Index;
 use super::row::Row;
-use super::types::Value;
+use super::types::{DataType, Value};
 
 /// Find rows in the database with an exact match for a value in a specific column.
 ///
@@ -11,18 +16,59 @@ use super::types::Value;
 ///
 /// # Returns
 /// A vector of references to rows that match the exact value in the specified column.
-pub fn find_exact<'a>(db: &'a Database, column_name: &str, value: &Value) -> Vec<&'a Row> {
-    let column_index = db
-        .schema
-        .columns
-        .iter()
-        .position(|(column, _)| column == column_name)
-        .unwrap();
+/// Comparisons follow SQL three-valued logic, so `Value::Null` never matches; use
+/// `find_null` to find missing values.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+/// if the value does not have the data type of the column.
+pub fn find_exact<'a>(db: &'a Database, column_name: &str, value: &Value) -> Result<Vec<&'a Row>> {
+    let column_index = db.schema.column_index(column_name)?;
+    let expected = db.schema.columns[column_index].data_type;
+
+    if let Some(found) = value.data_type()
+        && found != expected
+    {
+        return Err(Error::TypeMismatch {
+            column: column_name.to_string(),
+            expected,
+            found,
+        });
+    }
+
+    let matches = |row: &&Row| row.values[column_index].sql_eq(value) == Some(true);
+
+    if let Some(index) = db.indexes.iter().find(|index| index.column == column_index) {
+        return Ok(index
+            .get(value)
+            .into_iter()
+            .map(|position| &db.rows[position])
+            .filter(matches)
+            .collect());
+    }
+
+    Ok(db.rows.iter().filter(matches).collect())
+}
 
-    db.rows
+/// Find rows in the database with a `Value::Null` in a specific column.
+///
+/// # Arguments
+/// * `db` - The database to search in
+/// * `column_name` - The name of the column to search in
+///
+/// # Returns
+/// A vector of references to rows whose value in the specified column is `Value::Null`.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if the column does not exist.
+pub fn find_null<'a>(db: &'a Database, column_name: &str) -> Result<Vec<&'a Row>> {
+    let column_index = db.schema.column_index(column_name)?;
+
+    Ok(db
+        .rows
         .iter()
-        .filter(|row| &row.values[column_index] == value)
-        .collect()
+        .filter(|row| row.values[column_index].is_null())
+        .collect())
 }
 
 /// Find rows in the database with a partial match for a text in a specific column.
@@ -34,15 +80,26 @@ pub fn find_exact<'a>(db: &'a Database, column_name: &str, value: &Value) -> Vec
 ///
 /// # Returns
 /// A vector of references to rows that contain the specified text in the specified column.
-pub fn find_contains<'a>(db: &'a Database, column_name: &str, text: &str) -> Vec<&'a Row> {
-    let column_index = db
-        .schema
-        .columns
-        .iter()
-        .position(|(column, _)| column == column_name)
-        .unwrap();
+/// The match is a case-sensitive substring scan; `Filter::contains_ignore_case` ignores
+/// case, and `find_words` and `find_prefix` match whole words through a text index instead.
+///
+/// # Errors
+/// Returns `Error::UnknownColumn` if the column does not exist and `Error::TypeMismatch`
+/// if the column is not a text column.
+pub fn find_contains<'a>(db: &'a Database, column_name: &str, text: &str) -> Result<Vec<&'a Row>> {
+    let column_index = db.schema.column_index(column_name)?;
+    let found = db.schema.columns[column_index].data_type;
+
+    if found != DataType::Text {
+        return Err(Error::TypeMismatch {
+            column: column_name.to_string(),
+            expected: DataType::Text,
+            found,
+        });
+    }
 
-    db.rows
+    Ok(db
+        .rows
         .iter()
         .filter(|row| {
             if let Value::Text(s) = &row.values[column_index] {
@@ -51,5 +108,632 @@ pub fn find_contains<'a>(db: &'a Database, column_name: &str, text: &str) -> Vec
                 false
             }
         })
-        .collect()
+        .collect())
+}
+
+/// Find rows in the database that match a filter.
+///
+/// # Arguments
+/// * `db` - The database to search in
+/// * `filter` - The filter the rows must match
+///
+/// # Returns
+/// A vector of references to rows that match the filter.
+///
+/// # Errors
+/// Returns the errors of `Filter::compile` if the filter does not fit the schema.
+pub fn find_where<'a>(db: &'a Database, filter: &Filter) -> Result<Vec<&'a Row>> {
+    rows_matching(db, std::slice::from_ref(filter))
+}
+
+/// The rows matching every filter, in database order, looked up through the indexes of the
+/// database when the filters allow it.
+fn rows_matching<'a>(db: &'a Database, filters: &[Filter]) -> Result<Vec<&'a Row>> {
+    let predicates = filters
+        .iter()
+        .map(|filter| filter.compile(&db.schema))
+        .collect::<Result<Vec<_>>>()?;
+    let matches = |row: &&Row| predicates.iter().all(|matches| matches(row));
+    let positions = filters
+        .iter()
+        .filter_map(|filter| indexed_positions(db, filter))
+        .reduce(|a, b| intersect(&a, &b));
+
+    Ok(match positions {
+        Some(positions) => positions
+            .into_iter()
+            .map(|position| &db.rows[position])
+            .filter(matches)
+            .collect(),
+        None => db.rows.iter().filter(matches).collect(),
+    })
+}
+
+/// The ascending positions of the rows that may match the filter, found through the indexes,
+/// or `None` if the filter needs a full scan. The filter must still be checked on the rows.
+fn indexed_positions(db: &Database, filter: &Filter) -> Option<Vec<usize>> {
+    // Values of another type than the column are left to the scan, since index keys order
+    // an integer before an equal float.
+    let index = |column: &str, values: &[&Value]| -> Option<&Index> {
+        let column = db.schema.column_index(column).ok()?;
+        let data_type = db.schema.columns[column].data_type;
+        if values
+            .iter()
+            .any(|value| value.data_type() != Some(data_type))
+        {
+            return None;
+        }
+        db.indexes.iter().find(|index| index.column == column)
+    };
+
+    match filter {
+        Filter::Compare { column, op, value } => {
+            let index = index(column, &[value])?;
+            match op {
+                CompareOp::Eq => Some(index.get(value)),
+                CompareOp::Ne => None,
+                CompareOp::Lt => index.range(Bound::Unbounded, Bound::Excluded(value)),
+                CompareOp::Le => index.range(Bound::Unbounded, Bound::Included(value)),
+                CompareOp::Gt => index.range(Bound::Excluded(value), Bound::Unbounded),
+                CompareOp::Ge => index.range(Bound::Included(value), Bound::Unbounded),
+            }
+        }
+        Filter::Between { column, low, high } => {
+            index(column, &[low, high])?.range(Bound::Included(low), Bound::Included(high))
+        }
+        Filter::In { column, values } => {
+            let index = index(column, &values.iter().collect::<Vec<_>>())?;
+            let mut positions: Vec<usize> =
+                values.iter().flat_map(|value| index.get(value)).collect();
+            positions.sort_unstable();
+            positions.dedup();
+            Some(positions)
+        }
+        Filter::And(a, b) => match (indexed_positions(db, a), indexed_positions(db, b)) {
+            (Some(a), Some(b)) => Some(intersect(&a, &b)),
+            (positions, None) | (None, positions) => positions,
+        },
+        Filter::Or(a, b) => {
+            let mut positions = indexed_positions(db, a)?;
+            positions.extend(indexed_positions(db, b)?);
+            positions.sort_unstable();
+            positions.dedup();
+            Some(positions)
+        }
+        Filter::IsNull(_) | Filter::Text { .. } | Filter::Not(_) => None,
+    }
+}
+
+/// The positions in both ascending lists.
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let (mut i, mut j) = (0, 0);
+    let mut positions = Vec::new();
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                positions.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    positions
+}
+
+/// The direction of an ORDER BY column.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// The rows returned by a query, together with the names of their projected columns.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ResultSet {
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Row>,
+}
+
+impl ResultSet {
+    pub fn get_columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    pub fn get_rows(&self) -> &Vec<Row> {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Find the index of a projected column by its name.
+    ///
+    /// # Returns
+    /// The position of the column in the result rows, or `Error::UnknownColumn`.
+    pub fn column_index(&self, column_name: &str) -> Result<usize> {
+        self.columns
+            .iter()
+            .position(|column| column == column_name)
+            .ok_or_else(|| Error::UnknownColumn(column_name.to_string()))
+    }
+}
+
+/// A query over a database, built step by step and run with `Query::execute`.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let db = Database::from_csv("id,name,score\n1,Alice,95.5\n2,Bob,80\n3,Carol,88\n").unwrap();
+/// let result = db
+///     .query()
+///     .select(&["name"])
+///     .filter(Filter::ge("score", Value::Float(85.0)))
+///     .order_by("score", Order::Desc)
+///     .limit(1)
+///     .execute()
+///     .unwrap();
+///
+/// assert_eq!(result.get_columns(), &vec!["name".to_string()]);
+/// assert_eq!(result.get_rows()[0].get_value(0), Some(&Value::Text("Alice".to_string())));
+/// ```
+pub struct Query<'a> {
+    db: &'a Database,
+    columns: Option<Vec<String>>,
+    filters: Vec<Filter>,
+    order_by: Vec<(String, Order)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+impl Database {
+    /// Start a query selecting every column of every row.
+    pub fn query(&self) -> Query<'_> {
+        Query {
+            db: self,
+            columns: None,
+            filters: Vec::new(),
+            order_by: Vec::new(),
+            limit: None,
+            offset: 0,
+        }
+    }
+}
+
+impl<'a> Query<'a> {
+    /// Project the result onto the given columns, in the given order.
+    pub fn select(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|column| column.to_string()).collect());
+        self
+    }
+
+    /// Keep only rows matching the filter; several filters must all match.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sort by a column; later calls break ties of earlier ones.
+    pub fn order_by(mut self, column: &str, order: Order) -> Self {
+        self.order_by.push((column.to_string(), order));
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Run the query.
+    ///
+    /// Rows are filtered, sorted (with `Value::total_cmp`, so `Value::Null` comes first in
+    /// ascending order), skipped by the offset, cut by the limit and then projected.
+    ///
+    /// # Returns
+    /// The projected rows and their column names.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownColumn` if a selected, filtered or sorted column does not exist,
+    /// and `Error::TypeMismatch` if a filter value cannot be compared with its column.
+    pub fn execute(&self) -> Result<ResultSet> {
+        let schema = &self.db.schema;
+        let projection: Vec<usize> = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| schema.column_index(column))
+                .collect::<Result<_>>()?,
+            None => (0..schema.columns.len()).collect(),
+        };
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|(column, order)| Ok((schema.column_index(column)?, *order)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows = rows_matching(self.db, &self.filters)?;
+
+        if !order_by.is_empty() {
+            rows.sort_by(|a, b| {
+                order_by
+                    .iter()
+                    .map(|&(index, order)| {
+                        let ordering = a.values[index].total_cmp(&b.values[index]);
+                        match order {
+                            Order::Asc => ordering,
+                            Order::Desc => ordering.reverse(),
+                        }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+
+        let rows = rows
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|row| Row {
+                values: projection
+                    .iter()
+                    .map(|&index| row.values[index].clone())
+                    .collect(),
+            })
+            .collect();
+
+        Ok(ResultSet {
+            columns: projection
+                .iter()
+                .map(|&index| schema.columns[index].name.clone())
+                .collect(),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{Column, IndexKind, Schema, delete, insert, update};
+
+    fn users() -> Database {
+        let user = |id, name| vec![Value::Integer(id), text(name)];
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            vec![user(1, "Alice"), user(2, "Bob"), user(3, "Alicia")],
+        )
+    }
+
+    #[test]
+    fn test_find_exact() {
+        let db = users();
+        let rows = find_exact(&db, "id", &Value::Integer(2)).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_value(1), Some(&Value::Text("Bob".to_string())));
+    }
+
+    #[test]
+    fn test_find_exact_unknown_column() {
+        let db = users();
+        let result = find_exact(&db, "nmae", &Value::Integer(2));
+
+        assert!(matches!(result, Err(Error::UnknownColumn(column)) if column == "nmae"));
+    }
+
+    #[test]
+    fn test_find_exact_type_mismatch() {
+        let db = users();
+        let result = find_exact(&db, "id", &Value::Text("2".to_string()));
+
+        assert!(matches!(
+            result,
+            Err(Error::TypeMismatch {
+                expected: DataType::Integer,
+                found: DataType::Text,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_find_contains() {
+        let db = users();
+        let rows = find_contains(&db, "name", "lic").unwrap();
+
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn test_find_contains_unknown_column() {
+        let db = users();
+        let result = find_contains(&db, "title", "lic");
+
+        assert!(matches!(result, Err(Error::UnknownColumn(column)) if column == "title"));
+    }
+
+    #[test]
+    fn test_find_contains_non_text_column() {
+        let db = users();
+        let result = find_contains(&db, "id", "1");
+
+        assert!(matches!(
+            result,
+            Err(Error::TypeMismatch {
+                expected: DataType::Text,
+                found: DataType::Integer,
+                ..
+            })
+        ));
+    }
+
+    fn with_missing_names() -> Database {
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text).nullable(),
+            ],
+            vec![
+                vec![Value::Integer(1), text("Alice")],
+                vec![Value::Integer(2), Value::Null],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_find_exact_null_matches_nothing() {
+        let db = with_missing_names();
+
+        assert!(find_exact(&db, "name", &Value::Null).unwrap().is_empty());
+        assert_eq!(
+            find_exact(&db, "name", &Value::Text("Alice".to_string()))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_find_null() {
+        let db = with_missing_names();
+        let rows = find_null(&db, "name").unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_value(0), Some(&Value::Integer(2)));
+        assert!(matches!(
+            find_null(&db, "nmae"),
+            Err(Error::UnknownColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_find_where() {
+        let db = users();
+        let filter = Filter::in_list("id", vec![Value::Integer(1), Value::Integer(2)])
+            .and(!Filter::eq("name", Value::Text("Bob".to_string())));
+        let rows = find_where(&db, &filter).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_value(0), Some(&Value::Integer(1)));
+    }
+
+    fn scores() -> Database {
+        let score = |id, team, score| vec![Value::Integer(id), text(team), score];
+        table(
+            vec![
+                Column::new("id", DataType::Integer),
+                Column::new("team", DataType::Text),
+                Column::new("score", DataType::Float).nullable(),
+            ],
+            vec![
+                score(1, "red", Value::Float(70.0)),
+                score(2, "blue", Value::Float(90.0)),
+                score(3, "red", Value::Float(90.0)),
+                score(4, "blue", Value::Null),
+                score(5, "red", Value::Float(55.5)),
+            ],
+        )
+    }
+
+    fn first_column(result: &ResultSet) -> Vec<&Value> {
+        result.get_rows().iter().map(|row| &row.values[0]).collect()
+    }
+
+    #[test]
+    fn test_query_select_all() {
+        let db = scores();
+        let result = db.query().execute().unwrap();
+
+        assert_eq!(result.get_columns(), &vec!["id", "team", "score"]);
+        assert_eq!(result.len(), 5);
+    }
+
+    #[test]
+    fn test_query_projection_and_filters() {
+        let db = scores();
+        let result = db
+            .query()
+            .select(&["score", "id"])
+            .filter(Filter::eq("team", Value::Text("red".to_string())))
+            .filter(Filter::between(
+                "score",
+                Value::Float(60.0),
+                Value::Float(95.0),
+            ))
+            .execute()
+            .unwrap();
+
+        assert_eq!(result.get_columns(), &vec!["score", "id"]);
+        assert_eq!(result.column_index("id").unwrap(), 1);
+        assert_eq!(
+            first_column(&result),
+            vec![&Value::Float(70.0), &Value::Float(90.0)]
+        );
+    }
+
+    #[test]
+    fn test_query_order_by_several_columns() {
+        let db = scores();
+        let result = db
+            .query()
+            .select(&["id"])
+            .order_by("score", Order::Desc)
+            .order_by("id", Order::Asc)
+            .execute()
+            .unwrap();
+
+        let ids: Vec<Value> = [2, 3, 1, 5, 4].into_iter().map(Value::Integer).collect();
+        assert_eq!(first_column(&result), ids.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_query_limit_and_offset() {
+        let db = scores();
+        let result = db
+            .query()
+            .select(&["id"])
+            .order_by("id", Order::Asc)
+            .offset(1)
+            .limit(2)
+            .execute()
+            .unwrap();
+
+        assert_eq!(
+            first_column(&result),
+            vec![&Value::Integer(2), &Value::Integer(3)]
+        );
+    }
+
+    #[test]
+    fn test_query_unknown_columns() {
+        let db = scores();
+
+        assert!(matches!(
+            db.query().select(&["points"]).execute(),
+            Err(Error::UnknownColumn(column)) if column == "points"
+        ));
+        assert!(matches!(
+            db.query().order_by("rank", Order::Asc).execute(),
+            Err(Error::UnknownColumn(column)) if column == "rank"
+        ));
+    }
+
+    /// Apply a fixed mix of inserts, deletes and updates, checking after each step that
+    /// indexed lookups return the rows a scan of an unindexed copy returns.
+    #[test]
+    fn test_indexed_lookups_match_scans() {
+        let schema = Schema::from_columns(vec![
+            Column::new("key", DataType::Integer).nullable(),
+            Column::new("label", DataType::Text),
+        ]);
+        let mut indexed = Database::new(schema.clone());
+        let mut scanned = Database::new(schema);
+        indexed.create_index("key", IndexKind::BTree).unwrap();
+        indexed.create_index("label", IndexKind::Hash).unwrap();
+
+        let filters = [
+            Filter::eq("key", Value::Integer(3)),
+            Filter::lt("key", Value::Integer(4)),
+            Filter::ge("key", Value::Integer(7)),
+            Filter::between("key", Value::Integer(2), Value::Integer(5)),
+            Filter::between("key", Value::Integer(5), Value::Integer(2)),
+            Filter::in_list(
+                "label",
+                vec![Value::Text("b".to_string()), Value::Text("z".to_string())],
+            ),
+            Filter::gt("key", Value::Integer(1))
+                .and(Filter::eq("label", Value::Text("a".to_string()))),
+            Filter::eq("key", Value::Integer(1)).or(Filter::le("key", Value::Float(2.5))),
+            Filter::eq("label", Value::Text("c".to_string())).or(Filter::is_null("key")),
+        ];
+        let check = |indexed: &Database, scanned: &Database| {
+            for filter in &filters {
+                assert_eq!(
+                    find_where(indexed, filter).unwrap(),
+                    find_where(scanned, filter).unwrap(),
+                    "{:?}",
+                    filter
+                );
+            }
+            for key in [Value::Integer(3), Value::Integer(42), Value::Null] {
+                assert_eq!(
+                    find_exact(indexed, "key", &key).unwrap(),
+                    find_exact(scanned, "key", &key).unwrap()
+                );
+            }
+        };
+
+        for num in 0..60 {
+            let key = match num % 11 {
+                0 => Value::Null,
+                _ => Value::Integer(num * 7 % 10),
+            };
+            let label = Value::Text(["a", "b", "c"][num as usize % 3].to_string());
+            for db in [&mut indexed, &mut scanned] {
+                insert(db, Row::new(vec![key.clone(), label.clone()])).unwrap();
+            }
+        }
+        check(&indexed, &scanned);
+
+        for db in [&mut indexed, &mut scanned] {
+            delete(db, |row| row.get_value(0) == Some(&Value::Integer(4))).unwrap();
+        }
+        check(&indexed, &scanned);
+
+        for db in [&mut indexed, &mut scanned] {
+            update(
+                db,
+                |row| row.get_value(1) == Some(&Value::Text("b".to_string())),
+                &[("key", Value::Integer(3))],
+            )
+            .unwrap();
+        }
+        check(&indexed, &scanned);
+
+        assert_eq!(indexed.get_index_kind("key"), Some(IndexKind::BTree));
+        assert!(indexed.drop_index("key").unwrap());
+        assert!(!indexed.drop_index("key").unwrap());
+        assert_eq!(indexed.get_index_kind("key"), None);
+        check(&indexed, &scanned);
+    }
+
+    #[test]
+    fn test_query_uses_indexes() {
+        let mut db = users();
+        db.create_index("id", IndexKind::BTree).unwrap();
+
+        let result = db
+            .query()
+            .select(&["name"])
+            .filter(Filter::ge("id", Value::Integer(2)))
+            .filter(Filter::ne("name", Value::Text("Bob".to_string())))
+            .execute()
+            .unwrap();
+
+        assert_eq!(
+            result.get_rows(),
+            &vec![Row::new(vec![Value::Text("Alicia".to_string())])]
+        );
+        assert!(matches!(
+            db.create_index("email", IndexKind::Hash),
+            Err(Error::UnknownColumn(_))
+        ));
+    }
 }
@@ -0,0 +1,725 @@
+use super::error::{Error, Result};
+
+/// The largest bound accepted in a `{n,m}` repetition, which is compiled by copying.
+const MAX_REPEAT: u32 = 1000;
+
+/// The largest number of instructions a compiled expression may have, since nested
+/// repetitions such as `((a{1000}){1000}){1000}` multiply their copies.
+const MAX_PROGRAM: usize = 100_000;
+
+/// How deeply groups and repetitions may nest, since parsing and compiling recurse into
+/// them.
+const MAX_DEPTH: usize = 256;
+
+/// A compiled regular expression, matched in time linear in the length of the text.
+///
+/// Supported syntax: literals, `.` (any character but a newline), classes such as `[a-z_]`
+/// and `[^0-9]`, the escapes `\d`, `\w`, `\s` and their negations `\D`, `\W`, `\S`, the
+/// anchors `^` and `$`, groups `(...)` and `(?:...)`, alternation `|`, and the quantifiers
+/// `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`, optionally followed by a `?`. A leading `(?i)`
+/// makes the whole expression case-insensitive.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::Regex;
+///
+/// let regex = Regex::new(r"^(?:\w+\.)*\w+@example\.(com|org)$").unwrap();
+/// assert!(regex.is_match("jane.doe@example.org"));
+/// assert!(!regex.is_match("jane@example.net"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Regex {
+    pattern: String,
+    program: Vec<Inst>,
+    ignore_case: bool,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRegex` with the character position of the first syntax error
+    /// or of a group or repetition nested more than 256 deep, or position 0 if the
+    /// expression compiles to more than 100,000 instructions.
+    pub fn new(pattern: &str) -> Result<Regex> {
+        let (ignore_case, body) = match pattern.strip_prefix("(?i)") {
+            Some(body) => (true, body),
+            None => (false, pattern),
+        };
+        let offset = pattern.chars().count() - body.chars().count();
+        let mut parser = Parser {
+            pattern,
+            chars: body.chars().collect(),
+            position: 0,
+            offset,
+            depth: 0,
+            height: 0,
+        };
+        let node = parser.alternation()?;
+
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("unmatched `)`"));
+        }
+
+        let mut program = Vec::new();
+        if compile(&node, &mut program).is_none() {
+            return Err(Error::InvalidRegex {
+                pattern: pattern.to_string(),
+                position: 0,
+                message: format!("expression larger than {} instructions", MAX_PROGRAM),
+            });
+        }
+        program.push(Inst::Match);
+
+        Ok(Regex {
+            pattern: pattern.to_string(),
+            program,
+            ignore_case,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the expression matches anywhere in the text.
+    pub fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+
+        for position in 0..=chars.len() {
+            if self.add(&mut current, 0, position, chars.len()) {
+                return true;
+            }
+            let Some(&char) = chars.get(position) else {
+                break;
+            };
+
+            for index in 0..current.list.len() {
+                let pc = current.list[index];
+                let matches = match &self.program[pc] {
+                    Inst::Char(expected) => self.same(*expected, char),
+                    Inst::Any => char != '\n',
+                    Inst::Class(class) => self.in_class(class, char),
+                    _ => false,
+                };
+                if matches && self.add(&mut next, pc + 1, position + 1, chars.len()) {
+                    return true;
+                }
+            }
+
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+
+        false
+    }
+
+    /// Add a thread and follow its jumps and assertions; returns true if it reaches a match.
+    fn add(&self, threads: &mut Threads, pc: usize, position: usize, length: usize) -> bool {
+        let mut stack = vec![pc];
+
+        while let Some(pc) = stack.pop() {
+            if !threads.insert(pc) {
+                continue;
+            }
+            match self.program[pc] {
+                Inst::Match => return true,
+                Inst::Jump(target) => stack.push(target),
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::Start if position == 0 => stack.push(pc + 1),
+                Inst::End if position == length => stack.push(pc + 1),
+                _ => {}
+            }
+        }
+
+        false
+    }
+
+    fn same(&self, expected: char, char: char) -> bool {
+        expected == char || (self.ignore_case && fold_case(expected) == fold_case(char))
+    }
+
+    fn in_class(&self, class: &Class, char: char) -> bool {
+        let contains = |char: char| class.items.iter().any(|item| item.contains(char));
+        let found = contains(char)
+            || (self.ignore_case
+                && (char.to_lowercase().any(contains) || char.to_uppercase().any(contains)));
+        found != class.negated
+    }
+}
+
+fn fold_case(char: char) -> char {
+    char.to_lowercase().next().unwrap_or(char)
+}
+
+/// The set of live threads of the matcher, as a sparse set of program counters.
+struct Threads {
+    list: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Self {
+        Threads {
+            list: Vec::with_capacity(size),
+            seen: vec![false; size],
+        }
+    }
+
+    fn insert(&mut self, pc: usize) -> bool {
+        if self.seen[pc] {
+            return false;
+        }
+        self.seen[pc] = true;
+        self.list.push(pc);
+        true
+    }
+
+    fn clear(&mut self) {
+        for &pc in &self.list {
+            self.seen[pc] = false;
+        }
+        self.list.clear();
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Jump(usize),
+    /// Continue at both targets, preferring the first.
+    Split(usize, usize),
+    Match,
+}
+
+#[derive(Clone, Debug)]
+struct Class {
+    items: Vec<ClassItem>,
+    negated: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn contains(&self, char: char) -> bool {
+        match *self {
+            ClassItem::Range(low, high) => (low..=high).contains(&char),
+            ClassItem::Digit(negated) => char.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => (char.is_alphanumeric() || char == '_') != negated,
+            ClassItem::Space(negated) => char.is_whitespace() != negated,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    position: usize,
+    /// The number of characters of the pattern before `chars`, for error positions.
+    offset: usize,
+    /// The number of groups open at the current position.
+    depth: usize,
+    /// The height of the node parsed last, counting the nodes it is nested in.
+    height: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> Error {
+        Error::InvalidRegex {
+            pattern: self.pattern.to_string(),
+            position: self.offset + self.position,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn eat(&mut self, char: char) -> bool {
+        let found = self.peek() == Some(char);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Set the height of a node that holds the nodes parsed since `height` was taken.
+    fn nest(&mut self, height: usize) -> Result<()> {
+        self.height = height + 1;
+        match self.height > MAX_DEPTH {
+            true => Err(self.error("expression nested too deeply")),
+            false => Ok(()),
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Node> {
+        let mut branches = vec![self.concat()?];
+        let mut height = self.height;
+        while self.eat('|') {
+            branches.push(self.concat()?);
+            height = height.max(self.height);
+        }
+
+        match branches.len() {
+            1 => Ok(branches.pop().unwrap()),
+            _ => {
+                self.nest(height)?;
+                Ok(Node::Alternation(branches))
+            }
+        }
+    }
+
+    fn concat(&mut self) -> Result<Node> {
+        let mut nodes = Vec::new();
+        let mut height = 0;
+
+        while let Some(char) = self.peek()
+            && char != '|'
+            && char != ')'
+        {
+            let atom = self.atom()?;
+            nodes.push(self.quantifiers(atom)?);
+            height = height.max(self.height);
+        }
+
+        self.height = height;
+        match nodes.len() {
+            0 => Ok(Node::Empty),
+            1 => Ok(nodes.pop().unwrap()),
+            _ => {
+                self.nest(height)?;
+                Ok(Node::Concat(nodes))
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Result<Node> {
+        let start = self.position;
+        let char = self.peek().expect("concat checks for the end");
+        self.position += 1;
+        self.height = 1;
+
+        Ok(match char {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                if self.eat('?') && !self.eat(':') {
+                    return Err(self.error("unsupported group flag"));
+                }
+                if self.depth == MAX_DEPTH {
+                    self.position = start;
+                    return Err(self.error("expression nested too deeply"));
+                }
+                self.depth += 1;
+                let node = self.alternation()?;
+                self.depth -= 1;
+                if !self.eat(')') {
+                    self.position = start;
+                    return Err(self.error("unclosed group"));
+                }
+                node
+            }
+            '[' => Node::Class(self.class(start)?),
+            '\\' => match self.escape()? {
+                ClassItem::Range(char, _) => Node::Char(char),
+                item => Node::Class(Class {
+                    items: vec![item],
+                    negated: false,
+                }),
+            },
+            '*' | '+' | '?' => {
+                self.position = start;
+                return Err(self.error("nothing to repeat"));
+            }
+            '{' if self.repetition_follows() => {
+                self.position = start;
+                return Err(self.error("nothing to repeat"));
+            }
+            char => Node::Char(char),
+        })
+    }
+
+    /// Parse the escape after a `\`, as a single character range or a class shorthand.
+    fn escape(&mut self) -> Result<ClassItem> {
+        let Some(char) = self.peek() else {
+            return Err(self.error("trailing backslash"));
+        };
+        self.position += 1;
+
+        Ok(match char {
+            'd' | 'D' => ClassItem::Digit(char == 'D'),
+            'w' | 'W' => ClassItem::Word(char == 'W'),
+            's' | 'S' => ClassItem::Space(char == 'S'),
+            'n' => ClassItem::Range('\n', '\n'),
+            't' => ClassItem::Range('\t', '\t'),
+            'r' => ClassItem::Range('\r', '\r'),
+            char if char.is_alphanumeric() => {
+                self.position -= 1;
+                return Err(self.error("unknown escape"));
+            }
+            char => ClassItem::Range(char, char),
+        })
+    }
+
+    fn class(&mut self, start: usize) -> Result<Class> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+
+        loop {
+            let Some(char) = self.peek() else {
+                self.position = start;
+                return Err(self.error("unclosed character class"));
+            };
+            self.position += 1;
+
+            if char == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let item = match char {
+                '\\' => self.escape()?,
+                char => ClassItem::Range(char, char),
+            };
+            let ClassItem::Range(low, _) = item else {
+                items.push(item);
+                continue;
+            };
+
+            if self.peek() == Some('-')
+                && self.chars.get(self.position + 1).is_some_and(|&c| c != ']')
+            {
+                self.position += 1;
+                let high = match self.peek() {
+                    Some('\\') => {
+                        self.position += 1;
+                        match self.escape()? {
+                            ClassItem::Range(high, _) => high,
+                            _ => return Err(self.error("invalid class range")),
+                        }
+                    }
+                    Some(high) => {
+                        self.position += 1;
+                        high
+                    }
+                    None => unreachable!("checked above"),
+                };
+                if high < low {
+                    self.position -= 1;
+                    return Err(self.error("invalid class range"));
+                }
+                items.push(ClassItem::Range(low, high));
+            } else {
+                items.push(item);
+            }
+        }
+
+        Ok(Class { items, negated })
+    }
+
+    fn quantifiers(&mut self, mut node: Node) -> Result<Node> {
+        loop {
+            let start = self.position;
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') if self.repetition_follows() => {
+                    self.position += 1;
+                    let bounds = self.repetition()?;
+                    self.position -= 1;
+                    bounds
+                }
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            // A lazy quantifier matches the same texts.
+            self.eat('?');
+
+            if matches!(node, Node::Start | Node::End | Node::Empty) {
+                self.position = start;
+                return Err(self.error("nothing to repeat"));
+            }
+            if self.height == MAX_DEPTH {
+                self.position = start;
+                return Err(self.error("expression nested too deeply"));
+            }
+            self.height += 1;
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max,
+            };
+        }
+    }
+
+    /// Whether `{` starts a repetition like `{2}`, `{2,}` or `{2,5}` rather than a literal.
+    fn repetition_follows(&self) -> bool {
+        let rest: String = self.chars[self.position..]
+            .iter()
+            .take_while(|&&c| c != '}')
+            .collect();
+        let Some(body) = rest.strip_prefix('{') else {
+            return false;
+        };
+        let closed = self.chars.len() > self.position + rest.chars().count();
+        let (min, max) = body.split_once(',').unwrap_or((body, "0"));
+        closed
+            && !min.is_empty()
+            && min.chars().all(|c| c.is_ascii_digit())
+            && max.chars().all(|c| c.is_ascii_digit())
+    }
+
+    /// Parse the bounds of a repetition after its `{`, leaving the position after the `}`.
+    fn repetition(&mut self) -> Result<(u32, Option<u32>)> {
+        let start = self.position - 1;
+        let mut body = String::new();
+        while let Some(char) = self.peek() {
+            self.position += 1;
+            if char == '}' {
+                break;
+            }
+            body.push(char);
+        }
+
+        let parse = |text: &str| text.parse::<u32>().ok().filter(|&n| n <= MAX_REPEAT);
+        let bounds = match body.split_once(',') {
+            None => parse(&body).map(|n| (n, Some(n))),
+            Some((min, "")) => parse(min).map(|min| (min, None)),
+            Some((min, max)) => parse(min)
+                .zip(parse(max))
+                .map(|(min, max)| (min, Some(max))),
+        };
+
+        match bounds {
+            Some((min, Some(max))) if min > max => {
+                self.position = start;
+                Err(self.error("invalid repetition bounds"))
+            }
+            Some(bounds) => Ok(bounds),
+            None => {
+                self.position = start;
+                Err(self.error(&format!("repetition bound above {}", MAX_REPEAT)))
+            }
+        }
+    }
+}
+
+/// Append the instructions of a node to the program.
+///
+/// # Returns
+/// `None` once the program grows beyond `MAX_PROGRAM` instructions, checked before every
+/// copy of a repeated node so nested repetitions stop early.
+fn compile(node: &Node, program: &mut Vec<Inst>) -> Option<()> {
+    if program.len() > MAX_PROGRAM {
+        return None;
+    }
+    match node {
+        Node::Empty => {}
+        Node::Char(char) => program.push(Inst::Char(*char)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(class) => program.push(Inst::Class(class.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Concat(nodes) => {
+            for node in nodes {
+                compile(node, program)?;
+            }
+        }
+        Node::Alternation(branches) => {
+            let mut jumps = Vec::new();
+            for (index, branch) in branches.iter().enumerate() {
+                if index + 1 == branches.len() {
+                    compile(branch, program)?;
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, 0));
+                compile(branch, program)?;
+                jumps.push(program.len());
+                program.push(Inst::Jump(0));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, program)?;
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program)?;
+                    program.push(Inst::Jump(split));
+                    program[split] = Inst::Split(split + 1, program.len());
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(program.len() + 1, 0));
+                        compile(node, program)?;
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+    (program.len() <= MAX_PROGRAM).then_some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Regex::new(pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn test_literals_and_anchors() {
+        assert!(matches("bc", "abcd"));
+        assert!(!matches("^bc", "abcd"));
+        assert!(matches("^ab", "abcd"));
+        assert!(matches("cd$", "abcd"));
+        assert!(!matches("^abc$", "abcd"));
+        assert!(matches("", "anything"));
+        assert!(matches("^$", ""));
+        assert!(matches("a.c", "abc"));
+        assert!(!matches("a.c", "a\nc"));
+        assert!(matches(r"a\.c", "a.c"));
+        assert!(!matches(r"a\.c", "abc"));
+        assert!(matches("é", "café"));
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        assert!(matches("^ab*c$", "ac"));
+        assert!(matches("^ab*c$", "abbbc"));
+        assert!(!matches("^ab+c$", "ac"));
+        assert!(matches("^ab?c$", "abc"));
+        assert!(!matches("^ab?c$", "abbc"));
+        assert!(matches("^a{3}$", "aaa"));
+        assert!(!matches("^a{3}$", "aa"));
+        assert!(matches("^a{2,}$", "aaaaa"));
+        assert!(matches("^a{2,3}$", "aaa"));
+        assert!(!matches("^a{2,3}$", "aaaa"));
+        assert!(matches("^a+?b*?$", "aab"));
+        assert!(matches("^a{,2}$", "a{,2}"));
+        assert!(matches("^(a*)*$", "aaaa"));
+    }
+
+    #[test]
+    fn test_groups_and_alternation() {
+        assert!(matches("^(cat|dog)s?$", "dogs"));
+        assert!(!matches("^(cat|dog)s?$", "cow"));
+        assert!(matches("^(?:ab)+$", "ababab"));
+        assert!(!matches("^(?:ab)+$", "aba"));
+        assert!(matches("^(a|)$", ""));
+        assert!(matches("x|^y", "zzx"));
+    }
+
+    #[test]
+    fn test_classes() {
+        assert!(matches(r"^[a-c_]+$", "abc_cab"));
+        assert!(!matches(r"^[a-c]+$", "abd"));
+        assert!(matches(r"^[^0-9]+$", "abc"));
+        assert!(!matches(r"^[^0-9]+$", "a1"));
+        assert!(matches(r"^\d{3}-\d{4}$", "555-1234"));
+        assert!(matches(r"^\w+\s\W$", "héllo !"));
+        assert!(matches(r"^[\d.-]+$", "-1.5"));
+        assert!(matches(r"^[]a]+$", "]a"));
+        assert!(matches(r"^[a-]+$", "a-a"));
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert!(matches("(?i)^hello$", "HeLLo"));
+        assert!(matches("(?i)^[a-z]+$", "ECOLE"));
+        assert!(matches("(?i)straße", "STRAßE"));
+        assert!(!matches("^hello$", "Hello"));
+    }
+
+    #[test]
+    fn test_linear_time_on_pathological_pattern() {
+        let text = "a".repeat(5000);
+        assert!(!matches("^(a|a)*(a*)*b$", &text));
+    }
+
+    #[test]
+    fn test_errors() {
+        let position = |pattern: &str| match Regex::new(pattern) {
+            Err(Error::InvalidRegex { position, .. }) => position,
+            other => panic!("expected an error for {:?}, got {:?}", pattern, other),
+        };
+
+        assert_eq!(position("ab(c"), 2);
+        assert_eq!(position("abc)"), 3);
+        assert_eq!(position("*a"), 0);
+        assert_eq!(position("a|+"), 2);
+        assert_eq!(position("[abc"), 0);
+        assert_eq!(position("[z-a]"), 3);
+        assert_eq!(position("a{3,2}"), 1);
+        assert_eq!(position("a{5000}"), 1);
+        assert_eq!(position(r"a\q"), 2);
+        assert_eq!(position("a\\"), 2);
+        assert_eq!(position("(?i)(?x)"), 6);
+        assert_eq!(position("^*"), 1);
+        assert_eq!(position("((a{1000}){1000}){1000}"), 0);
+        assert_eq!(position("(?i)(a{1000}|b{1000}){1000}"), 0);
+        assert!(Regex::new("(a{100}){100}").is_ok());
+
+        assert_eq!(position(&"(".repeat(200_000)), MAX_DEPTH);
+        assert_eq!(position(&format!("(?i){}", "(".repeat(300))), 4 + MAX_DEPTH);
+        assert_eq!(position(&format!("a{}", "*".repeat(300))), MAX_DEPTH);
+        // Each group adds an alternation and a concatenation.
+        let nested = |depth| format!("{}{}", "(a|a".repeat(depth), ")".repeat(depth));
+        assert!(position(&nested(150)) > 0);
+        assert!(Regex::new(&nested(100)).unwrap().is_match("aaa"));
+        let nested = format!("{}a{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert!(Regex::new(&nested).unwrap().is_match("a"));
+        assert!(Regex::new(&"a*b+".repeat(1000)).is_ok());
+    }
+}
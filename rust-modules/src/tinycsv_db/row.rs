@@ -1,5 +1,6 @@
 use super::types::Value;
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct Row {
     pub(super) values: Vec<Value>,
 }
@@ -1,11 +1,246 @@
-use super::types::DataType;
+use std::fmt;
+use std::sync::Arc;
 
+use super::error::{Error, Result};
+use super::types::{Coercion, DataType, Value};
+
+/// A named CHECK constraint: a predicate every non-null value of a column must satisfy.
+#[derive(Clone)]
+pub struct Check {
+    pub(crate) name: String,
+    predicate: Arc<dyn Fn(&Value) -> bool + Send + Sync>,
+}
+
+impl Check {
+    pub fn new(name: &str, predicate: impl Fn(&Value) -> bool + Send + Sync + 'static) -> Self {
+        Check {
+            name: name.to_string(),
+            predicate: Arc::new(predicate),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn accepts(&self, value: &Value) -> bool {
+        (self.predicate)(value)
+    }
+}
+
+impl fmt::Debug for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Check").field(&self.name).finish()
+    }
+}
+
+/// A named, typed column of a schema together with its constraints.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub(crate) name: String,
+    pub(crate) data_type: DataType,
+    pub(crate) nullable: bool,
+    pub(crate) primary_key: bool,
+    pub(crate) unique: bool,
+    pub(crate) default: Option<Value>,
+    pub(crate) checks: Vec<Check>,
+}
+
+/// Columns are equal when their names, types and constraints match. CHECK constraints are
+/// closures, so they are left out of the comparison.
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.data_type == other.data_type
+            && self.nullable == other.nullable
+            && self.primary_key == other.primary_key
+            && self.unique == other.unique
+            && self.default == other.default
+    }
+}
+
+impl Column {
+    /// Create a column that does not accept `Value::Null`.
+    pub fn new(name: &str, data_type: DataType) -> Self {
+        Column {
+            name: name.to_string(),
+            data_type,
+            nullable: false,
+            primary_key: false,
+            unique: false,
+            default: None,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Allow the column to hold `Value::Null`.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// Forbid `Value::Null` in the column (the NOT NULL constraint, which is the default).
+    pub fn not_null(mut self) -> Self {
+        self.nullable = false;
+        self
+    }
+
+    /// Make the column the primary key, which implies UNIQUE and NOT NULL.
+    pub fn primary_key(mut self) -> Self {
+        self.primary_key = true;
+        self.unique = true;
+        self.nullable = false;
+        self
+    }
+
+    /// Forbid two rows from having the same non-null value in the column.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// Store the given value instead of `Value::Null` when an inserted row leaves the column
+    /// empty. Updates to `Value::Null` are not replaced.
+    pub fn default(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Require every non-null value of the column to satisfy the check.
+    pub fn check(mut self, check: Check) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    pub fn is_primary_key(&self) -> bool {
+        self.primary_key
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn get_default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    /// Check that a value can be stored in the column, converting it if allowed.
+    ///
+    /// Defaults are not applied here, as they only fill in values of inserted rows: an
+    /// update storing `Value::Null` must respect the nullability of the column. Uniqueness is
+    /// not checked either, as it depends on the other rows of the database.
+    pub(crate) fn validate(&self, value: Value, coercion: Coercion) -> Result<Value> {
+        let Some(found) = value.data_type() else {
+            return match self.nullable {
+                true => Ok(value),
+                false => Err(Error::NullViolation(self.name.clone())),
+            };
+        };
+
+        let value = value
+            .coerce(self.data_type, coercion)
+            .ok_or_else(|| Error::TypeMismatch {
+                column: self.name.clone(),
+                expected: self.data_type,
+                found,
+            })?;
+
+        if let Some(check) = self.checks.iter().find(|check| !check.accepts(&value)) {
+            return Err(Error::CheckViolation {
+                column: self.name.clone(),
+                check: check.name.clone(),
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct Schema {
-    pub(crate) columns: Vec<(String, DataType)>,
+    pub(crate) columns: Vec<Column>,
 }
 
 impl Schema {
+    /// Create a schema of columns that do not accept `Value::Null`.
     pub fn new(columns: Vec<(String, DataType)>) -> Self {
+        Schema {
+            columns: columns
+                .into_iter()
+                .map(|(name, data_type)| Column::new(&name, data_type))
+                .collect(),
+        }
+    }
+
+    /// Create a schema from column definitions.
+    pub fn from_columns(columns: Vec<Column>) -> Self {
         Schema { columns }
     }
+
+    pub fn get_columns(&self) -> &Vec<Column> {
+        &self.columns
+    }
+
+    /// Get the primary key column, if the schema has one.
+    pub fn primary_key(&self) -> Option<&Column> {
+        self.columns.iter().find(|column| column.primary_key)
+    }
+
+    /// Find the index of a column by its name.
+    ///
+    /// # Arguments
+    /// * `column_name` - The name of the column to look up
+    ///
+    /// # Returns
+    /// The position of the column in the schema, or `Error::UnknownColumn` if there is no such column.
+    pub fn column_index(&self, column_name: &str) -> Result<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.name == column_name)
+            .ok_or_else(|| Error::UnknownColumn(column_name.to_string()))
+    }
+
+    /// Check that values form a valid row of the schema, converting them if allowed.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the row, one per column
+    /// * `coercion` - The coercion mode deciding which type conversions are allowed
+    ///
+    /// # Returns
+    /// The values converted to the data types of their columns, with defaults filled in.
+    ///
+    /// # Errors
+    /// Returns `Error::ArityMismatch` if there is not one value per column,
+    /// `Error::NullViolation` if `Value::Null` is given for a column that is not nullable,
+    /// `Error::TypeMismatch` if a value does not match (or cannot be converted to) the data
+    /// type of its column and `Error::CheckViolation` if a value fails a CHECK constraint.
+    pub fn validate(&self, values: Vec<Value>, coercion: Coercion) -> Result<Vec<Value>> {
+        if values.len() != self.columns.len() {
+            return Err(Error::ArityMismatch {
+                expected: self.columns.len(),
+                found: values.len(),
+            });
+        }
+
+        values
+            .into_iter()
+            .zip(&self.columns)
+            .map(|(value, column)| match (value, &column.default) {
+                (Value::Null, Some(default)) => column.validate(default.clone(), coercion),
+                (value, _) => column.validate(value, coercion),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,210 @@
+use crate::tinycsv_db::error::{Error, Result};
+
+/// A token of a SQL statement.
+#[derive(Clone, PartialEq, Debug)]
+pub(super) enum Token {
+    /// An identifier or keyword; `quoted` is true for `"double quoted"` identifiers,
+    /// which are never keywords.
+    Ident {
+        name: String,
+        quoted: bool,
+    },
+    Number(String),
+    Str(String),
+    Symbol(&'static str),
+    End,
+}
+
+/// A token together with its 1-based line and column in the source.
+#[derive(Clone, Debug)]
+pub(super) struct Spanned {
+    pub(super) token: Token,
+    pub(super) line: usize,
+    pub(super) column: usize,
+}
+
+const SYMBOLS: [&str; 13] = [
+    "<=", ">=", "<>", "!=", "=", "<", ">", ",", "(", ")", "*", ";", "-",
+];
+
+/// Split a SQL statement into tokens, ending with `Token::End`.
+///
+/// # Errors
+/// Returns `Error::Sql` for unterminated strings or quoted identifiers and unexpected characters.
+pub(super) fn tokenize(sql: &str) -> Result<Vec<Spanned>> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    loop {
+        while let Some(char) = chars.next_if(|c| c.is_whitespace()) {
+            if char == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        let (start_line, start_column) = (line, column);
+        let error = |message: String| Error::Sql {
+            line: start_line,
+            column: start_column,
+            message,
+        };
+        let Some(&char) = chars.peek() else {
+            tokens.push(Spanned {
+                token: Token::End,
+                line,
+                column,
+            });
+            return Ok(tokens);
+        };
+
+        let mut text = String::new();
+        let token = if char.is_alphabetic() || char == '_' {
+            while let Some(char) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                text.push(char);
+            }
+            Token::Ident {
+                name: text.clone(),
+                quoted: false,
+            }
+        } else if char.is_ascii_digit() || char == '.' {
+            while let Some(char) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+                text.push(char);
+                if matches!(char, 'e' | 'E')
+                    && let Some(sign) = chars.next_if(|c| matches!(c, '+' | '-'))
+                {
+                    text.push(sign);
+                }
+            }
+            Token::Number(text.clone())
+        } else if char == '\'' || char == '"' {
+            chars.next();
+            text.push(char);
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some(c) if c == char && chars.next_if_eq(&char).is_some() => {
+                        text.push_str(&format!("{}{}", c, c));
+                        value.push(c);
+                    }
+                    Some(c) if c == char => break,
+                    Some(c) => {
+                        text.push(c);
+                        value.push(c);
+                    }
+                    None => return Err(error("unterminated quoted text".to_string())),
+                }
+            }
+            text.push(char);
+            match char {
+                '\'' => Token::Str(value),
+                _ => Token::Ident {
+                    name: value,
+                    quoted: true,
+                },
+            }
+        } else {
+            let rest: String = chars.clone().take(2).collect();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(*symbol))
+                .ok_or_else(|| error(format!("unexpected character `{}`", char)))?;
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            text.push_str(symbol);
+            Token::Symbol(symbol)
+        };
+
+        for char in text.chars() {
+            if char == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        tokens.push(Spanned {
+            token,
+            line: start_line,
+            column: start_column,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(sql: &str) -> Vec<Token> {
+        tokenize(sql)
+            .unwrap()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect()
+    }
+
+    fn ident(name: &str) -> Token {
+        Token::Ident {
+            name: name.to_string(),
+            quoted: false,
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokens("SELECT \"full name\" FROM t WHERE score >= 1.5e-3 AND name <> 'O''Neil';"),
+            vec![
+                ident("SELECT"),
+                Token::Ident {
+                    name: "full name".to_string(),
+                    quoted: true
+                },
+                ident("FROM"),
+                ident("t"),
+                ident("WHERE"),
+                ident("score"),
+                Token::Symbol(">="),
+                Token::Number("1.5e-3".to_string()),
+                ident("AND"),
+                ident("name"),
+                Token::Symbol("<>"),
+                Token::Str("O'Neil".to_string()),
+                Token::Symbol(";"),
+                Token::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_positions() {
+        let spanned = tokenize("SELECT *\n  FROM t").unwrap();
+
+        assert_eq!((spanned[2].line, spanned[2].column), (2, 3));
+        assert_eq!((spanned[3].line, spanned[3].column), (2, 8));
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert!(matches!(
+            tokenize("SELECT 'open"),
+            Err(Error::Sql {
+                line: 1,
+                column: 8,
+                ..
+            })
+        ));
+        assert!(matches!(
+            tokenize("SELECT #"),
+            Err(Error::Sql {
+                line: 1,
+                column: 8,
+                ..
+            })
+        ));
+    }
+}
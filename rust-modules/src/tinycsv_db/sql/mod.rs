@@ -0,0 +1,461 @@
+mod lexer;
+mod parser;
+
+use super::database::Database;
+use super::error::{Error, Result};
+use super::operations::{delete, delete_where, insert_all, update, update_where};
+use super::query::ResultSet;
+use super::row::Row;
+use super::schema::Schema;
+use super::types::{DataType, Value};
+use parser::{Name, Select};
+pub(crate) use parser::{Statement, parse};
+
+/// The outcome of a SQL statement.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SqlResult {
+    /// The rows produced by a `SELECT`.
+    Rows(ResultSet),
+    /// The number of rows inserted, updated or deleted.
+    Affected(usize),
+}
+
+/// Run a single SQL statement against the database.
+///
+/// Supported statements are `SELECT cols FROM t [WHERE ...] [GROUP BY cols] [HAVING ...]
+/// [ORDER BY col [ASC|DESC], ...] [LIMIT n] [OFFSET n]`,
+/// `INSERT INTO t [(cols)] VALUES (...), ...`, `UPDATE t SET col = value, ... [WHERE ...]`
+/// and `DELETE FROM t [WHERE ...]`. `WHERE` accepts comparisons, `[NOT] BETWEEN`, `[NOT] IN`,
+/// `IS [NOT] NULL`, `AND`, `OR`, `NOT` and parentheses. The table name is ignored here; use
+/// `Catalog::execute` to run statements against named tables.
+///
+/// `SELECT`, `HAVING` and `ORDER BY` accept the aggregates `COUNT(*)`, `COUNT([DISTINCT] col)`,
+/// `SUM`, `AVG`, `MIN` and `MAX`, named as in `Aggregate` or with `AS alias`, and grouped like
+/// `Database::group_by`.
+///
+/// Columns left out of an `INSERT` column list are `NULL`, so their defaults apply. A multi-row
+/// `INSERT` inserts no row if any row is rejected.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let mut db = Database::from_csv("id,name,score\n1,Alice,95.5\n2,Bob,80\n").unwrap();
+/// execute(&mut db, "INSERT INTO t VALUES (3, 'Carol', 88)").unwrap();
+///
+/// let result = execute(&mut db, "SELECT name FROM t WHERE score > 85 ORDER BY name").unwrap();
+/// let SqlResult::Rows(rows) = result else { panic!() };
+/// assert_eq!(rows.len(), 2);
+/// ```
+///
+/// # Errors
+/// Returns `Error::Sql` with the line and column of a syntax error, of an unknown column or of
+/// a column given a value of the wrong type, and the errors of `insert`, `update_where`,
+/// `delete_where` and `Query::execute` otherwise.
+pub fn execute(db: &mut Database, sql: &str) -> Result<SqlResult> {
+    execute_statement(db, parse(sql)?)
+}
+
+/// Run a parsed statement against the database, ignoring its table name.
+///
+/// Unknown columns and type mismatches are reported as `Error::Sql` at the first place the
+/// statement names the column, or at its table name if it does not name the column.
+pub(crate) fn execute_statement(db: &mut Database, statement: Statement) -> Result<SqlResult> {
+    let names = statement.names().to_vec();
+    let table = statement.table().to_string();
+
+    run(db, statement).map_err(|error| {
+        let column = match &error {
+            Error::UnknownColumn(column) | Error::TypeMismatch { column, .. } => column,
+            _ => return error,
+        };
+        let name = names
+            .iter()
+            .find(|name| name.name == *column)
+            .or_else(|| names.iter().find(|name| name.name == table));
+        match name {
+            Some(Name { line, column, .. }) => Error::Sql {
+                line: *line,
+                column: *column,
+                message: error.to_string(),
+            },
+            None => error,
+        }
+    })
+}
+
+fn run(db: &mut Database, statement: Statement) -> Result<SqlResult> {
+    match statement {
+        Statement::Select(select) => select_rows(db, select).map(SqlResult::Rows),
+        Statement::Insert { columns, rows, .. } => {
+            let rows = rows
+                .into_iter()
+                .map(|values| insert_row(&db.schema, columns.as_deref(), values))
+                .collect::<Result<Vec<Row>>>()?;
+            insert_all(db, rows).map(SqlResult::Affected)
+        }
+        Statement::Update {
+            assignments,
+            filter,
+            ..
+        } => {
+            let assignments = assignments
+                .into_iter()
+                .map(|(column, value)| {
+                    let data_type = db.schema.columns[db.schema.column_index(&column)?].data_type;
+                    Ok((column, literal(value, data_type)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let assignments: Vec<(&str, Value)> = assignments
+                .iter()
+                .map(|(column, value)| (column.as_str(), value.clone()))
+                .collect();
+            match filter {
+                Some(filter) => update_where(db, &filter, &assignments),
+                None => update(db, |_| true, &assignments),
+            }
+            .map(SqlResult::Affected)
+        }
+        Statement::Delete { filter, .. } => match filter {
+            Some(filter) => delete_where(db, &filter),
+            None => delete(db, |_| true),
+        }
+        .map(SqlResult::Affected),
+    }
+}
+
+fn select_rows(db: &Database, mut select: Select) -> Result<ResultSet> {
+    if !select.group_by.is_empty() || !select.aggregates.is_empty() || select.having.is_some() {
+        let group_by = std::mem::take(&mut select.group_by);
+        let group_by: Vec<&str> = group_by.iter().map(String::as_str).collect();
+        let mut grouping = db.group_by(&group_by);
+        for aggregate in std::mem::take(&mut select.aggregates) {
+            grouping = grouping.aggregate(aggregate);
+        }
+        if let Some(filter) = select.filter.take() {
+            grouping = grouping.filter(filter);
+        }
+        if let Some(having) = select.having.take() {
+            grouping = grouping.having(having);
+        }
+        return select_rows(&grouping.execute()?, select);
+    }
+
+    let mut query = db.query();
+
+    if let Some(columns) = &select.columns {
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        query = query.select(&columns);
+    }
+    if let Some(filter) = select.filter {
+        query = query.filter(filter);
+    }
+    for (column, order) in &select.order_by {
+        query = query.order_by(column, *order);
+    }
+    if let Some(limit) = select.limit {
+        query = query.limit(limit);
+    }
+
+    query.offset(select.offset).execute()
+}
+
+/// Build a full row from the values of an `INSERT`, in schema order.
+fn insert_row(schema: &Schema, columns: Option<&[String]>, values: Vec<Value>) -> Result<Row> {
+    let Some(columns) = columns else {
+        // A wrong number of values is left for `Schema::validate` to report.
+        let values = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| match schema.columns.get(index) {
+                Some(column) => literal(value, column.data_type),
+                None => value,
+            })
+            .collect();
+        return Ok(Row { values });
+    };
+
+    if columns.len() != values.len() {
+        return Err(Error::ArityMismatch {
+            expected: columns.len(),
+            found: values.len(),
+        });
+    }
+
+    let mut row = vec![Value::Null; schema.columns.len()];
+    for (column, value) in columns.iter().zip(values) {
+        let index = schema.column_index(column)?;
+        row[index] = literal(value, schema.columns[index].data_type);
+    }
+    Ok(Row { values: row })
+}
+
+/// Widen integer literals written for float columns, so `score = 80` works without coercion.
+fn literal(value: Value, data_type: DataType) -> Value {
+    match (value, data_type) {
+        (Value::Integer(integer), DataType::Float) => Value::Float(integer as f64),
+        (value, _) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::error::Error;
+    use crate::tinycsv_db::fixtures::{text, users};
+
+    fn names(result: SqlResult) -> Vec<Value> {
+        match result {
+            SqlResult::Rows(result) => result
+                .get_rows()
+                .iter()
+                .map(|row| row.get_value(0).unwrap().clone())
+                .collect(),
+            SqlResult::Affected(_) => panic!("expected rows"),
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let mut db = users();
+
+        assert_eq!(
+            names(
+                execute(
+                    &mut db,
+                    "SELECT name FROM users WHERE score >= 80 ORDER BY score DESC"
+                )
+                .unwrap()
+            ),
+            vec![text("Alice"), text("Bob")]
+        );
+        assert_eq!(
+            names(
+                execute(
+                    &mut db,
+                    "SELECT name FROM users WHERE score IS NULL OR NOT active = TRUE"
+                )
+                .unwrap()
+            ),
+            vec![text("Bob"), text("Carol")]
+        );
+        assert_eq!(
+            names(execute(&mut db, "SELECT name FROM users WHERE name NOT LIKE '%o%'").unwrap()),
+            vec![text("Alice")]
+        );
+        assert_eq!(
+            names(
+                execute(
+                    &mut db,
+                    "SELECT name FROM users ORDER BY name DESC LIMIT 1 OFFSET 1"
+                )
+                .unwrap()
+            ),
+            vec![text("Bob")]
+        );
+
+        let terms: Vec<String> = (0..300).map(|id| format!("id = {}", id)).collect();
+        let sql = format!("SELECT name FROM users WHERE {}", terms.join(" OR "));
+        assert_eq!(
+            names(execute(&mut db, &sql).unwrap()),
+            vec![text("Alice"), text("Bob"), text("Carol")]
+        );
+
+        let SqlResult::Rows(result) = execute(&mut db, "SELECT * FROM users").unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(result.get_columns().len(), 4);
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn test_insert_with_columns() {
+        let mut db = users();
+
+        assert!(matches!(
+            execute(&mut db, "INSERT INTO users (name, id) VALUES ('Dave', 4)"),
+            Ok(SqlResult::Affected(1))
+        ));
+        assert_eq!(
+            db.get_rows()[3].get_values(),
+            &vec![
+                Value::Integer(4),
+                text("Dave"),
+                Value::Null,
+                Value::Boolean(true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_is_atomic() {
+        let mut db = users();
+
+        assert!(matches!(
+            execute(
+                &mut db,
+                "INSERT INTO users VALUES (4, 'Dave', 1, TRUE), (1, 'Eve', 2, TRUE)"
+            ),
+            Err(Error::UniqueViolation { .. })
+        ));
+        assert!(matches!(
+            execute(&mut db, "INSERT INTO users VALUES (5, 'Frank', 1.0)"),
+            Err(Error::ArityMismatch {
+                expected: 4,
+                found: 3
+            })
+        ));
+        assert!(matches!(
+            execute(&mut db, "INSERT INTO users (id, nickname) VALUES (5, 'F')"),
+            Err(Error::Sql {
+                line: 1,
+                column: 24,
+                ..
+            })
+        ));
+        assert!(matches!(
+            execute(
+                &mut db,
+                "INSERT INTO users VALUES ('5', 'Frank', 1.0, TRUE)"
+            ),
+            Err(Error::Sql {
+                line: 1,
+                column: 13,
+                ..
+            })
+        ));
+        assert_eq!(db.get_rows().len(), 3);
+    }
+
+    #[test]
+    fn test_update_and_delete() {
+        let mut db = users();
+
+        assert!(matches!(
+            execute(
+                &mut db,
+                "UPDATE users SET score = 70, active = FALSE WHERE score BETWEEN 80 AND 90"
+            ),
+            Ok(SqlResult::Affected(1))
+        ));
+        assert_eq!(db.get_rows()[1].get_value(2), Some(&Value::Float(70.0)));
+        assert!(matches!(
+            execute(&mut db, "UPDATE users SET name = 1 WHERE id = 1"),
+            Err(Error::Sql {
+                line: 1,
+                column: 18,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            execute(&mut db, "DELETE FROM users WHERE id NOT IN (1, 2)"),
+            Ok(SqlResult::Affected(1))
+        ));
+        assert!(matches!(
+            execute(&mut db, "DELETE FROM users"),
+            Ok(SqlResult::Affected(2))
+        ));
+        assert!(db.get_rows().is_empty());
+    }
+
+    #[test]
+    fn test_where_errors() {
+        let mut db = users();
+
+        assert!(matches!(
+            execute(&mut db, "SELECT * FROM users WHERE nickname = 'x'"),
+            Err(Error::Sql {
+                line: 1,
+                column: 27,
+                message,
+            }) if message == "unknown column `nickname`"
+        ));
+        assert!(matches!(
+            execute(&mut db, "SELECT * FROM users WHERE name > 3"),
+            Err(Error::Sql {
+                line: 1,
+                column: 27,
+                ..
+            })
+        ));
+        assert!(matches!(
+            execute(&mut db, "DELETE FROM users WHERE id = "),
+            Err(Error::Sql {
+                line: 1,
+                column: 30,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_group_by() {
+        let mut db = users();
+        execute(&mut db, "INSERT INTO users VALUES (4, 'Alice', 60, FALSE)").unwrap();
+
+        let SqlResult::Rows(result) = execute(
+            &mut db,
+            "SELECT name, COUNT(*) AS n, avg(score) FROM users WHERE id > 1 \
+             GROUP BY name HAVING COUNT(*) >= 1 AND MAX(id) < 4 ORDER BY n DESC, name",
+        )
+        .unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(result.get_columns(), &vec!["name", "n", "avg(score)"]);
+        assert_eq!(
+            result
+                .get_rows()
+                .iter()
+                .map(Row::get_values)
+                .collect::<Vec<_>>(),
+            vec![
+                &vec![text("Bob"), Value::Integer(1), Value::Float(80.0)],
+                &vec![text("Carol"), Value::Integer(1), Value::Null],
+            ]
+        );
+
+        assert_eq!(
+            names(execute(&mut db, "SELECT COUNT(DISTINCT name), SUM(id) FROM users").unwrap()),
+            vec![Value::Integer(3)]
+        );
+    }
+
+    #[test]
+    fn test_group_by_errors() {
+        let mut db = users();
+
+        assert!(matches!(
+            execute(&mut db, "SELECT name, COUNT(*) FROM users"),
+            Err(Error::Sql {
+                line: 1,
+                column: 8,
+                ..
+            })
+        ));
+        assert!(matches!(
+            execute(&mut db, "SELECT * FROM users GROUP BY name"),
+            Err(Error::Sql {
+                line: 1,
+                column: 8,
+                ..
+            })
+        ));
+        assert!(matches!(
+            execute(&mut db, "SELECT id FROM users WHERE COUNT(*) > 1"),
+            Err(Error::Sql {
+                line: 1,
+                column: 28,
+                ..
+            })
+        ));
+        assert!(matches!(
+            execute(&mut db, "SELECT SUM(name) FROM users"),
+            Err(Error::Sql {
+                line: 1,
+                column: 12,
+                ..
+            })
+        ));
+    }
+}
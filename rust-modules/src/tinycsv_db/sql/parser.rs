@@ -0,0 +1,705 @@
+use super::lexer::{Spanned, Token, tokenize};
+use crate::tinycsv_db::aggregate::{Aggregate, AggregateFn};
+use crate::tinycsv_db::error::{Error, Result};
+use crate::tinycsv_db::filter::{CompareOp, Filter};
+use crate::tinycsv_db::query::Order;
+use crate::tinycsv_db::types::Value;
+
+/// How deeply conditions may nest through parentheses and NOT, so a hostile statement cannot
+/// overflow the stack of the recursive parser. Chains of AND and OR are parsed in a loop
+/// and do not count.
+const MAX_DEPTH: usize = 256;
+
+const RESERVED: [&str; 27] = [
+    "SELECT", "FROM", "WHERE", "GROUP", "HAVING", "ORDER", "BY", "LIMIT", "OFFSET", "INSERT",
+    "INTO", "VALUES", "UPDATE", "SET", "DELETE", "AND", "OR", "NOT", "IN", "BETWEEN", "LIKE", "IS",
+    "NULL", "TRUE", "FALSE", "AS", "DISTINCT",
+];
+
+/// An identifier as written in a statement, with the 1-based line and column it starts at.
+#[derive(Clone, Debug)]
+pub(crate) struct Name {
+    pub(crate) name: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// A parsed `SELECT` statement.
+///
+/// Aggregate calls anywhere in the statement are collected in `aggregates` and replaced by
+/// the name of their result column, so `HAVING` and `ORDER BY` refer to them like columns.
+#[derive(Debug)]
+pub(crate) struct Select {
+    pub(crate) table: String,
+    pub(crate) columns: Option<Vec<String>>,
+    pub(crate) filter: Option<Filter>,
+    pub(crate) group_by: Vec<String>,
+    pub(crate) aggregates: Vec<Aggregate>,
+    pub(crate) having: Option<Filter>,
+    pub(crate) order_by: Vec<(String, Order)>,
+    pub(crate) limit: Option<usize>,
+    pub(crate) offset: usize,
+    pub(crate) names: Vec<Name>,
+}
+
+/// A parsed SQL statement.
+#[derive(Debug)]
+pub(crate) enum Statement {
+    Select(Select),
+    Insert {
+        table: String,
+        columns: Option<Vec<String>>,
+        rows: Vec<Vec<Value>>,
+        names: Vec<Name>,
+    },
+    Update {
+        table: String,
+        assignments: Vec<(String, Value)>,
+        filter: Option<Filter>,
+        names: Vec<Name>,
+    },
+    Delete {
+        table: String,
+        filter: Option<Filter>,
+        names: Vec<Name>,
+    },
+}
+
+impl Statement {
+    /// The name of the table the statement reads or changes.
+    pub(crate) fn table(&self) -> &str {
+        match self {
+            Statement::Select(Select { table, .. })
+            | Statement::Insert { table, .. }
+            | Statement::Update { table, .. }
+            | Statement::Delete { table, .. } => table,
+        }
+    }
+
+    /// Every identifier of the statement in the order it was written.
+    pub(crate) fn names(&self) -> &[Name] {
+        match self {
+            Statement::Select(Select { names, .. })
+            | Statement::Insert { names, .. }
+            | Statement::Update { names, .. }
+            | Statement::Delete { names, .. } => names,
+        }
+    }
+}
+
+/// Parse a single SQL statement, optionally ended by `;`.
+///
+/// # Errors
+/// Returns `Error::Sql` with the position of the first token that does not fit the grammar.
+pub(crate) fn parse(sql: &str) -> Result<Statement> {
+    let mut parser = Parser {
+        tokens: tokenize(sql)?,
+        position: 0,
+        aggregates: Vec::new(),
+        allow_aggregates: false,
+        depth: 0,
+        names: Vec::new(),
+    };
+    let statement = parser.statement()?;
+
+    parser.symbol(";");
+    if parser.peek() != &Token::End {
+        return Err(parser.error("expected end of statement"));
+    }
+
+    Ok(statement)
+}
+
+/// Combine a chain of terms in order into a balanced tree, so long chains stay shallow for
+/// the code that walks filters recursively.
+fn balance(mut terms: Vec<Filter>, combine: &impl Fn(Filter, Filter) -> Filter) -> Filter {
+    if terms.len() == 1 {
+        return terms.pop().unwrap();
+    }
+    let right = terms.split_off(terms.len().div_ceil(2));
+    combine(balance(terms, combine), balance(right, combine))
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    aggregates: Vec<Aggregate>,
+    allow_aggregates: bool,
+    depth: usize,
+    names: Vec<Name>,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].token
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].token.clone();
+        if token != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn location(&self) -> (usize, usize) {
+        let spanned = &self.tokens[self.position];
+        (spanned.line, spanned.column)
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let Spanned {
+            token,
+            line,
+            column,
+        } = &self.tokens[self.position];
+        let found = match token {
+            Token::Ident { name, .. } => format!("`{}`", name),
+            Token::Number(text) => format!("`{}`", text),
+            Token::Str(text) => format!("'{}'", text),
+            Token::Symbol(symbol) => format!("`{}`", symbol),
+            Token::End => "end of statement".to_string(),
+        };
+
+        Error::Sql {
+            line: *line,
+            column: *column,
+            message: format!("{}, found {}", message, found),
+        }
+    }
+
+    /// Consume the keyword if it is next.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.peek(),
+            Token::Ident { name, quoted: false } if name.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        match self.keyword(keyword) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected {}", keyword))),
+        }
+    }
+
+    /// Consume the symbol if it is next.
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Token::Symbol(found) if *found == symbol);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        match self.symbol(symbol) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{}`", symbol))),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.peek() {
+            Token::Ident { name, quoted }
+                if *quoted || !RESERVED.iter().any(|word| name.eq_ignore_ascii_case(word)) =>
+            {
+                let name = name.clone();
+                let (line, column) = self.location();
+                self.names.push(Name {
+                    name: name.clone(),
+                    line,
+                    column,
+                });
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    /// Parse a comma-separated list of at least one item.
+    fn list<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.symbol(",") {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn statement(&mut self) -> Result<Statement> {
+        if self.keyword("SELECT") {
+            self.select().map(Statement::Select)
+        } else if self.keyword("INSERT") {
+            self.insert()
+        } else if self.keyword("UPDATE") {
+            self.update()
+        } else if self.keyword("DELETE") {
+            self.delete()
+        } else {
+            Err(self.error("expected SELECT, INSERT, UPDATE or DELETE"))
+        }
+    }
+
+    fn select(&mut self) -> Result<Select> {
+        let star = self.location();
+        let mut plain = Vec::new();
+        self.allow_aggregates = true;
+        let columns = match self.symbol("*") {
+            true => None,
+            false => Some(self.list(|parser| {
+                let location = parser.location();
+                if let Some(mut aggregate) = parser.aggregate_call()? {
+                    if parser.keyword("AS") {
+                        aggregate = aggregate.alias(&parser.identifier()?);
+                    }
+                    return Ok(parser.register(aggregate));
+                }
+                let column = parser.identifier()?;
+                plain.push((column.clone(), location));
+                Ok(column)
+            })?),
+        };
+        self.expect_keyword("FROM")?;
+        let table = self.identifier()?;
+        self.allow_aggregates = false;
+        let filter = self.where_clause()?;
+        self.allow_aggregates = true;
+        let mut group_by = Vec::new();
+        let mut order_by = Vec::new();
+
+        if self.keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            group_by = self.list(Parser::identifier)?;
+        }
+        let having = match self.keyword("HAVING") {
+            true => Some(self.or()?),
+            false => None,
+        };
+
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            order_by = self.list(|parser| {
+                let column = parser.operand()?;
+                let order = match parser.keyword("DESC") {
+                    true => Order::Desc,
+                    false => {
+                        parser.keyword("ASC");
+                        Order::Asc
+                    }
+                };
+                Ok((column, order))
+            })?;
+        }
+
+        let limit = match self.keyword("LIMIT") {
+            true => Some(self.count()?),
+            false => None,
+        };
+        let offset = match self.keyword("OFFSET") {
+            true => self.count()?,
+            false => 0,
+        };
+
+        if !group_by.is_empty() || !self.aggregates.is_empty() || having.is_some() {
+            if columns.is_none() {
+                return Err(sql_error(
+                    star,
+                    "cannot select * with GROUP BY or aggregates",
+                ));
+            }
+            if let Some((column, location)) =
+                plain.iter().find(|(column, _)| !group_by.contains(column))
+            {
+                return Err(sql_error(
+                    *location,
+                    &format!(
+                        "column `{}` must appear in GROUP BY or an aggregate",
+                        column
+                    ),
+                ));
+            }
+        }
+
+        Ok(Select {
+            table,
+            columns,
+            filter,
+            group_by,
+            aggregates: std::mem::take(&mut self.aggregates),
+            having,
+            order_by,
+            limit,
+            offset,
+            names: std::mem::take(&mut self.names),
+        })
+    }
+
+    /// Parse an aggregate call such as `COUNT(DISTINCT name)`, if one is next.
+    fn aggregate_call(&mut self) -> Result<Option<Aggregate>> {
+        let function = match (self.peek(), &self.tokens[self.position + 1..]) {
+            (
+                Token::Ident {
+                    name,
+                    quoted: false,
+                },
+                [next, ..],
+            ) if next.token == Token::Symbol("(") => match name.to_ascii_uppercase().as_str() {
+                "COUNT" => AggregateFn::Count,
+                "SUM" => AggregateFn::Sum,
+                "AVG" => AggregateFn::Avg,
+                "MIN" => AggregateFn::Min,
+                "MAX" => AggregateFn::Max,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        if !self.allow_aggregates {
+            return Err(self.error("aggregate functions are not allowed here"));
+        }
+        self.advance();
+        self.advance();
+
+        let aggregate = if function == AggregateFn::Count && self.symbol("*") {
+            Aggregate::count_all()
+        } else if function == AggregateFn::Count && self.keyword("DISTINCT") {
+            Aggregate::new(AggregateFn::CountDistinct, Some(&self.identifier()?))
+        } else {
+            Aggregate::new(function, Some(&self.identifier()?))
+        };
+        self.expect_symbol(")")?;
+
+        Ok(Some(aggregate))
+    }
+
+    /// Record an aggregate unless an identical one is already computed, returning its name.
+    fn register(&mut self, aggregate: Aggregate) -> String {
+        let name = aggregate.get_name();
+        if !self.aggregates.iter().any(|known| known.get_name() == name) {
+            self.aggregates.push(aggregate);
+        }
+        name
+    }
+
+    /// Parse a column name or, where allowed, an aggregate call standing for its result column.
+    fn operand(&mut self) -> Result<String> {
+        match self.aggregate_call()? {
+            Some(aggregate) => Ok(self.register(aggregate)),
+            None => self.identifier(),
+        }
+    }
+
+    fn insert(&mut self) -> Result<Statement> {
+        self.expect_keyword("INTO")?;
+        let table = self.identifier()?;
+        let columns = match self.symbol("(") {
+            true => {
+                let columns = self.list(Parser::identifier)?;
+                self.expect_symbol(")")?;
+                Some(columns)
+            }
+            false => None,
+        };
+        self.expect_keyword("VALUES")?;
+        let rows = self.list(|parser| {
+            parser.expect_symbol("(")?;
+            let values = parser.list(Parser::literal)?;
+            parser.expect_symbol(")")?;
+            Ok(values)
+        })?;
+
+        Ok(Statement::Insert {
+            table,
+            columns,
+            rows,
+            names: std::mem::take(&mut self.names),
+        })
+    }
+
+    fn update(&mut self) -> Result<Statement> {
+        let table = self.identifier()?;
+        self.expect_keyword("SET")?;
+        let assignments = self.list(|parser| {
+            let column = parser.identifier()?;
+            parser.expect_symbol("=")?;
+            Ok((column, parser.literal()?))
+        })?;
+        let filter = self.where_clause()?;
+
+        Ok(Statement::Update {
+            table,
+            assignments,
+            filter,
+            names: std::mem::take(&mut self.names),
+        })
+    }
+
+    fn delete(&mut self) -> Result<Statement> {
+        self.expect_keyword("FROM")?;
+        let table = self.identifier()?;
+        let filter = self.where_clause()?;
+
+        Ok(Statement::Delete {
+            table,
+            filter,
+            names: std::mem::take(&mut self.names),
+        })
+    }
+
+    fn where_clause(&mut self) -> Result<Option<Filter>> {
+        match self.keyword("WHERE") {
+            true => self.or().map(Some),
+            false => Ok(None),
+        }
+    }
+
+    fn or(&mut self) -> Result<Filter> {
+        let mut terms = vec![self.and()?];
+        while self.keyword("OR") {
+            terms.push(self.and()?);
+        }
+        Ok(balance(terms, &Filter::or))
+    }
+
+    fn and(&mut self) -> Result<Filter> {
+        let mut terms = vec![self.not()?];
+        while self.keyword("AND") {
+            terms.push(self.not()?);
+        }
+        Ok(balance(terms, &Filter::and))
+    }
+
+    fn not(&mut self) -> Result<Filter> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("condition nested too deeply"));
+        }
+        self.depth += 1;
+        let filter = if self.keyword("NOT") {
+            self.not().map(|filter| !filter)
+        } else if self.symbol("(") {
+            self.or()
+                .and_then(|filter| self.expect_symbol(")").map(|()| filter))
+        } else {
+            self.predicate()
+        };
+        self.depth -= 1;
+        filter
+    }
+
+    fn predicate(&mut self) -> Result<Filter> {
+        let column = self.operand()?;
+
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let filter = Filter::is_null(&column);
+            return Ok(if negated { !filter } else { filter });
+        }
+
+        let negated = self.keyword("NOT");
+
+        let filter = if self.keyword("BETWEEN") {
+            let low = self.literal()?;
+            self.expect_keyword("AND")?;
+            Filter::between(&column, low, self.literal()?)
+        } else if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let values = self.list(Parser::literal)?;
+            self.expect_symbol(")")?;
+            Filter::in_list(&column, values)
+        } else if self.keyword("LIKE") {
+            let Token::Str(pattern) = self.peek().clone() else {
+                return Err(self.error("expected string pattern"));
+            };
+            self.advance();
+            Filter::like(&column, &pattern)
+        } else if negated {
+            return Err(self.error("expected BETWEEN, IN or LIKE"));
+        } else {
+            let op = match self.peek() {
+                Token::Symbol("=") => CompareOp::Eq,
+                Token::Symbol("!=") | Token::Symbol("<>") => CompareOp::Ne,
+                Token::Symbol("<") => CompareOp::Lt,
+                Token::Symbol("<=") => CompareOp::Le,
+                Token::Symbol(">") => CompareOp::Gt,
+                Token::Symbol(">=") => CompareOp::Ge,
+                _ => return Err(self.error("expected comparison operator")),
+            };
+            self.advance();
+            Filter::compare(&column, op, self.literal()?)
+        };
+
+        Ok(if negated { !filter } else { filter })
+    }
+
+    fn literal(&mut self) -> Result<Value> {
+        let negative = self.symbol("-");
+
+        let value = match self.peek().clone() {
+            Token::Number(text) => self.number(&text, negative)?,
+            _ if negative => return Err(self.error("expected number")),
+            Token::Str(text) => Value::Text(text),
+            Token::Ident { quoted: false, .. } if self.keyword("NULL") => return Ok(Value::Null),
+            Token::Ident { quoted: false, .. } if self.keyword("TRUE") => {
+                return Ok(Value::Boolean(true));
+            }
+            Token::Ident { quoted: false, .. } if self.keyword("FALSE") => {
+                return Ok(Value::Boolean(false));
+            }
+            _ => return Err(self.error("expected literal")),
+        };
+
+        self.advance();
+        Ok(value)
+    }
+
+    fn number(&self, text: &str, negative: bool) -> Result<Value> {
+        let text = match negative {
+            true => format!("-{}", text),
+            false => text.to_string(),
+        };
+        let value = match text.contains(['.', 'e', 'E']) {
+            true => text.parse().ok().map(Value::Float),
+            false => text.parse().ok().map(Value::Integer),
+        };
+
+        value.ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn count(&mut self) -> Result<usize> {
+        match self.peek().clone() {
+            Token::Number(text) if text.parse::<usize>().is_ok() => {
+                self.advance();
+                Ok(text.parse().unwrap())
+            }
+            _ => Err(self.error("expected non-negative integer")),
+        }
+    }
+}
+
+fn sql_error((line, column): (usize, usize), message: &str) -> Error {
+    Error::Sql {
+        line,
+        column,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_select() {
+        let Statement::Select(select) = parse(
+            "select name, score from t where not (score < 50 or name in ('x', 'y')) \
+             order by score desc, name limit 10 offset 5;",
+        )
+        .unwrap() else {
+            panic!("expected SELECT");
+        };
+
+        assert_eq!(
+            select.columns,
+            Some(vec!["name".to_string(), "score".to_string()])
+        );
+        assert!(select.filter.is_some());
+        assert_eq!(
+            select.order_by,
+            vec![
+                ("score".to_string(), Order::Desc),
+                ("name".to_string(), Order::Asc)
+            ]
+        );
+        assert_eq!((select.limit, select.offset), (Some(10), 5));
+        assert_eq!(
+            select
+                .names
+                .iter()
+                .map(|name| (name.name.as_str(), name.line, name.column))
+                .collect::<Vec<_>>(),
+            vec![
+                ("name", 1, 8),
+                ("score", 1, 14),
+                ("t", 1, 25),
+                ("score", 1, 38),
+                ("name", 1, 52),
+                ("score", 1, 81),
+                ("name", 1, 93),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        let Statement::Insert { rows, .. } =
+            parse("INSERT INTO t VALUES (-3, 2.5, 'a', TRUE, false, NULL, 1e3)").unwrap()
+        else {
+            panic!("expected INSERT");
+        };
+
+        assert_eq!(
+            rows,
+            vec![vec![
+                Value::Integer(-3),
+                Value::Float(2.5),
+                Value::Text("a".to_string()),
+                Value::Boolean(true),
+                Value::Boolean(false),
+                Value::Null,
+                Value::Float(1000.0),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let position = |sql: &str| match parse(sql) {
+            Err(Error::Sql { line, column, .. }) => (line, column),
+            other => panic!("expected SQL error, got {:?}", other),
+        };
+
+        assert_eq!(position("SELEC * FROM t"), (1, 1));
+        assert_eq!(position("SELECT * FROM t WHERE"), (1, 22));
+        assert_eq!(position("SELECT *\nFROM t\nWHERE score >"), (3, 14));
+        assert_eq!(position("DELETE FROM t WHERE a NOT = 1"), (1, 27));
+        assert_eq!(position("SELECT * FROM t WHERE name"), (1, 27));
+        assert_eq!(position("SELECT * FROM t WHERE name name"), (1, 28));
+        assert!(matches!(
+            parse("SELECT * FROM t WHERE name"),
+            Err(Error::Sql { message, .. }) if message.ends_with("found end of statement")
+        ));
+        assert_eq!(position("SELECT * FROM t WHERE a LIKE 1"), (1, 30));
+        assert_eq!(position("SELECT * FROM select"), (1, 15));
+        assert_eq!(position("SELECT * FROM t LIMIT -1"), (1, 23));
+        assert_eq!(position("UPDATE t SET a = 1 b = 2"), (1, 20));
+        assert_eq!(
+            position("INSERT INTO t VALUES (99999999999999999999)"),
+            (1, 23)
+        );
+    }
+
+    #[test]
+    fn test_parse_depth_limit() {
+        let nested = |prefix: &str, count: usize| {
+            format!("SELECT * FROM t WHERE {}a = 1", prefix.repeat(count))
+        };
+        let too_deep = |sql: &str| match parse(sql) {
+            Err(Error::Sql { message, .. }) => message.contains("too deeply"),
+            _ => false,
+        };
+
+        assert!(parse(&(nested("(", 100) + &")".repeat(100))).is_ok());
+        assert!(too_deep(&nested("(", 200_000)));
+        assert!(too_deep(&nested("NOT ", 200_000)));
+        assert!(parse(&nested("a = 1 AND ", 200_000)).is_ok());
+
+        let terms: Vec<String> = (0..300).map(|id| format!("id = {}", id)).collect();
+        let sql = format!("SELECT * FROM t WHERE {}", terms.join(" OR "));
+        assert!(parse(&sql).is_ok());
+    }
+}
@@ -0,0 +1,482 @@
+use super::database::Database;
+use super::error::{Error, Result};
+use super::filter::Filter;
+use super::operations::{
+    delete_rows, insert_all, remove_rows, replace_rows, restore_rows, update_rows,
+};
+use super::row::Row;
+use super::types::Value;
+use super::wal::Log;
+
+/// A buffered change, applied when the transaction commits.
+enum Operation<'a> {
+    Insert(Vec<Row>),
+    Delete(Box<dyn FnMut(&Row) -> bool + 'a>),
+    DeleteWhere(Filter),
+    Update(Box<dyn FnMut(&Row) -> bool + 'a>, Vec<(String, Value)>),
+    UpdateWhere(Filter, Vec<(String, Value)>),
+}
+
+/// What an applied operation changed, to undo it if a later one fails.
+enum Undo {
+    /// The number of rows appended.
+    Insert(usize),
+    /// The former positions of the deleted rows, and the rows.
+    Delete(Vec<usize>, Vec<Row>),
+    /// The positions of the updated rows, and the rows before the update.
+    Update(Vec<usize>, Vec<Row>),
+}
+
+impl Undo {
+    fn apply(self, db: &mut Database) {
+        match self {
+            Undo::Insert(count) => {
+                let positions: Vec<usize> = (db.rows.len() - count..db.rows.len()).collect();
+                remove_rows(db, &positions);
+            }
+            Undo::Delete(positions, rows) => restore_rows(db, &positions, rows),
+            Undo::Update(positions, rows) => {
+                for &position in &positions {
+                    for index in &mut db.unique {
+                        index.remove(&db.rows[position]);
+                    }
+                }
+                replace_rows(db, &positions, rows);
+            }
+        }
+    }
+}
+
+/// A batch of inserts, deletes and updates applied to a database all at once, started with
+/// `Database::begin`.
+///
+/// Changes are buffered and leave the database untouched until `Transaction::commit`
+/// applies them in order. If any of them fails, none is applied. Dropping the transaction
+/// without committing discards the changes, like `Transaction::rollback`.
+///
+/// # Examples
+/// ```
+/// use rust_modules::tinycsv_db::*;
+///
+/// let mut db = Database::new(Schema::from_columns(vec![
+///     Column::new("id", DataType::Integer).primary_key(),
+///     Column::new("name", DataType::Text),
+/// ]));
+/// let row = |id: i64, name: &str| {
+///     Row::new(vec![Value::Integer(id), Value::Text(name.to_string())])
+/// };
+///
+/// let mut transaction = db.begin();
+/// transaction.insert(row(1, "Alice"));
+/// transaction.insert(row(1, "Bob"));
+/// assert!(matches!(transaction.commit(), Err(Error::UniqueViolation { .. })));
+/// assert!(db.get_rows().is_empty());
+///
+/// let mut transaction = db.begin();
+/// transaction.insert(row(1, "Alice"));
+/// transaction.savepoint("bob");
+/// transaction.insert(row(1, "Bob"));
+/// transaction.rollback_to("bob").unwrap();
+/// transaction.commit().unwrap();
+/// assert_eq!(db.get_rows().len(), 1);
+/// ```
+pub struct Transaction<'a> {
+    db: &'a mut Database,
+    operations: Vec<Operation<'a>>,
+    /// The names of the savepoints with the number of operations before each, in the
+    /// order they were set.
+    savepoints: Vec<(String, usize)>,
+}
+
+impl Database {
+    /// Start a transaction buffering changes to the database until it commits.
+    pub fn begin(&mut self) -> Transaction<'_> {
+        Transaction {
+            db: self,
+            operations: Vec::new(),
+            savepoints: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Transaction<'a> {
+    /// Insert a row, like `insert`.
+    pub fn insert(&mut self, row: Row) -> &mut Self {
+        self.insert_all(vec![row])
+    }
+
+    /// Insert several rows, like `execute` does for a multi-row `INSERT`.
+    pub fn insert_all(&mut self, rows: Vec<Row>) -> &mut Self {
+        self.operations.push(Operation::Insert(rows));
+        self
+    }
+
+    /// Delete the rows matching a predicate, like `delete`.
+    ///
+    /// The predicate sees the rows as changed by the earlier operations of the transaction.
+    pub fn delete(&mut self, predicate: impl FnMut(&Row) -> bool + 'a) -> &mut Self {
+        self.operations.push(Operation::Delete(Box::new(predicate)));
+        self
+    }
+
+    /// Delete the rows matching a filter, like `delete_where`.
+    pub fn delete_where(&mut self, filter: Filter) -> &mut Self {
+        self.operations.push(Operation::DeleteWhere(filter));
+        self
+    }
+
+    /// Update the rows matching a predicate, like `update`.
+    pub fn update(
+        &mut self,
+        predicate: impl FnMut(&Row) -> bool + 'a,
+        assignments: &[(&str, Value)],
+    ) -> &mut Self {
+        let assignments = owned(assignments);
+        self.operations
+            .push(Operation::Update(Box::new(predicate), assignments));
+        self
+    }
+
+    /// Update the rows matching a filter, like `update_where`.
+    pub fn update_where(&mut self, filter: Filter, assignments: &[(&str, Value)]) -> &mut Self {
+        let assignments = owned(assignments);
+        self.operations
+            .push(Operation::UpdateWhere(filter, assignments));
+        self
+    }
+
+    /// Mark the current point of the transaction, to undo the later changes with
+    /// `Transaction::rollback_to`. Setting a savepoint with the name of an existing one
+    /// replaces it.
+    pub fn savepoint(&mut self, name: &str) {
+        self.savepoints.retain(|(existing, _)| existing != name);
+        self.savepoints
+            .push((name.to_string(), self.operations.len()));
+    }
+
+    /// Discard the changes made since a savepoint, and the savepoints set after it. The
+    /// savepoint itself is kept, so it can be rolled back to again.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownSavepoint` if there is no savepoint with the name.
+    pub fn rollback_to(&mut self, name: &str) -> Result<()> {
+        let index = self.find(name)?;
+        self.operations.truncate(self.savepoints[index].1);
+        self.savepoints.truncate(index + 1);
+        Ok(())
+    }
+
+    /// Forget a savepoint and the savepoints set after it, keeping the changes.
+    ///
+    /// # Errors
+    /// Returns `Error::UnknownSavepoint` if there is no savepoint with the name.
+    pub fn release(&mut self, name: &str) -> Result<()> {
+        let index = self.find(name)?;
+        self.savepoints.truncate(index);
+        Ok(())
+    }
+
+    fn find(&self, name: &str) -> Result<usize> {
+        (self.savepoints.iter())
+            .position(|(existing, _)| existing == name)
+            .ok_or_else(|| Error::UnknownSavepoint(name.to_string()))
+    }
+
+    /// Apply the changes in order, all or none.
+    ///
+    /// The changes are applied to the database in place, remembering how to undo each one;
+    /// if a change fails, those already applied are undone in reverse order. For a database
+    /// stored in a file, the changes are then written to its log as one batch, so a crash
+    /// keeps either all of them or none, and they are undone as well if that write fails.
+    ///
+    /// # Errors
+    /// Returns the first error of the underlying operation, such as
+    /// `Error::UniqueViolation` or `Error::UnknownColumn`, or `Error::Io` if the changes
+    /// cannot be logged, leaving the database as it was before the transaction began.
+    pub fn commit(self) -> Result<()> {
+        let db = self.db;
+        // Collect the records of the changes to log them as one batch at the end.
+        let log = db.log.take();
+        if log.is_some() {
+            db.log = Some(Log::Buffer(Vec::new()));
+        }
+
+        let mut undo = Vec::new();
+        let mut result = Ok(());
+        for operation in self.operations {
+            match apply(db, operation) {
+                Ok(change) => undo.push(change),
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+
+        let records = std::mem::replace(&mut db.log, log);
+        if let (Ok(()), Some(Log::File(log)), Some(Log::Buffer(records))) =
+            (&result, &mut db.log, records)
+        {
+            result = log.commit(&records);
+        }
+
+        if result.is_err() {
+            for change in undo.into_iter().rev() {
+                change.apply(db);
+            }
+        }
+        result
+    }
+
+    /// Discard the changes; dropping the transaction does the same.
+    pub fn rollback(self) {}
+}
+
+/// Apply one operation, returning how to undo it.
+fn apply(db: &mut Database, operation: Operation) -> Result<Undo> {
+    type Changed = (Vec<usize>, Vec<Row>);
+    let deleted = |(positions, rows): Changed| Undo::Delete(positions, rows);
+    let updated = |(positions, rows): Changed| Undo::Update(positions, rows);
+
+    match operation {
+        Operation::Insert(rows) => insert_all(db, rows).map(Undo::Insert),
+        Operation::Delete(predicate) => delete_rows(db, predicate).map(deleted),
+        Operation::DeleteWhere(filter) => {
+            let predicate = filter.compile(&db.schema)?;
+            delete_rows(db, predicate).map(deleted)
+        }
+        Operation::Update(predicate, assignments) => {
+            update_rows(db, predicate, &borrowed(&assignments)).map(updated)
+        }
+        Operation::UpdateWhere(filter, assignments) => {
+            let predicate = filter.compile(&db.schema)?;
+            update_rows(db, predicate, &borrowed(&assignments)).map(updated)
+        }
+    }
+}
+
+fn owned(assignments: &[(&str, Value)]) -> Vec<(String, Value)> {
+    (assignments.iter())
+        .map(|(column, value)| (column.to_string(), value.clone()))
+        .collect()
+}
+
+fn borrowed(assignments: &[(String, Value)]) -> Vec<(&str, Value)> {
+    (assignments.iter())
+        .map(|(column, value)| (column.as_str(), value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::{table, text};
+    use crate::tinycsv_db::{
+        Column, DataType, IndexKind, find_exact, find_similar, find_words, search,
+    };
+    use std::ops::Bound;
+
+    fn accounts() -> Database {
+        let mut db = table(
+            vec![
+                Column::new("id", DataType::Integer).primary_key(),
+                Column::new("owner", DataType::Text),
+                Column::new("balance", DataType::Integer),
+            ],
+            vec![
+                account(1, "alice", 100).values,
+                account(2, "bob", 50).values,
+            ],
+        );
+        db.create_text_index("owner").unwrap();
+        db
+    }
+
+    fn account(id: i64, owner: &str, balance: i64) -> Row {
+        Row::new(vec![
+            Value::Integer(id),
+            text(owner),
+            Value::Integer(balance),
+        ])
+    }
+
+    fn balances(db: &Database) -> Vec<(i64, i64)> {
+        (db.get_rows().iter())
+            .map(|row| match (row.get_value(0), row.get_value(2)) {
+                (Some(Value::Integer(id)), Some(Value::Integer(balance))) => (*id, *balance),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_commit_applies_changes_in_order() {
+        let mut db = accounts();
+        let mut transaction = db.begin();
+        transaction
+            .insert(account(3, "carol", 10))
+            .update_where(
+                Filter::eq("owner", text("bob")),
+                &[("balance", Value::Integer(75))],
+            )
+            .delete(|row| row.values[0] == Value::Integer(1))
+            // Sees the row inserted above.
+            .update(
+                |row| row.values[2] == Value::Integer(10),
+                &[("id", Value::Integer(1))],
+            );
+        transaction.commit().unwrap();
+
+        assert_eq!(balances(&db), vec![(2, 75), (1, 10)]);
+        assert_eq!(find_words(&db, "owner", "carol").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_constraint_failure_rolls_back_the_whole_batch() {
+        let mut db = accounts();
+        let mut transaction = db.begin();
+        transaction
+            .delete_where(Filter::eq("id", Value::Integer(2)))
+            .insert(account(3, "carol", 10))
+            .update_where(
+                Filter::eq("id", Value::Integer(3)),
+                &[("id", Value::Integer(1))],
+            );
+
+        assert!(matches!(
+            transaction.commit(),
+            Err(Error::UniqueViolation { .. })
+        ));
+        assert_eq!(balances(&db), vec![(1, 100), (2, 50)]);
+        assert_eq!(find_exact(&db, "id", &Value::Integer(3)).unwrap().len(), 0);
+
+        // The unique index was not touched either, so id 3 is still free.
+        let mut transaction = db.begin();
+        transaction.insert(account(3, "carol", 10));
+        transaction.commit().unwrap();
+        assert_eq!(balances(&db), vec![(1, 100), (2, 50), (3, 10)]);
+    }
+
+    #[test]
+    fn test_failure_restores_every_index() {
+        let mut db = accounts();
+        db.create_index("balance", IndexKind::BTree).unwrap();
+        db.create_trigram_index("owner").unwrap();
+        let mut transaction = db.begin();
+        transaction.insert_all(vec![account(3, "carol", 10), account(4, "dave", 50)]);
+        transaction.commit().unwrap();
+        let before = db.clone();
+
+        let mut transaction = db.begin();
+        transaction
+            .delete_where(Filter::eq("balance", Value::Integer(50)))
+            .update_where(
+                Filter::eq("id", Value::Integer(1)),
+                &[("owner", text("zed"))],
+            )
+            .insert(account(5, "erin", 50))
+            .delete(|row| row.values[0] == Value::Integer(3))
+            .insert(account(1, "again", 0));
+
+        assert!(matches!(
+            transaction.commit(),
+            Err(Error::UniqueViolation { .. })
+        ));
+        assert_eq!(db.get_rows(), before.get_rows());
+        for balance in [0, 10, 50, 100] {
+            let value = Value::Integer(balance);
+            assert_eq!(db.indexes[0].get(&value), before.indexes[0].get(&value));
+        }
+        assert_eq!(
+            db.indexes[0].range(Bound::Included(&Value::Integer(50)), Bound::Unbounded),
+            Some(vec![0, 1, 3])
+        );
+        let query = "alice bob carol dave zed erin";
+        assert_eq!(
+            search(&db, "owner", query).unwrap(),
+            search(&before, "owner", query).unwrap()
+        );
+        assert_eq!(
+            find_similar(&db, "owner", "dav", 0.2).unwrap(),
+            find_similar(&before, "owner", "dav", 0.2).unwrap()
+        );
+        assert_eq!(find_exact(&db, "id", &Value::Integer(5)).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_predicates_may_keep_state() {
+        let mut db = accounts();
+        let mut transaction = db.begin();
+        transaction.insert(account(3, "carol", 10));
+        let mut seen = 0;
+        transaction.update(
+            move |_| {
+                seen += 1;
+                seen == 1
+            },
+            &[("balance", Value::Integer(0))],
+        );
+        let mut seen = 0;
+        transaction.delete(move |_| {
+            seen += 1;
+            seen % 2 == 0
+        });
+        transaction.commit().unwrap();
+
+        assert_eq!(balances(&db), vec![(1, 0), (3, 10)]);
+    }
+
+    #[test]
+    fn test_unknown_column_rolls_back() {
+        let mut db = accounts();
+        let mut transaction = db.begin();
+        transaction
+            .insert(account(3, "carol", 10))
+            .delete_where(Filter::is_null("email"));
+
+        assert!(matches!(transaction.commit(), Err(Error::UnknownColumn(_))));
+        assert_eq!(db.get_rows().len(), 2);
+    }
+
+    #[test]
+    fn test_rollback_and_drop_discard_changes() {
+        let mut db = accounts();
+
+        let mut transaction = db.begin();
+        transaction.delete(|_| true);
+        transaction.rollback();
+        assert_eq!(db.get_rows().len(), 2);
+
+        {
+            let mut transaction = db.begin();
+            transaction.delete(|_| true);
+        }
+        assert_eq!(db.get_rows().len(), 2);
+    }
+
+    #[test]
+    fn test_savepoints() {
+        let mut db = accounts();
+        let mut transaction = db.begin();
+
+        transaction.insert(account(3, "carol", 10));
+        transaction.savepoint("a");
+        transaction.insert(account(4, "dave", 20));
+        transaction.savepoint("b");
+        transaction.insert(account(5, "erin", 30));
+
+        transaction.rollback_to("a").unwrap();
+        assert!(matches!(
+            transaction.rollback_to("b"),
+            Err(Error::UnknownSavepoint(name)) if name == "b"
+        ));
+        transaction.insert(account(6, "frank", 40));
+        transaction.rollback_to("a").unwrap();
+        transaction.insert(account(7, "grace", 50));
+        transaction.release("a").unwrap();
+        assert!(transaction.release("a").is_err());
+        transaction.commit().unwrap();
+
+        assert_eq!(balances(&db), vec![(1, 100), (2, 50), (3, 10), (7, 50)]);
+    }
+}
@@ -1,3 +1,6 @@
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum DataType {
     Integer,
     Text,
@@ -5,10 +8,188 @@ pub enum DataType {
     Boolean,
 }
 
-#[derive(PartialEq, Debug)]
+impl DataType {
+    /// Get the name of the data type used in typed CSV headers (`int`, `text`, `float`, `bool`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataType::Integer => "int",
+            DataType::Text => "text",
+            DataType::Float => "float",
+            DataType::Boolean => "bool",
+        }
+    }
+
+    /// Look up a data type by the name used in typed CSV headers.
+    pub fn from_name(name: &str) -> Option<DataType> {
+        match name {
+            "int" => Some(DataType::Integer),
+            "text" => Some(DataType::Text),
+            "float" => Some(DataType::Float),
+            "bool" => Some(DataType::Boolean),
+            _ => None,
+        }
+    }
+}
+
+/// How values of a different type are handled when they are stored in a column.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Coercion {
+    /// Values must have exactly the data type of the column.
+    #[default]
+    Strict,
+    /// Values are converted to the data type of the column when no information is lost:
+    /// integers into floats, whole floats into integers, text that parses as the column type,
+    /// and any value into text.
+    Lenient,
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum Value {
     Integer(i64),
     Text(String),
     Float(f64),
     Boolean(bool),
+    Null,
+}
+
+impl Value {
+    /// Get the data type of the value.
+    ///
+    /// # Returns
+    /// The data type of the value, or `None` for `Value::Null`, which belongs to every type.
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            Value::Integer(_) => Some(DataType::Integer),
+            Value::Text(_) => Some(DataType::Text),
+            Value::Float(_) => Some(DataType::Float),
+            Value::Boolean(_) => Some(DataType::Boolean),
+            Value::Null => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Compare two values for equality with SQL three-valued logic.
+    ///
+    /// # Arguments
+    /// * `other` - The value to compare with
+    ///
+    /// # Returns
+    /// `None` (unknown) if either value is `Value::Null`, otherwise whether the values are equal.
+    pub fn sql_eq(&self, other: &Value) -> Option<bool> {
+        if self.is_null() || other.is_null() {
+            None
+        } else {
+            Some(self == other)
+        }
+    }
+
+    /// Parse a value of the given data type from its text representation.
+    ///
+    /// # Arguments
+    /// * `text` - The text to parse
+    /// * `data_type` - The data type the text should be parsed as
+    ///
+    /// # Returns
+    /// The parsed value, or `None` if the text is not a valid value of the data type.
+    pub fn parse(text: &str, data_type: DataType) -> Option<Value> {
+        match data_type {
+            DataType::Integer => text.parse().ok().map(Value::Integer),
+            DataType::Text => Some(Value::Text(text.to_string())),
+            DataType::Float => text.parse().ok().map(Value::Float),
+            DataType::Boolean => text.parse().ok().map(Value::Boolean),
+        }
+    }
+
+    /// Compare two values with SQL three-valued logic.
+    ///
+    /// Integers and floats are compared numerically, and other values only with values of
+    /// the same data type.
+    ///
+    /// # Arguments
+    /// * `other` - The value to compare with
+    ///
+    /// # Returns
+    /// The ordering of the values, or `None` (unknown) if either value is `Value::Null` or
+    /// the values cannot be compared.
+    pub fn sql_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+            (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Compare two values with a total order, used for sorting.
+    ///
+    /// `Value::Null` sorts first, followed by booleans, numbers (integers and floats compared
+    /// numerically, an integer before an equal float) and text. Floats are ordered with
+    /// `f64::total_cmp`, so `NaN` sorts after every other number.
+    ///
+    /// # Arguments
+    /// * `other` - The value to compare with
+    ///
+    /// # Returns
+    /// The ordering of the values.
+    pub fn total_cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).total_cmp(b).then(Ordering::Less),
+            (Value::Float(a), Value::Integer(b)) => {
+                a.total_cmp(&(*b as f64)).then(Ordering::Greater)
+            }
+            (Value::Text(a), Value::Text(b)) => a.cmp(b),
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Boolean(_) => 1,
+            Value::Integer(_) | Value::Float(_) => 2,
+            Value::Text(_) => 3,
+        }
+    }
+
+    /// Convert the value to the given data type according to the coercion mode.
+    ///
+    /// # Arguments
+    /// * `data_type` - The data type to convert the value to
+    /// * `coercion` - The coercion mode deciding which conversions are allowed
+    ///
+    /// # Returns
+    /// The converted value, or `None` if the value cannot be converted. `Value::Null` is
+    /// returned unchanged.
+    pub fn coerce(self, data_type: DataType, coercion: Coercion) -> Option<Value> {
+        if self.data_type().is_none_or(|found| found == data_type) {
+            return Some(self);
+        }
+
+        if coercion == Coercion::Strict {
+            return None;
+        }
+
+        match (self, data_type) {
+            (Value::Integer(num), DataType::Float) => Some(Value::Float(num as f64)),
+            (Value::Float(num), DataType::Integer)
+                if num.fract() == 0.0 && num >= i64::MIN as f64 && num < i64::MAX as f64 =>
+            {
+                Some(Value::Integer(num as i64))
+            }
+            (Value::Text(text), data_type) => Value::parse(text.trim(), data_type),
+            (Value::Integer(num), DataType::Text) => Some(Value::Text(num.to_string())),
+            (Value::Float(num), DataType::Text) => Some(Value::Text(num.to_string())),
+            (Value::Boolean(bool), DataType::Text) => Some(Value::Text(bool.to_string())),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,654 @@
+/// The characters whose canonical decomposition is a single base character followed by
+/// combining diacritical marks, with that base character, sorted by character.
+///
+/// Generated from `UnicodeData.txt` of Unicode 14.0.0: decompositions are followed
+/// recursively and the marks of the blocks Combining Diacritical Marks (U+0300..U+036F),
+/// its Extended (U+1AB0..U+1AFF) and Supplement (U+1DC0..U+1DFF) blocks, Combining Marks
+/// for Symbols (U+20D0..U+20FF) and Combining Half Marks (U+FE20..U+FE2F) are dropped.
+#[rustfmt::skip]
+pub(crate) const DECOMPOSITIONS: &[(char, char)] = &[
+    ('\u{00C0}', '\u{0041}'), ('\u{00C1}', '\u{0041}'), ('\u{00C2}', '\u{0041}'),
+    ('\u{00C3}', '\u{0041}'), ('\u{00C4}', '\u{0041}'), ('\u{00C5}', '\u{0041}'),
+    ('\u{00C7}', '\u{0043}'), ('\u{00C8}', '\u{0045}'), ('\u{00C9}', '\u{0045}'),
+    ('\u{00CA}', '\u{0045}'), ('\u{00CB}', '\u{0045}'), ('\u{00CC}', '\u{0049}'),
+    ('\u{00CD}', '\u{0049}'), ('\u{00CE}', '\u{0049}'), ('\u{00CF}', '\u{0049}'),
+    ('\u{00D1}', '\u{004E}'), ('\u{00D2}', '\u{004F}'), ('\u{00D3}', '\u{004F}'),
+    ('\u{00D4}', '\u{004F}'), ('\u{00D5}', '\u{004F}'), ('\u{00D6}', '\u{004F}'),
+    ('\u{00D9}', '\u{0055}'), ('\u{00DA}', '\u{0055}'), ('\u{00DB}', '\u{0055}'),
+    ('\u{00DC}', '\u{0055}'), ('\u{00DD}', '\u{0059}'), ('\u{00E0}', '\u{0061}'),
+    ('\u{00E1}', '\u{0061}'), ('\u{00E2}', '\u{0061}'), ('\u{00E3}', '\u{0061}'),
+    ('\u{00E4}', '\u{0061}'), ('\u{00E5}', '\u{0061}'), ('\u{00E7}', '\u{0063}'),
+    ('\u{00E8}', '\u{0065}'), ('\u{00E9}', '\u{0065}'), ('\u{00EA}', '\u{0065}'),
+    ('\u{00EB}', '\u{0065}'), ('\u{00EC}', '\u{0069}'), ('\u{00ED}', '\u{0069}'),
+    ('\u{00EE}', '\u{0069}'), ('\u{00EF}', '\u{0069}'), ('\u{00F1}', '\u{006E}'),
+    ('\u{00F2}', '\u{006F}'), ('\u{00F3}', '\u{006F}'), ('\u{00F4}', '\u{006F}'),
+    ('\u{00F5}', '\u{006F}'), ('\u{00F6}', '\u{006F}'), ('\u{00F9}', '\u{0075}'),
+    ('\u{00FA}', '\u{0075}'), ('\u{00FB}', '\u{0075}'), ('\u{00FC}', '\u{0075}'),
+    ('\u{00FD}', '\u{0079}'), ('\u{00FF}', '\u{0079}'), ('\u{0100}', '\u{0041}'),
+    ('\u{0101}', '\u{0061}'), ('\u{0102}', '\u{0041}'), ('\u{0103}', '\u{0061}'),
+    ('\u{0104}', '\u{0041}'), ('\u{0105}', '\u{0061}'), ('\u{0106}', '\u{0043}'),
+    ('\u{0107}', '\u{0063}'), ('\u{0108}', '\u{0043}'), ('\u{0109}', '\u{0063}'),
+    ('\u{010A}', '\u{0043}'), ('\u{010B}', '\u{0063}'), ('\u{010C}', '\u{0043}'),
+    ('\u{010D}', '\u{0063}'), ('\u{010E}', '\u{0044}'), ('\u{010F}', '\u{0064}'),
+    ('\u{0112}', '\u{0045}'), ('\u{0113}', '\u{0065}'), ('\u{0114}', '\u{0045}'),
+    ('\u{0115}', '\u{0065}'), ('\u{0116}', '\u{0045}'), ('\u{0117}', '\u{0065}'),
+    ('\u{0118}', '\u{0045}'), ('\u{0119}', '\u{0065}'), ('\u{011A}', '\u{0045}'),
+    ('\u{011B}', '\u{0065}'), ('\u{011C}', '\u{0047}'), ('\u{011D}', '\u{0067}'),
+    ('\u{011E}', '\u{0047}'), ('\u{011F}', '\u{0067}'), ('\u{0120}', '\u{0047}'),
+    ('\u{0121}', '\u{0067}'), ('\u{0122}', '\u{0047}'), ('\u{0123}', '\u{0067}'),
+    ('\u{0124}', '\u{0048}'), ('\u{0125}', '\u{0068}'), ('\u{0128}', '\u{0049}'),
+    ('\u{0129}', '\u{0069}'), ('\u{012A}', '\u{0049}'), ('\u{012B}', '\u{0069}'),
+    ('\u{012C}', '\u{0049}'), ('\u{012D}', '\u{0069}'), ('\u{012E}', '\u{0049}'),
+    ('\u{012F}', '\u{0069}'), ('\u{0130}', '\u{0049}'), ('\u{0134}', '\u{004A}'),
+    ('\u{0135}', '\u{006A}'), ('\u{0136}', '\u{004B}'), ('\u{0137}', '\u{006B}'),
+    ('\u{0139}', '\u{004C}'), ('\u{013A}', '\u{006C}'), ('\u{013B}', '\u{004C}'),
+    ('\u{013C}', '\u{006C}'), ('\u{013D}', '\u{004C}'), ('\u{013E}', '\u{006C}'),
+    ('\u{0143}', '\u{004E}'), ('\u{0144}', '\u{006E}'), ('\u{0145}', '\u{004E}'),
+    ('\u{0146}', '\u{006E}'), ('\u{0147}', '\u{004E}'), ('\u{0148}', '\u{006E}'),
+    ('\u{014C}', '\u{004F}'), ('\u{014D}', '\u{006F}'), ('\u{014E}', '\u{004F}'),
+    ('\u{014F}', '\u{006F}'), ('\u{0150}', '\u{004F}'), ('\u{0151}', '\u{006F}'),
+    ('\u{0154}', '\u{0052}'), ('\u{0155}', '\u{0072}'), ('\u{0156}', '\u{0052}'),
+    ('\u{0157}', '\u{0072}'), ('\u{0158}', '\u{0052}'), ('\u{0159}', '\u{0072}'),
+    ('\u{015A}', '\u{0053}'), ('\u{015B}', '\u{0073}'), ('\u{015C}', '\u{0053}'),
+    ('\u{015D}', '\u{0073}'), ('\u{015E}', '\u{0053}'), ('\u{015F}', '\u{0073}'),
+    ('\u{0160}', '\u{0053}'), ('\u{0161}', '\u{0073}'), ('\u{0162}', '\u{0054}'),
+    ('\u{0163}', '\u{0074}'), ('\u{0164}', '\u{0054}'), ('\u{0165}', '\u{0074}'),
+    ('\u{0168}', '\u{0055}'), ('\u{0169}', '\u{0075}'), ('\u{016A}', '\u{0055}'),
+    ('\u{016B}', '\u{0075}'), ('\u{016C}', '\u{0055}'), ('\u{016D}', '\u{0075}'),
+    ('\u{016E}', '\u{0055}'), ('\u{016F}', '\u{0075}'), ('\u{0170}', '\u{0055}'),
+    ('\u{0171}', '\u{0075}'), ('\u{0172}', '\u{0055}'), ('\u{0173}', '\u{0075}'),
+    ('\u{0174}', '\u{0057}'), ('\u{0175}', '\u{0077}'), ('\u{0176}', '\u{0059}'),
+    ('\u{0177}', '\u{0079}'), ('\u{0178}', '\u{0059}'), ('\u{0179}', '\u{005A}'),
+    ('\u{017A}', '\u{007A}'), ('\u{017B}', '\u{005A}'), ('\u{017C}', '\u{007A}'),
+    ('\u{017D}', '\u{005A}'), ('\u{017E}', '\u{007A}'), ('\u{01A0}', '\u{004F}'),
+    ('\u{01A1}', '\u{006F}'), ('\u{01AF}', '\u{0055}'), ('\u{01B0}', '\u{0075}'),
+    ('\u{01CD}', '\u{0041}'), ('\u{01CE}', '\u{0061}'), ('\u{01CF}', '\u{0049}'),
+    ('\u{01D0}', '\u{0069}'), ('\u{01D1}', '\u{004F}'), ('\u{01D2}', '\u{006F}'),
+    ('\u{01D3}', '\u{0055}'), ('\u{01D4}', '\u{0075}'), ('\u{01D5}', '\u{0055}'),
+    ('\u{01D6}', '\u{0075}'), ('\u{01D7}', '\u{0055}'), ('\u{01D8}', '\u{0075}'),
+    ('\u{01D9}', '\u{0055}'), ('\u{01DA}', '\u{0075}'), ('\u{01DB}', '\u{0055}'),
+    ('\u{01DC}', '\u{0075}'), ('\u{01DE}', '\u{0041}'), ('\u{01DF}', '\u{0061}'),
+    ('\u{01E0}', '\u{0041}'), ('\u{01E1}', '\u{0061}'), ('\u{01E2}', '\u{00C6}'),
+    ('\u{01E3}', '\u{00E6}'), ('\u{01E6}', '\u{0047}'), ('\u{01E7}', '\u{0067}'),
+    ('\u{01E8}', '\u{004B}'), ('\u{01E9}', '\u{006B}'), ('\u{01EA}', '\u{004F}'),
+    ('\u{01EB}', '\u{006F}'), ('\u{01EC}', '\u{004F}'), ('\u{01ED}', '\u{006F}'),
+    ('\u{01EE}', '\u{01B7}'), ('\u{01EF}', '\u{0292}'), ('\u{01F0}', '\u{006A}'),
+    ('\u{01F4}', '\u{0047}'), ('\u{01F5}', '\u{0067}'), ('\u{01F8}', '\u{004E}'),
+    ('\u{01F9}', '\u{006E}'), ('\u{01FA}', '\u{0041}'), ('\u{01FB}', '\u{0061}'),
+    ('\u{01FC}', '\u{00C6}'), ('\u{01FD}', '\u{00E6}'), ('\u{01FE}', '\u{00D8}'),
+    ('\u{01FF}', '\u{00F8}'), ('\u{0200}', '\u{0041}'), ('\u{0201}', '\u{0061}'),
+    ('\u{0202}', '\u{0041}'), ('\u{0203}', '\u{0061}'), ('\u{0204}', '\u{0045}'),
+    ('\u{0205}', '\u{0065}'), ('\u{0206}', '\u{0045}'), ('\u{0207}', '\u{0065}'),
+    ('\u{0208}', '\u{0049}'), ('\u{0209}', '\u{0069}'), ('\u{020A}', '\u{0049}'),
+    ('\u{020B}', '\u{0069}'), ('\u{020C}', '\u{004F}'), ('\u{020D}', '\u{006F}'),
+    ('\u{020E}', '\u{004F}'), ('\u{020F}', '\u{006F}'), ('\u{0210}', '\u{0052}'),
+    ('\u{0211}', '\u{0072}'), ('\u{0212}', '\u{0052}'), ('\u{0213}', '\u{0072}'),
+    ('\u{0214}', '\u{0055}'), ('\u{0215}', '\u{0075}'), ('\u{0216}', '\u{0055}'),
+    ('\u{0217}', '\u{0075}'), ('\u{0218}', '\u{0053}'), ('\u{0219}', '\u{0073}'),
+    ('\u{021A}', '\u{0054}'), ('\u{021B}', '\u{0074}'), ('\u{021E}', '\u{0048}'),
+    ('\u{021F}', '\u{0068}'), ('\u{0226}', '\u{0041}'), ('\u{0227}', '\u{0061}'),
+    ('\u{0228}', '\u{0045}'), ('\u{0229}', '\u{0065}'), ('\u{022A}', '\u{004F}'),
+    ('\u{022B}', '\u{006F}'), ('\u{022C}', '\u{004F}'), ('\u{022D}', '\u{006F}'),
+    ('\u{022E}', '\u{004F}'), ('\u{022F}', '\u{006F}'), ('\u{0230}', '\u{004F}'),
+    ('\u{0231}', '\u{006F}'), ('\u{0232}', '\u{0059}'), ('\u{0233}', '\u{0079}'),
+    ('\u{0374}', '\u{02B9}'), ('\u{037E}', '\u{003B}'), ('\u{0385}', '\u{00A8}'),
+    ('\u{0386}', '\u{0391}'), ('\u{0387}', '\u{00B7}'), ('\u{0388}', '\u{0395}'),
+    ('\u{0389}', '\u{0397}'), ('\u{038A}', '\u{0399}'), ('\u{038C}', '\u{039F}'),
+    ('\u{038E}', '\u{03A5}'), ('\u{038F}', '\u{03A9}'), ('\u{0390}', '\u{03B9}'),
+    ('\u{03AA}', '\u{0399}'), ('\u{03AB}', '\u{03A5}'), ('\u{03AC}', '\u{03B1}'),
+    ('\u{03AD}', '\u{03B5}'), ('\u{03AE}', '\u{03B7}'), ('\u{03AF}', '\u{03B9}'),
+    ('\u{03B0}', '\u{03C5}'), ('\u{03CA}', '\u{03B9}'), ('\u{03CB}', '\u{03C5}'),
+    ('\u{03CC}', '\u{03BF}'), ('\u{03CD}', '\u{03C5}'), ('\u{03CE}', '\u{03C9}'),
+    ('\u{03D3}', '\u{03D2}'), ('\u{03D4}', '\u{03D2}'), ('\u{0400}', '\u{0415}'),
+    ('\u{0401}', '\u{0415}'), ('\u{0403}', '\u{0413}'), ('\u{0407}', '\u{0406}'),
+    ('\u{040C}', '\u{041A}'), ('\u{040D}', '\u{0418}'), ('\u{040E}', '\u{0423}'),
+    ('\u{0419}', '\u{0418}'), ('\u{0439}', '\u{0438}'), ('\u{0450}', '\u{0435}'),
+    ('\u{0451}', '\u{0435}'), ('\u{0453}', '\u{0433}'), ('\u{0457}', '\u{0456}'),
+    ('\u{045C}', '\u{043A}'), ('\u{045D}', '\u{0438}'), ('\u{045E}', '\u{0443}'),
+    ('\u{0476}', '\u{0474}'), ('\u{0477}', '\u{0475}'), ('\u{04C1}', '\u{0416}'),
+    ('\u{04C2}', '\u{0436}'), ('\u{04D0}', '\u{0410}'), ('\u{04D1}', '\u{0430}'),
+    ('\u{04D2}', '\u{0410}'), ('\u{04D3}', '\u{0430}'), ('\u{04D6}', '\u{0415}'),
+    ('\u{04D7}', '\u{0435}'), ('\u{04DA}', '\u{04D8}'), ('\u{04DB}', '\u{04D9}'),
+    ('\u{04DC}', '\u{0416}'), ('\u{04DD}', '\u{0436}'), ('\u{04DE}', '\u{0417}'),
+    ('\u{04DF}', '\u{0437}'), ('\u{04E2}', '\u{0418}'), ('\u{04E3}', '\u{0438}'),
+    ('\u{04E4}', '\u{0418}'), ('\u{04E5}', '\u{0438}'), ('\u{04E6}', '\u{041E}'),
+    ('\u{04E7}', '\u{043E}'), ('\u{04EA}', '\u{04E8}'), ('\u{04EB}', '\u{04E9}'),
+    ('\u{04EC}', '\u{042D}'), ('\u{04ED}', '\u{044D}'), ('\u{04EE}', '\u{0423}'),
+    ('\u{04EF}', '\u{0443}'), ('\u{04F0}', '\u{0423}'), ('\u{04F1}', '\u{0443}'),
+    ('\u{04F2}', '\u{0423}'), ('\u{04F3}', '\u{0443}'), ('\u{04F4}', '\u{0427}'),
+    ('\u{04F5}', '\u{0447}'), ('\u{04F8}', '\u{042B}'), ('\u{04F9}', '\u{044B}'),
+    ('\u{1E00}', '\u{0041}'), ('\u{1E01}', '\u{0061}'), ('\u{1E02}', '\u{0042}'),
+    ('\u{1E03}', '\u{0062}'), ('\u{1E04}', '\u{0042}'), ('\u{1E05}', '\u{0062}'),
+    ('\u{1E06}', '\u{0042}'), ('\u{1E07}', '\u{0062}'), ('\u{1E08}', '\u{0043}'),
+    ('\u{1E09}', '\u{0063}'), ('\u{1E0A}', '\u{0044}'), ('\u{1E0B}', '\u{0064}'),
+    ('\u{1E0C}', '\u{0044}'), ('\u{1E0D}', '\u{0064}'), ('\u{1E0E}', '\u{0044}'),
+    ('\u{1E0F}', '\u{0064}'), ('\u{1E10}', '\u{0044}'), ('\u{1E11}', '\u{0064}'),
+    ('\u{1E12}', '\u{0044}'), ('\u{1E13}', '\u{0064}'), ('\u{1E14}', '\u{0045}'),
+    ('\u{1E15}', '\u{0065}'), ('\u{1E16}', '\u{0045}'), ('\u{1E17}', '\u{0065}'),
+    ('\u{1E18}', '\u{0045}'), ('\u{1E19}', '\u{0065}'), ('\u{1E1A}', '\u{0045}'),
+    ('\u{1E1B}', '\u{0065}'), ('\u{1E1C}', '\u{0045}'), ('\u{1E1D}', '\u{0065}'),
+    ('\u{1E1E}', '\u{0046}'), ('\u{1E1F}', '\u{0066}'), ('\u{1E20}', '\u{0047}'),
+    ('\u{1E21}', '\u{0067}'), ('\u{1E22}', '\u{0048}'), ('\u{1E23}', '\u{0068}'),
+    ('\u{1E24}', '\u{0048}'), ('\u{1E25}', '\u{0068}'), ('\u{1E26}', '\u{0048}'),
+    ('\u{1E27}', '\u{0068}'), ('\u{1E28}', '\u{0048}'), ('\u{1E29}', '\u{0068}'),
+    ('\u{1E2A}', '\u{0048}'), ('\u{1E2B}', '\u{0068}'), ('\u{1E2C}', '\u{0049}'),
+    ('\u{1E2D}', '\u{0069}'), ('\u{1E2E}', '\u{0049}'), ('\u{1E2F}', '\u{0069}'),
+    ('\u{1E30}', '\u{004B}'), ('\u{1E31}', '\u{006B}'), ('\u{1E32}', '\u{004B}'),
+    ('\u{1E33}', '\u{006B}'), ('\u{1E34}', '\u{004B}'), ('\u{1E35}', '\u{006B}'),
+    ('\u{1E36}', '\u{004C}'), ('\u{1E37}', '\u{006C}'), ('\u{1E38}', '\u{004C}'),
+    ('\u{1E39}', '\u{006C}'), ('\u{1E3A}', '\u{004C}'), ('\u{1E3B}', '\u{006C}'),
+    ('\u{1E3C}', '\u{004C}'), ('\u{1E3D}', '\u{006C}'), ('\u{1E3E}', '\u{004D}'),
+    ('\u{1E3F}', '\u{006D}'), ('\u{1E40}', '\u{004D}'), ('\u{1E41}', '\u{006D}'),
+    ('\u{1E42}', '\u{004D}'), ('\u{1E43}', '\u{006D}'), ('\u{1E44}', '\u{004E}'),
+    ('\u{1E45}', '\u{006E}'), ('\u{1E46}', '\u{004E}'), ('\u{1E47}', '\u{006E}'),
+    ('\u{1E48}', '\u{004E}'), ('\u{1E49}', '\u{006E}'), ('\u{1E4A}', '\u{004E}'),
+    ('\u{1E4B}', '\u{006E}'), ('\u{1E4C}', '\u{004F}'), ('\u{1E4D}', '\u{006F}'),
+    ('\u{1E4E}', '\u{004F}'), ('\u{1E4F}', '\u{006F}'), ('\u{1E50}', '\u{004F}'),
+    ('\u{1E51}', '\u{006F}'), ('\u{1E52}', '\u{004F}'), ('\u{1E53}', '\u{006F}'),
+    ('\u{1E54}', '\u{0050}'), ('\u{1E55}', '\u{0070}'), ('\u{1E56}', '\u{0050}'),
+    ('\u{1E57}', '\u{0070}'), ('\u{1E58}', '\u{0052}'), ('\u{1E59}', '\u{0072}'),
+    ('\u{1E5A}', '\u{0052}'), ('\u{1E5B}', '\u{0072}'), ('\u{1E5C}', '\u{0052}'),
+    ('\u{1E5D}', '\u{0072}'), ('\u{1E5E}', '\u{0052}'), ('\u{1E5F}', '\u{0072}'),
+    ('\u{1E60}', '\u{0053}'), ('\u{1E61}', '\u{0073}'), ('\u{1E62}', '\u{0053}'),
+    ('\u{1E63}', '\u{0073}'), ('\u{1E64}', '\u{0053}'), ('\u{1E65}', '\u{0073}'),
+    ('\u{1E66}', '\u{0053}'), ('\u{1E67}', '\u{0073}'), ('\u{1E68}', '\u{0053}'),
+    ('\u{1E69}', '\u{0073}'), ('\u{1E6A}', '\u{0054}'), ('\u{1E6B}', '\u{0074}'),
+    ('\u{1E6C}', '\u{0054}'), ('\u{1E6D}', '\u{0074}'), ('\u{1E6E}', '\u{0054}'),
+    ('\u{1E6F}', '\u{0074}'), ('\u{1E70}', '\u{0054}'), ('\u{1E71}', '\u{0074}'),
+    ('\u{1E72}', '\u{0055}'), ('\u{1E73}', '\u{0075}'), ('\u{1E74}', '\u{0055}'),
+    ('\u{1E75}', '\u{0075}'), ('\u{1E76}', '\u{0055}'), ('\u{1E77}', '\u{0075}'),
+    ('\u{1E78}', '\u{0055}'), ('\u{1E79}', '\u{0075}'), ('\u{1E7A}', '\u{0055}'),
+    ('\u{1E7B}', '\u{0075}'), ('\u{1E7C}', '\u{0056}'), ('\u{1E7D}', '\u{0076}'),
+    ('\u{1E7E}', '\u{0056}'), ('\u{1E7F}', '\u{0076}'), ('\u{1E80}', '\u{0057}'),
+    ('\u{1E81}', '\u{0077}'), ('\u{1E82}', '\u{0057}'), ('\u{1E83}', '\u{0077}'),
+    ('\u{1E84}', '\u{0057}'), ('\u{1E85}', '\u{0077}'), ('\u{1E86}', '\u{0057}'),
+    ('\u{1E87}', '\u{0077}'), ('\u{1E88}', '\u{0057}'), ('\u{1E89}', '\u{0077}'),
+    ('\u{1E8A}', '\u{0058}'), ('\u{1E8B}', '\u{0078}'), ('\u{1E8C}', '\u{0058}'),
+    ('\u{1E8D}', '\u{0078}'), ('\u{1E8E}', '\u{0059}'), ('\u{1E8F}', '\u{0079}'),
+    ('\u{1E90}', '\u{005A}'), ('\u{1E91}', '\u{007A}'), ('\u{1E92}', '\u{005A}'),
+    ('\u{1E93}', '\u{007A}'), ('\u{1E94}', '\u{005A}'), ('\u{1E95}', '\u{007A}'),
+    ('\u{1E96}', '\u{0068}'), ('\u{1E97}', '\u{0074}'), ('\u{1E98}', '\u{0077}'),
+    ('\u{1E99}', '\u{0079}'), ('\u{1E9B}', '\u{017F}'), ('\u{1EA0}', '\u{0041}'),
+    ('\u{1EA1}', '\u{0061}'), ('\u{1EA2}', '\u{0041}'), ('\u{1EA3}', '\u{0061}'),
+    ('\u{1EA4}', '\u{0041}'), ('\u{1EA5}', '\u{0061}'), ('\u{1EA6}', '\u{0041}'),
+    ('\u{1EA7}', '\u{0061}'), ('\u{1EA8}', '\u{0041}'), ('\u{1EA9}', '\u{0061}'),
+    ('\u{1EAA}', '\u{0041}'), ('\u{1EAB}', '\u{0061}'), ('\u{1EAC}', '\u{0041}'),
+    ('\u{1EAD}', '\u{0061}'), ('\u{1EAE}', '\u{0041}'), ('\u{1EAF}', '\u{0061}'),
+    ('\u{1EB0}', '\u{0041}'), ('\u{1EB1}', '\u{0061}'), ('\u{1EB2}', '\u{0041}'),
+    ('\u{1EB3}', '\u{0061}'), ('\u{1EB4}', '\u{0041}'), ('\u{1EB5}', '\u{0061}'),
+    ('\u{1EB6}', '\u{0041}'), ('\u{1EB7}', '\u{0061}'), ('\u{1EB8}', '\u{0045}'),
+    ('\u{1EB9}', '\u{0065}'), ('\u{1EBA}', '\u{0045}'), ('\u{1EBB}', '\u{0065}'),
+    ('\u{1EBC}', '\u{0045}'), ('\u{1EBD}', '\u{0065}'), ('\u{1EBE}', '\u{0045}'),
+    ('\u{1EBF}', '\u{0065}'), ('\u{1EC0}', '\u{0045}'), ('\u{1EC1}', '\u{0065}'),
+    ('\u{1EC2}', '\u{0045}'), ('\u{1EC3}', '\u{0065}'), ('\u{1EC4}', '\u{0045}'),
+    ('\u{1EC5}', '\u{0065}'), ('\u{1EC6}', '\u{0045}'), ('\u{1EC7}', '\u{0065}'),
+    ('\u{1EC8}', '\u{0049}'), ('\u{1EC9}', '\u{0069}'), ('\u{1ECA}', '\u{0049}'),
+    ('\u{1ECB}', '\u{0069}'), ('\u{1ECC}', '\u{004F}'), ('\u{1ECD}', '\u{006F}'),
+    ('\u{1ECE}', '\u{004F}'), ('\u{1ECF}', '\u{006F}'), ('\u{1ED0}', '\u{004F}'),
+    ('\u{1ED1}', '\u{006F}'), ('\u{1ED2}', '\u{004F}'), ('\u{1ED3}', '\u{006F}'),
+    ('\u{1ED4}', '\u{004F}'), ('\u{1ED5}', '\u{006F}'), ('\u{1ED6}', '\u{004F}'),
+    ('\u{1ED7}', '\u{006F}'), ('\u{1ED8}', '\u{004F}'), ('\u{1ED9}', '\u{006F}'),
+    ('\u{1EDA}', '\u{004F}'), ('\u{1EDB}', '\u{006F}'), ('\u{1EDC}', '\u{004F}'),
+    ('\u{1EDD}', '\u{006F}'), ('\u{1EDE}', '\u{004F}'), ('\u{1EDF}', '\u{006F}'),
+    ('\u{1EE0}', '\u{004F}'), ('\u{1EE1}', '\u{006F}'), ('\u{1EE2}', '\u{004F}'),
+    ('\u{1EE3}', '\u{006F}'), ('\u{1EE4}', '\u{0055}'), ('\u{1EE5}', '\u{0075}'),
+    ('\u{1EE6}', '\u{0055}'), ('\u{1EE7}', '\u{0075}'), ('\u{1EE8}', '\u{0055}'),
+    ('\u{1EE9}', '\u{0075}'), ('\u{1EEA}', '\u{0055}'), ('\u{1EEB}', '\u{0075}'),
+    ('\u{1EEC}', '\u{0055}'), ('\u{1EED}', '\u{0075}'), ('\u{1EEE}', '\u{0055}'),
+    ('\u{1EEF}', '\u{0075}'), ('\u{1EF0}', '\u{0055}'), ('\u{1EF1}', '\u{0075}'),
+    ('\u{1EF2}', '\u{0059}'), ('\u{1EF3}', '\u{0079}'), ('\u{1EF4}', '\u{0059}'),
+    ('\u{1EF5}', '\u{0079}'), ('\u{1EF6}', '\u{0059}'), ('\u{1EF7}', '\u{0079}'),
+    ('\u{1EF8}', '\u{0059}'), ('\u{1EF9}', '\u{0079}'), ('\u{1F00}', '\u{03B1}'),
+    ('\u{1F01}', '\u{03B1}'), ('\u{1F02}', '\u{03B1}'), ('\u{1F03}', '\u{03B1}'),
+    ('\u{1F04}', '\u{03B1}'), ('\u{1F05}', '\u{03B1}'), ('\u{1F06}', '\u{03B1}'),
+    ('\u{1F07}', '\u{03B1}'), ('\u{1F08}', '\u{0391}'), ('\u{1F09}', '\u{0391}'),
+    ('\u{1F0A}', '\u{0391}'), ('\u{1F0B}', '\u{0391}'), ('\u{1F0C}', '\u{0391}'),
+    ('\u{1F0D}', '\u{0391}'), ('\u{1F0E}', '\u{0391}'), ('\u{1F0F}', '\u{0391}'),
+    ('\u{1F10}', '\u{03B5}'), ('\u{1F11}', '\u{03B5}'), ('\u{1F12}', '\u{03B5}'),
+    ('\u{1F13}', '\u{03B5}'), ('\u{1F14}', '\u{03B5}'), ('\u{1F15}', '\u{03B5}'),
+    ('\u{1F18}', '\u{0395}'), ('\u{1F19}', '\u{0395}'), ('\u{1F1A}', '\u{0395}'),
+    ('\u{1F1B}', '\u{0395}'), ('\u{1F1C}', '\u{0395}'), ('\u{1F1D}', '\u{0395}'),
+    ('\u{1F20}', '\u{03B7}'), ('\u{1F21}', '\u{03B7}'), ('\u{1F22}', '\u{03B7}'),
+    ('\u{1F23}', '\u{03B7}'), ('\u{1F24}', '\u{03B7}'), ('\u{1F25}', '\u{03B7}'),
+    ('\u{1F26}', '\u{03B7}'), ('\u{1F27}', '\u{03B7}'), ('\u{1F28}', '\u{0397}'),
+    ('\u{1F29}', '\u{0397}'), ('\u{1F2A}', '\u{0397}'), ('\u{1F2B}', '\u{0397}'),
+    ('\u{1F2C}', '\u{0397}'), ('\u{1F2D}', '\u{0397}'), ('\u{1F2E}', '\u{0397}'),
+    ('\u{1F2F}', '\u{0397}'), ('\u{1F30}', '\u{03B9}'), ('\u{1F31}', '\u{03B9}'),
+    ('\u{1F32}', '\u{03B9}'), ('\u{1F33}', '\u{03B9}'), ('\u{1F34}', '\u{03B9}'),
+    ('\u{1F35}', '\u{03B9}'), ('\u{1F36}', '\u{03B9}'), ('\u{1F37}', '\u{03B9}'),
+    ('\u{1F38}', '\u{0399}'), ('\u{1F39}', '\u{0399}'), ('\u{1F3A}', '\u{0399}'),
+    ('\u{1F3B}', '\u{0399}'), ('\u{1F3C}', '\u{0399}'), ('\u{1F3D}', '\u{0399}'),
+    ('\u{1F3E}', '\u{0399}'), ('\u{1F3F}', '\u{0399}'), ('\u{1F40}', '\u{03BF}'),
+    ('\u{1F41}', '\u{03BF}'), ('\u{1F42}', '\u{03BF}'), ('\u{1F43}', '\u{03BF}'),
+    ('\u{1F44}', '\u{03BF}'), ('\u{1F45}', '\u{03BF}'), ('\u{1F48}', '\u{039F}'),
+    ('\u{1F49}', '\u{039F}'), ('\u{1F4A}', '\u{039F}'), ('\u{1F4B}', '\u{039F}'),
+    ('\u{1F4C}', '\u{039F}'), ('\u{1F4D}', '\u{039F}'), ('\u{1F50}', '\u{03C5}'),
+    ('\u{1F51}', '\u{03C5}'), ('\u{1F52}', '\u{03C5}'), ('\u{1F53}', '\u{03C5}'),
+    ('\u{1F54}', '\u{03C5}'), ('\u{1F55}', '\u{03C5}'), ('\u{1F56}', '\u{03C5}'),
+    ('\u{1F57}', '\u{03C5}'), ('\u{1F59}', '\u{03A5}'), ('\u{1F5B}', '\u{03A5}'),
+    ('\u{1F5D}', '\u{03A5}'), ('\u{1F5F}', '\u{03A5}'), ('\u{1F60}', '\u{03C9}'),
+    ('\u{1F61}', '\u{03C9}'), ('\u{1F62}', '\u{03C9}'), ('\u{1F63}', '\u{03C9}'),
+    ('\u{1F64}', '\u{03C9}'), ('\u{1F65}', '\u{03C9}'), ('\u{1F66}', '\u{03C9}'),
+    ('\u{1F67}', '\u{03C9}'), ('\u{1F68}', '\u{03A9}'), ('\u{1F69}', '\u{03A9}'),
+    ('\u{1F6A}', '\u{03A9}'), ('\u{1F6B}', '\u{03A9}'), ('\u{1F6C}', '\u{03A9}'),
+    ('\u{1F6D}', '\u{03A9}'), ('\u{1F6E}', '\u{03A9}'), ('\u{1F6F}', '\u{03A9}'),
+    ('\u{1F70}', '\u{03B1}'), ('\u{1F71}', '\u{03B1}'), ('\u{1F72}', '\u{03B5}'),
+    ('\u{1F73}', '\u{03B5}'), ('\u{1F74}', '\u{03B7}'), ('\u{1F75}', '\u{03B7}'),
+    ('\u{1F76}', '\u{03B9}'), ('\u{1F77}', '\u{03B9}'), ('\u{1F78}', '\u{03BF}'),
+    ('\u{1F79}', '\u{03BF}'), ('\u{1F7A}', '\u{03C5}'), ('\u{1F7B}', '\u{03C5}'),
+    ('\u{1F7C}', '\u{03C9}'), ('\u{1F7D}', '\u{03C9}'), ('\u{1F80}', '\u{03B1}'),
+    ('\u{1F81}', '\u{03B1}'), ('\u{1F82}', '\u{03B1}'), ('\u{1F83}', '\u{03B1}'),
+    ('\u{1F84}', '\u{03B1}'), ('\u{1F85}', '\u{03B1}'), ('\u{1F86}', '\u{03B1}'),
+    ('\u{1F87}', '\u{03B1}'), ('\u{1F88}', '\u{0391}'), ('\u{1F89}', '\u{0391}'),
+    ('\u{1F8A}', '\u{0391}'), ('\u{1F8B}', '\u{0391}'), ('\u{1F8C}', '\u{0391}'),
+    ('\u{1F8D}', '\u{0391}'), ('\u{1F8E}', '\u{0391}'), ('\u{1F8F}', '\u{0391}'),
+    ('\u{1F90}', '\u{03B7}'), ('\u{1F91}', '\u{03B7}'), ('\u{1F92}', '\u{03B7}'),
+    ('\u{1F93}', '\u{03B7}'), ('\u{1F94}', '\u{03B7}'), ('\u{1F95}', '\u{03B7}'),
+    ('\u{1F96}', '\u{03B7}'), ('\u{1F97}', '\u{03B7}'), ('\u{1F98}', '\u{0397}'),
+    ('\u{1F99}', '\u{0397}'), ('\u{1F9A}', '\u{0397}'), ('\u{1F9B}', '\u{0397}'),
+    ('\u{1F9C}', '\u{0397}'), ('\u{1F9D}', '\u{0397}'), ('\u{1F9E}', '\u{0397}'),
+    ('\u{1F9F}', '\u{0397}'), ('\u{1FA0}', '\u{03C9}'), ('\u{1FA1}', '\u{03C9}'),
+    ('\u{1FA2}', '\u{03C9}'), ('\u{1FA3}', '\u{03C9}'), ('\u{1FA4}', '\u{03C9}'),
+    ('\u{1FA5}', '\u{03C9}'), ('\u{1FA6}', '\u{03C9}'), ('\u{1FA7}', '\u{03C9}'),
+    ('\u{1FA8}', '\u{03A9}'), ('\u{1FA9}', '\u{03A9}'), ('\u{1FAA}', '\u{03A9}'),
+    ('\u{1FAB}', '\u{03A9}'), ('\u{1FAC}', '\u{03A9}'), ('\u{1FAD}', '\u{03A9}'),
+    ('\u{1FAE}', '\u{03A9}'), ('\u{1FAF}', '\u{03A9}'), ('\u{1FB0}', '\u{03B1}'),
+    ('\u{1FB1}', '\u{03B1}'), ('\u{1FB2}', '\u{03B1}'), ('\u{1FB3}', '\u{03B1}'),
+    ('\u{1FB4}', '\u{03B1}'), ('\u{1FB6}', '\u{03B1}'), ('\u{1FB7}', '\u{03B1}'),
+    ('\u{1FB8}', '\u{0391}'), ('\u{1FB9}', '\u{0391}'), ('\u{1FBA}', '\u{0391}'),
+    ('\u{1FBB}', '\u{0391}'), ('\u{1FBC}', '\u{0391}'), ('\u{1FBE}', '\u{03B9}'),
+    ('\u{1FC1}', '\u{00A8}'), ('\u{1FC2}', '\u{03B7}'), ('\u{1FC3}', '\u{03B7}'),
+    ('\u{1FC4}', '\u{03B7}'), ('\u{1FC6}', '\u{03B7}'), ('\u{1FC7}', '\u{03B7}'),
+    ('\u{1FC8}', '\u{0395}'), ('\u{1FC9}', '\u{0395}'), ('\u{1FCA}', '\u{0397}'),
+    ('\u{1FCB}', '\u{0397}'), ('\u{1FCC}', '\u{0397}'), ('\u{1FCD}', '\u{1FBF}'),
+    ('\u{1FCE}', '\u{1FBF}'), ('\u{1FCF}', '\u{1FBF}'), ('\u{1FD0}', '\u{03B9}'),
+    ('\u{1FD1}', '\u{03B9}'), ('\u{1FD2}', '\u{03B9}'), ('\u{1FD3}', '\u{03B9}'),
+    ('\u{1FD6}', '\u{03B9}'), ('\u{1FD7}', '\u{03B9}'), ('\u{1FD8}', '\u{0399}'),
+    ('\u{1FD9}', '\u{0399}'), ('\u{1FDA}', '\u{0399}'), ('\u{1FDB}', '\u{0399}'),
+    ('\u{1FDD}', '\u{1FFE}'), ('\u{1FDE}', '\u{1FFE}'), ('\u{1FDF}', '\u{1FFE}'),
+    ('\u{1FE0}', '\u{03C5}'), ('\u{1FE1}', '\u{03C5}'), ('\u{1FE2}', '\u{03C5}'),
+    ('\u{1FE3}', '\u{03C5}'), ('\u{1FE4}', '\u{03C1}'), ('\u{1FE5}', '\u{03C1}'),
+    ('\u{1FE6}', '\u{03C5}'), ('\u{1FE7}', '\u{03C5}'), ('\u{1FE8}', '\u{03A5}'),
+    ('\u{1FE9}', '\u{03A5}'), ('\u{1FEA}', '\u{03A5}'), ('\u{1FEB}', '\u{03A5}'),
+    ('\u{1FEC}', '\u{03A1}'), ('\u{1FED}', '\u{00A8}'), ('\u{1FEE}', '\u{00A8}'),
+    ('\u{1FEF}', '\u{0060}'), ('\u{1FF2}', '\u{03C9}'), ('\u{1FF3}', '\u{03C9}'),
+    ('\u{1FF4}', '\u{03C9}'), ('\u{1FF6}', '\u{03C9}'), ('\u{1FF7}', '\u{03C9}'),
+    ('\u{1FF8}', '\u{039F}'), ('\u{1FF9}', '\u{039F}'), ('\u{1FFA}', '\u{03A9}'),
+    ('\u{1FFB}', '\u{03A9}'), ('\u{1FFC}', '\u{03A9}'), ('\u{1FFD}', '\u{00B4}'),
+    ('\u{2000}', '\u{2002}'), ('\u{2001}', '\u{2003}'), ('\u{2126}', '\u{03A9}'),
+    ('\u{212A}', '\u{004B}'), ('\u{212B}', '\u{0041}'), ('\u{219A}', '\u{2190}'),
+    ('\u{219B}', '\u{2192}'), ('\u{21AE}', '\u{2194}'), ('\u{21CD}', '\u{21D0}'),
+    ('\u{21CE}', '\u{21D4}'), ('\u{21CF}', '\u{21D2}'), ('\u{2204}', '\u{2203}'),
+    ('\u{2209}', '\u{2208}'), ('\u{220C}', '\u{220B}'), ('\u{2224}', '\u{2223}'),
+    ('\u{2226}', '\u{2225}'), ('\u{2241}', '\u{223C}'), ('\u{2244}', '\u{2243}'),
+    ('\u{2247}', '\u{2245}'), ('\u{2249}', '\u{2248}'), ('\u{2260}', '\u{003D}'),
+    ('\u{2262}', '\u{2261}'), ('\u{226D}', '\u{224D}'), ('\u{226E}', '\u{003C}'),
+    ('\u{226F}', '\u{003E}'), ('\u{2270}', '\u{2264}'), ('\u{2271}', '\u{2265}'),
+    ('\u{2274}', '\u{2272}'), ('\u{2275}', '\u{2273}'), ('\u{2278}', '\u{2276}'),
+    ('\u{2279}', '\u{2277}'), ('\u{2280}', '\u{227A}'), ('\u{2281}', '\u{227B}'),
+    ('\u{2284}', '\u{2282}'), ('\u{2285}', '\u{2283}'), ('\u{2288}', '\u{2286}'),
+    ('\u{2289}', '\u{2287}'), ('\u{22AC}', '\u{22A2}'), ('\u{22AD}', '\u{22A8}'),
+    ('\u{22AE}', '\u{22A9}'), ('\u{22AF}', '\u{22AB}'), ('\u{22E0}', '\u{227C}'),
+    ('\u{22E1}', '\u{227D}'), ('\u{22E2}', '\u{2291}'), ('\u{22E3}', '\u{2292}'),
+    ('\u{22EA}', '\u{22B2}'), ('\u{22EB}', '\u{22B3}'), ('\u{22EC}', '\u{22B4}'),
+    ('\u{22ED}', '\u{22B5}'), ('\u{2329}', '\u{3008}'), ('\u{232A}', '\u{3009}'),
+    ('\u{2ADC}', '\u{2ADD}'), ('\u{F900}', '\u{8C48}'), ('\u{F901}', '\u{66F4}'),
+    ('\u{F902}', '\u{8ECA}'), ('\u{F903}', '\u{8CC8}'), ('\u{F904}', '\u{6ED1}'),
+    ('\u{F905}', '\u{4E32}'), ('\u{F906}', '\u{53E5}'), ('\u{F907}', '\u{9F9C}'),
+    ('\u{F908}', '\u{9F9C}'), ('\u{F909}', '\u{5951}'), ('\u{F90A}', '\u{91D1}'),
+    ('\u{F90B}', '\u{5587}'), ('\u{F90C}', '\u{5948}'), ('\u{F90D}', '\u{61F6}'),
+    ('\u{F90E}', '\u{7669}'), ('\u{F90F}', '\u{7F85}'), ('\u{F910}', '\u{863F}'),
+    ('\u{F911}', '\u{87BA}'), ('\u{F912}', '\u{88F8}'), ('\u{F913}', '\u{908F}'),
+    ('\u{F914}', '\u{6A02}'), ('\u{F915}', '\u{6D1B}'), ('\u{F916}', '\u{70D9}'),
+    ('\u{F917}', '\u{73DE}'), ('\u{F918}', '\u{843D}'), ('\u{F919}', '\u{916A}'),
+    ('\u{F91A}', '\u{99F1}'), ('\u{F91B}', '\u{4E82}'), ('\u{F91C}', '\u{5375}'),
+    ('\u{F91D}', '\u{6B04}'), ('\u{F91E}', '\u{721B}'), ('\u{F91F}', '\u{862D}'),
+    ('\u{F920}', '\u{9E1E}'), ('\u{F921}', '\u{5D50}'), ('\u{F922}', '\u{6FEB}'),
+    ('\u{F923}', '\u{85CD}'), ('\u{F924}', '\u{8964}'), ('\u{F925}', '\u{62C9}'),
+    ('\u{F926}', '\u{81D8}'), ('\u{F927}', '\u{881F}'), ('\u{F928}', '\u{5ECA}'),
+    ('\u{F929}', '\u{6717}'), ('\u{F92A}', '\u{6D6A}'), ('\u{F92B}', '\u{72FC}'),
+    ('\u{F92C}', '\u{90CE}'), ('\u{F92D}', '\u{4F86}'), ('\u{F92E}', '\u{51B7}'),
+    ('\u{F92F}', '\u{52DE}'), ('\u{F930}', '\u{64C4}'), ('\u{F931}', '\u{6AD3}'),
+    ('\u{F932}', '\u{7210}'), ('\u{F933}', '\u{76E7}'), ('\u{F934}', '\u{8001}'),
+    ('\u{F935}', '\u{8606}'), ('\u{F936}', '\u{865C}'), ('\u{F937}', '\u{8DEF}'),
+    ('\u{F938}', '\u{9732}'), ('\u{F939}', '\u{9B6F}'), ('\u{F93A}', '\u{9DFA}'),
+    ('\u{F93B}', '\u{788C}'), ('\u{F93C}', '\u{797F}'), ('\u{F93D}', '\u{7DA0}'),
+    ('\u{F93E}', '\u{83C9}'), ('\u{F93F}', '\u{9304}'), ('\u{F940}', '\u{9E7F}'),
+    ('\u{F941}', '\u{8AD6}'), ('\u{F942}', '\u{58DF}'), ('\u{F943}', '\u{5F04}'),
+    ('\u{F944}', '\u{7C60}'), ('\u{F945}', '\u{807E}'), ('\u{F946}', '\u{7262}'),
+    ('\u{F947}', '\u{78CA}'), ('\u{F948}', '\u{8CC2}'), ('\u{F949}', '\u{96F7}'),
+    ('\u{F94A}', '\u{58D8}'), ('\u{F94B}', '\u{5C62}'), ('\u{F94C}', '\u{6A13}'),
+    ('\u{F94D}', '\u{6DDA}'), ('\u{F94E}', '\u{6F0F}'), ('\u{F94F}', '\u{7D2F}'),
+    ('\u{F950}', '\u{7E37}'), ('\u{F951}', '\u{964B}'), ('\u{F952}', '\u{52D2}'),
+    ('\u{F953}', '\u{808B}'), ('\u{F954}', '\u{51DC}'), ('\u{F955}', '\u{51CC}'),
+    ('\u{F956}', '\u{7A1C}'), ('\u{F957}', '\u{7DBE}'), ('\u{F958}', '\u{83F1}'),
+    ('\u{F959}', '\u{9675}'), ('\u{F95A}', '\u{8B80}'), ('\u{F95B}', '\u{62CF}'),
+    ('\u{F95C}', '\u{6A02}'), ('\u{F95D}', '\u{8AFE}'), ('\u{F95E}', '\u{4E39}'),
+    ('\u{F95F}', '\u{5BE7}'), ('\u{F960}', '\u{6012}'), ('\u{F961}', '\u{7387}'),
+    ('\u{F962}', '\u{7570}'), ('\u{F963}', '\u{5317}'), ('\u{F964}', '\u{78FB}'),
+    ('\u{F965}', '\u{4FBF}'), ('\u{F966}', '\u{5FA9}'), ('\u{F967}', '\u{4E0D}'),
+    ('\u{F968}', '\u{6CCC}'), ('\u{F969}', '\u{6578}'), ('\u{F96A}', '\u{7D22}'),
+    ('\u{F96B}', '\u{53C3}'), ('\u{F96C}', '\u{585E}'), ('\u{F96D}', '\u{7701}'),
+    ('\u{F96E}', '\u{8449}'), ('\u{F96F}', '\u{8AAA}'), ('\u{F970}', '\u{6BBA}'),
+    ('\u{F971}', '\u{8FB0}'), ('\u{F972}', '\u{6C88}'), ('\u{F973}', '\u{62FE}'),
+    ('\u{F974}', '\u{82E5}'), ('\u{F975}', '\u{63A0}'), ('\u{F976}', '\u{7565}'),
+    ('\u{F977}', '\u{4EAE}'), ('\u{F978}', '\u{5169}'), ('\u{F979}', '\u{51C9}'),
+    ('\u{F97A}', '\u{6881}'), ('\u{F97B}', '\u{7CE7}'), ('\u{F97C}', '\u{826F}'),
+    ('\u{F97D}', '\u{8AD2}'), ('\u{F97E}', '\u{91CF}'), ('\u{F97F}', '\u{52F5}'),
+    ('\u{F980}', '\u{5442}'), ('\u{F981}', '\u{5973}'), ('\u{F982}', '\u{5EEC}'),
+    ('\u{F983}', '\u{65C5}'), ('\u{F984}', '\u{6FFE}'), ('\u{F985}', '\u{792A}'),
+    ('\u{F986}', '\u{95AD}'), ('\u{F987}', '\u{9A6A}'), ('\u{F988}', '\u{9E97}'),
+    ('\u{F989}', '\u{9ECE}'), ('\u{F98A}', '\u{529B}'), ('\u{F98B}', '\u{66C6}'),
+    ('\u{F98C}', '\u{6B77}'), ('\u{F98D}', '\u{8F62}'), ('\u{F98E}', '\u{5E74}'),
+    ('\u{F98F}', '\u{6190}'), ('\u{F990}', '\u{6200}'), ('\u{F991}', '\u{649A}'),
+    ('\u{F992}', '\u{6F23}'), ('\u{F993}', '\u{7149}'), ('\u{F994}', '\u{7489}'),
+    ('\u{F995}', '\u{79CA}'), ('\u{F996}', '\u{7DF4}'), ('\u{F997}', '\u{806F}'),
+    ('\u{F998}', '\u{8F26}'), ('\u{F999}', '\u{84EE}'), ('\u{F99A}', '\u{9023}'),
+    ('\u{F99B}', '\u{934A}'), ('\u{F99C}', '\u{5217}'), ('\u{F99D}', '\u{52A3}'),
+    ('\u{F99E}', '\u{54BD}'), ('\u{F99F}', '\u{70C8}'), ('\u{F9A0}', '\u{88C2}'),
+    ('\u{F9A1}', '\u{8AAA}'), ('\u{F9A2}', '\u{5EC9}'), ('\u{F9A3}', '\u{5FF5}'),
+    ('\u{F9A4}', '\u{637B}'), ('\u{F9A5}', '\u{6BAE}'), ('\u{F9A6}', '\u{7C3E}'),
+    ('\u{F9A7}', '\u{7375}'), ('\u{F9A8}', '\u{4EE4}'), ('\u{F9A9}', '\u{56F9}'),
+    ('\u{F9AA}', '\u{5BE7}'), ('\u{F9AB}', '\u{5DBA}'), ('\u{F9AC}', '\u{601C}'),
+    ('\u{F9AD}', '\u{73B2}'), ('\u{F9AE}', '\u{7469}'), ('\u{F9AF}', '\u{7F9A}'),
+    ('\u{F9B0}', '\u{8046}'), ('\u{F9B1}', '\u{9234}'), ('\u{F9B2}', '\u{96F6}'),
+    ('\u{F9B3}', '\u{9748}'), ('\u{F9B4}', '\u{9818}'), ('\u{F9B5}', '\u{4F8B}'),
+    ('\u{F9B6}', '\u{79AE}'), ('\u{F9B7}', '\u{91B4}'), ('\u{F9B8}', '\u{96B8}'),
+    ('\u{F9B9}', '\u{60E1}'), ('\u{F9BA}', '\u{4E86}'), ('\u{F9BB}', '\u{50DA}'),
+    ('\u{F9BC}', '\u{5BEE}'), ('\u{F9BD}', '\u{5C3F}'), ('\u{F9BE}', '\u{6599}'),
+    ('\u{F9BF}', '\u{6A02}'), ('\u{F9C0}', '\u{71CE}'), ('\u{F9C1}', '\u{7642}'),
+    ('\u{F9C2}', '\u{84FC}'), ('\u{F9C3}', '\u{907C}'), ('\u{F9C4}', '\u{9F8D}'),
+    ('\u{F9C5}', '\u{6688}'), ('\u{F9C6}', '\u{962E}'), ('\u{F9C7}', '\u{5289}'),
+    ('\u{F9C8}', '\u{677B}'), ('\u{F9C9}', '\u{67F3}'), ('\u{F9CA}', '\u{6D41}'),
+    ('\u{F9CB}', '\u{6E9C}'), ('\u{F9CC}', '\u{7409}'), ('\u{F9CD}', '\u{7559}'),
+    ('\u{F9CE}', '\u{786B}'), ('\u{F9CF}', '\u{7D10}'), ('\u{F9D0}', '\u{985E}'),
+    ('\u{F9D1}', '\u{516D}'), ('\u{F9D2}', '\u{622E}'), ('\u{F9D3}', '\u{9678}'),
+    ('\u{F9D4}', '\u{502B}'), ('\u{F9D5}', '\u{5D19}'), ('\u{F9D6}', '\u{6DEA}'),
+    ('\u{F9D7}', '\u{8F2A}'), ('\u{F9D8}', '\u{5F8B}'), ('\u{F9D9}', '\u{6144}'),
+    ('\u{F9DA}', '\u{6817}'), ('\u{F9DB}', '\u{7387}'), ('\u{F9DC}', '\u{9686}'),
+    ('\u{F9DD}', '\u{5229}'), ('\u{F9DE}', '\u{540F}'), ('\u{F9DF}', '\u{5C65}'),
+    ('\u{F9E0}', '\u{6613}'), ('\u{F9E1}', '\u{674E}'), ('\u{F9E2}', '\u{68A8}'),
+    ('\u{F9E3}', '\u{6CE5}'), ('\u{F9E4}', '\u{7406}'), ('\u{F9E5}', '\u{75E2}'),
+    ('\u{F9E6}', '\u{7F79}'), ('\u{F9E7}', '\u{88CF}'), ('\u{F9E8}', '\u{88E1}'),
+    ('\u{F9E9}', '\u{91CC}'), ('\u{F9EA}', '\u{96E2}'), ('\u{F9EB}', '\u{533F}'),
+    ('\u{F9EC}', '\u{6EBA}'), ('\u{F9ED}', '\u{541D}'), ('\u{F9EE}', '\u{71D0}'),
+    ('\u{F9EF}', '\u{7498}'), ('\u{F9F0}', '\u{85FA}'), ('\u{F9F1}', '\u{96A3}'),
+    ('\u{F9F2}', '\u{9C57}'), ('\u{F9F3}', '\u{9E9F}'), ('\u{F9F4}', '\u{6797}'),
+    ('\u{F9F5}', '\u{6DCB}'), ('\u{F9F6}', '\u{81E8}'), ('\u{F9F7}', '\u{7ACB}'),
+    ('\u{F9F8}', '\u{7B20}'), ('\u{F9F9}', '\u{7C92}'), ('\u{F9FA}', '\u{72C0}'),
+    ('\u{F9FB}', '\u{7099}'), ('\u{F9FC}', '\u{8B58}'), ('\u{F9FD}', '\u{4EC0}'),
+    ('\u{F9FE}', '\u{8336}'), ('\u{F9FF}', '\u{523A}'), ('\u{FA00}', '\u{5207}'),
+    ('\u{FA01}', '\u{5EA6}'), ('\u{FA02}', '\u{62D3}'), ('\u{FA03}', '\u{7CD6}'),
+    ('\u{FA04}', '\u{5B85}'), ('\u{FA05}', '\u{6D1E}'), ('\u{FA06}', '\u{66B4}'),
+    ('\u{FA07}', '\u{8F3B}'), ('\u{FA08}', '\u{884C}'), ('\u{FA09}', '\u{964D}'),
+    ('\u{FA0A}', '\u{898B}'), ('\u{FA0B}', '\u{5ED3}'), ('\u{FA0C}', '\u{5140}'),
+    ('\u{FA0D}', '\u{55C0}'), ('\u{FA10}', '\u{585A}'), ('\u{FA12}', '\u{6674}'),
+    ('\u{FA15}', '\u{51DE}'), ('\u{FA16}', '\u{732A}'), ('\u{FA17}', '\u{76CA}'),
+    ('\u{FA18}', '\u{793C}'), ('\u{FA19}', '\u{795E}'), ('\u{FA1A}', '\u{7965}'),
+    ('\u{FA1B}', '\u{798F}'), ('\u{FA1C}', '\u{9756}'), ('\u{FA1D}', '\u{7CBE}'),
+    ('\u{FA1E}', '\u{7FBD}'), ('\u{FA20}', '\u{8612}'), ('\u{FA22}', '\u{8AF8}'),
+    ('\u{FA25}', '\u{9038}'), ('\u{FA26}', '\u{90FD}'), ('\u{FA2A}', '\u{98EF}'),
+    ('\u{FA2B}', '\u{98FC}'), ('\u{FA2C}', '\u{9928}'), ('\u{FA2D}', '\u{9DB4}'),
+    ('\u{FA2E}', '\u{90DE}'), ('\u{FA2F}', '\u{96B7}'), ('\u{FA30}', '\u{4FAE}'),
+    ('\u{FA31}', '\u{50E7}'), ('\u{FA32}', '\u{514D}'), ('\u{FA33}', '\u{52C9}'),
+    ('\u{FA34}', '\u{52E4}'), ('\u{FA35}', '\u{5351}'), ('\u{FA36}', '\u{559D}'),
+    ('\u{FA37}', '\u{5606}'), ('\u{FA38}', '\u{5668}'), ('\u{FA39}', '\u{5840}'),
+    ('\u{FA3A}', '\u{58A8}'), ('\u{FA3B}', '\u{5C64}'), ('\u{FA3C}', '\u{5C6E}'),
+    ('\u{FA3D}', '\u{6094}'), ('\u{FA3E}', '\u{6168}'), ('\u{FA3F}', '\u{618E}'),
+    ('\u{FA40}', '\u{61F2}'), ('\u{FA41}', '\u{654F}'), ('\u{FA42}', '\u{65E2}'),
+    ('\u{FA43}', '\u{6691}'), ('\u{FA44}', '\u{6885}'), ('\u{FA45}', '\u{6D77}'),
+    ('\u{FA46}', '\u{6E1A}'), ('\u{FA47}', '\u{6F22}'), ('\u{FA48}', '\u{716E}'),
+    ('\u{FA49}', '\u{722B}'), ('\u{FA4A}', '\u{7422}'), ('\u{FA4B}', '\u{7891}'),
+    ('\u{FA4C}', '\u{793E}'), ('\u{FA4D}', '\u{7949}'), ('\u{FA4E}', '\u{7948}'),
+    ('\u{FA4F}', '\u{7950}'), ('\u{FA50}', '\u{7956}'), ('\u{FA51}', '\u{795D}'),
+    ('\u{FA52}', '\u{798D}'), ('\u{FA53}', '\u{798E}'), ('\u{FA54}', '\u{7A40}'),
+    ('\u{FA55}', '\u{7A81}'), ('\u{FA56}', '\u{7BC0}'), ('\u{FA57}', '\u{7DF4}'),
+    ('\u{FA58}', '\u{7E09}'), ('\u{FA59}', '\u{7E41}'), ('\u{FA5A}', '\u{7F72}'),
+    ('\u{FA5B}', '\u{8005}'), ('\u{FA5C}', '\u{81ED}'), ('\u{FA5D}', '\u{8279}'),
+    ('\u{FA5E}', '\u{8279}'), ('\u{FA5F}', '\u{8457}'), ('\u{FA60}', '\u{8910}'),
+    ('\u{FA61}', '\u{8996}'), ('\u{FA62}', '\u{8B01}'), ('\u{FA63}', '\u{8B39}'),
+    ('\u{FA64}', '\u{8CD3}'), ('\u{FA65}', '\u{8D08}'), ('\u{FA66}', '\u{8FB6}'),
+    ('\u{FA67}', '\u{9038}'), ('\u{FA68}', '\u{96E3}'), ('\u{FA69}', '\u{97FF}'),
+    ('\u{FA6A}', '\u{983B}'), ('\u{FA6B}', '\u{6075}'), ('\u{FA6C}', '\u{242EE}'),
+    ('\u{FA6D}', '\u{8218}'), ('\u{FA70}', '\u{4E26}'), ('\u{FA71}', '\u{51B5}'),
+    ('\u{FA72}', '\u{5168}'), ('\u{FA73}', '\u{4F80}'), ('\u{FA74}', '\u{5145}'),
+    ('\u{FA75}', '\u{5180}'), ('\u{FA76}', '\u{52C7}'), ('\u{FA77}', '\u{52FA}'),
+    ('\u{FA78}', '\u{559D}'), ('\u{FA79}', '\u{5555}'), ('\u{FA7A}', '\u{5599}'),
+    ('\u{FA7B}', '\u{55E2}'), ('\u{FA7C}', '\u{585A}'), ('\u{FA7D}', '\u{58B3}'),
+    ('\u{FA7E}', '\u{5944}'), ('\u{FA7F}', '\u{5954}'), ('\u{FA80}', '\u{5A62}'),
+    ('\u{FA81}', '\u{5B28}'), ('\u{FA82}', '\u{5ED2}'), ('\u{FA83}', '\u{5ED9}'),
+    ('\u{FA84}', '\u{5F69}'), ('\u{FA85}', '\u{5FAD}'), ('\u{FA86}', '\u{60D8}'),
+    ('\u{FA87}', '\u{614E}'), ('\u{FA88}', '\u{6108}'), ('\u{FA89}', '\u{618E}'),
+    ('\u{FA8A}', '\u{6160}'), ('\u{FA8B}', '\u{61F2}'), ('\u{FA8C}', '\u{6234}'),
+    ('\u{FA8D}', '\u{63C4}'), ('\u{FA8E}', '\u{641C}'), ('\u{FA8F}', '\u{6452}'),
+    ('\u{FA90}', '\u{6556}'), ('\u{FA91}', '\u{6674}'), ('\u{FA92}', '\u{6717}'),
+    ('\u{FA93}', '\u{671B}'), ('\u{FA94}', '\u{6756}'), ('\u{FA95}', '\u{6B79}'),
+    ('\u{FA96}', '\u{6BBA}'), ('\u{FA97}', '\u{6D41}'), ('\u{FA98}', '\u{6EDB}'),
+    ('\u{FA99}', '\u{6ECB}'), ('\u{FA9A}', '\u{6F22}'), ('\u{FA9B}', '\u{701E}'),
+    ('\u{FA9C}', '\u{716E}'), ('\u{FA9D}', '\u{77A7}'), ('\u{FA9E}', '\u{7235}'),
+    ('\u{FA9F}', '\u{72AF}'), ('\u{FAA0}', '\u{732A}'), ('\u{FAA1}', '\u{7471}'),
+    ('\u{FAA2}', '\u{7506}'), ('\u{FAA3}', '\u{753B}'), ('\u{FAA4}', '\u{761D}'),
+    ('\u{FAA5}', '\u{761F}'), ('\u{FAA6}', '\u{76CA}'), ('\u{FAA7}', '\u{76DB}'),
+    ('\u{FAA8}', '\u{76F4}'), ('\u{FAA9}', '\u{774A}'), ('\u{FAAA}', '\u{7740}'),
+    ('\u{FAAB}', '\u{78CC}'), ('\u{FAAC}', '\u{7AB1}'), ('\u{FAAD}', '\u{7BC0}'),
+    ('\u{FAAE}', '\u{7C7B}'), ('\u{FAAF}', '\u{7D5B}'), ('\u{FAB0}', '\u{7DF4}'),
+    ('\u{FAB1}', '\u{7F3E}'), ('\u{FAB2}', '\u{8005}'), ('\u{FAB3}', '\u{8352}'),
+    ('\u{FAB4}', '\u{83EF}'), ('\u{FAB5}', '\u{8779}'), ('\u{FAB6}', '\u{8941}'),
+    ('\u{FAB7}', '\u{8986}'), ('\u{FAB8}', '\u{8996}'), ('\u{FAB9}', '\u{8ABF}'),
+    ('\u{FABA}', '\u{8AF8}'), ('\u{FABB}', '\u{8ACB}'), ('\u{FABC}', '\u{8B01}'),
+    ('\u{FABD}', '\u{8AFE}'), ('\u{FABE}', '\u{8AED}'), ('\u{FABF}', '\u{8B39}'),
+    ('\u{FAC0}', '\u{8B8A}'), ('\u{FAC1}', '\u{8D08}'), ('\u{FAC2}', '\u{8F38}'),
+    ('\u{FAC3}', '\u{9072}'), ('\u{FAC4}', '\u{9199}'), ('\u{FAC5}', '\u{9276}'),
+    ('\u{FAC6}', '\u{967C}'), ('\u{FAC7}', '\u{96E3}'), ('\u{FAC8}', '\u{9756}'),
+    ('\u{FAC9}', '\u{97DB}'), ('\u{FACA}', '\u{97FF}'), ('\u{FACB}', '\u{980B}'),
+    ('\u{FACC}', '\u{983B}'), ('\u{FACD}', '\u{9B12}'), ('\u{FACE}', '\u{9F9C}'),
+    ('\u{FACF}', '\u{2284A}'), ('\u{FAD0}', '\u{22844}'), ('\u{FAD1}', '\u{233D5}'),
+    ('\u{FAD2}', '\u{3B9D}'), ('\u{FAD3}', '\u{4018}'), ('\u{FAD4}', '\u{4039}'),
+    ('\u{FAD5}', '\u{25249}'), ('\u{FAD6}', '\u{25CD0}'), ('\u{FAD7}', '\u{27ED3}'),
+    ('\u{FAD8}', '\u{9F43}'), ('\u{FAD9}', '\u{9F8E}'), ('\u{2F800}', '\u{4E3D}'),
+    ('\u{2F801}', '\u{4E38}'), ('\u{2F802}', '\u{4E41}'), ('\u{2F803}', '\u{20122}'),
+    ('\u{2F804}', '\u{4F60}'), ('\u{2F805}', '\u{4FAE}'), ('\u{2F806}', '\u{4FBB}'),
+    ('\u{2F807}', '\u{5002}'), ('\u{2F808}', '\u{507A}'), ('\u{2F809}', '\u{5099}'),
+    ('\u{2F80A}', '\u{50E7}'), ('\u{2F80B}', '\u{50CF}'), ('\u{2F80C}', '\u{349E}'),
+    ('\u{2F80D}', '\u{2063A}'), ('\u{2F80E}', '\u{514D}'), ('\u{2F80F}', '\u{5154}'),
+    ('\u{2F810}', '\u{5164}'), ('\u{2F811}', '\u{5177}'), ('\u{2F812}', '\u{2051C}'),
+    ('\u{2F813}', '\u{34B9}'), ('\u{2F814}', '\u{5167}'), ('\u{2F815}', '\u{518D}'),
+    ('\u{2F816}', '\u{2054B}'), ('\u{2F817}', '\u{5197}'), ('\u{2F818}', '\u{51A4}'),
+    ('\u{2F819}', '\u{4ECC}'), ('\u{2F81A}', '\u{51AC}'), ('\u{2F81B}', '\u{51B5}'),
+    ('\u{2F81C}', '\u{291DF}'), ('\u{2F81D}', '\u{51F5}'), ('\u{2F81E}', '\u{5203}'),
+    ('\u{2F81F}', '\u{34DF}'), ('\u{2F820}', '\u{523B}'), ('\u{2F821}', '\u{5246}'),
+    ('\u{2F822}', '\u{5272}'), ('\u{2F823}', '\u{5277}'), ('\u{2F824}', '\u{3515}'),
+    ('\u{2F825}', '\u{52C7}'), ('\u{2F826}', '\u{52C9}'), ('\u{2F827}', '\u{52E4}'),
+    ('\u{2F828}', '\u{52FA}'), ('\u{2F829}', '\u{5305}'), ('\u{2F82A}', '\u{5306}'),
+    ('\u{2F82B}', '\u{5317}'), ('\u{2F82C}', '\u{5349}'), ('\u{2F82D}', '\u{5351}'),
+    ('\u{2F82E}', '\u{535A}'), ('\u{2F82F}', '\u{5373}'), ('\u{2F830}', '\u{537D}'),
+    ('\u{2F831}', '\u{537F}'), ('\u{2F832}', '\u{537F}'), ('\u{2F833}', '\u{537F}'),
+    ('\u{2F834}', '\u{20A2C}'), ('\u{2F835}', '\u{7070}'), ('\u{2F836}', '\u{53CA}'),
+    ('\u{2F837}', '\u{53DF}'), ('\u{2F838}', '\u{20B63}'), ('\u{2F839}', '\u{53EB}'),
+    ('\u{2F83A}', '\u{53F1}'), ('\u{2F83B}', '\u{5406}'), ('\u{2F83C}', '\u{549E}'),
+    ('\u{2F83D}', '\u{5438}'), ('\u{2F83E}', '\u{5448}'), ('\u{2F83F}', '\u{5468}'),
+    ('\u{2F840}', '\u{54A2}'), ('\u{2F841}', '\u{54F6}'), ('\u{2F842}', '\u{5510}'),
+    ('\u{2F843}', '\u{5553}'), ('\u{2F844}', '\u{5563}'), ('\u{2F845}', '\u{5584}'),
+    ('\u{2F846}', '\u{5584}'), ('\u{2F847}', '\u{5599}'), ('\u{2F848}', '\u{55AB}'),
+    ('\u{2F849}', '\u{55B3}'), ('\u{2F84A}', '\u{55C2}'), ('\u{2F84B}', '\u{5716}'),
+    ('\u{2F84C}', '\u{5606}'), ('\u{2F84D}', '\u{5717}'), ('\u{2F84E}', '\u{5651}'),
+    ('\u{2F84F}', '\u{5674}'), ('\u{2F850}', '\u{5207}'), ('\u{2F851}', '\u{58EE}'),
+    ('\u{2F852}', '\u{57CE}'), ('\u{2F853}', '\u{57F4}'), ('\u{2F854}', '\u{580D}'),
+    ('\u{2F855}', '\u{578B}'), ('\u{2F856}', '\u{5832}'), ('\u{2F857}', '\u{5831}'),
+    ('\u{2F858}', '\u{58AC}'), ('\u{2F859}', '\u{214E4}'), ('\u{2F85A}', '\u{58F2}'),
+    ('\u{2F85B}', '\u{58F7}'), ('\u{2F85C}', '\u{5906}'), ('\u{2F85D}', '\u{591A}'),
+    ('\u{2F85E}', '\u{5922}'), ('\u{2F85F}', '\u{5962}'), ('\u{2F860}', '\u{216A8}'),
+    ('\u{2F861}', '\u{216EA}'), ('\u{2F862}', '\u{59EC}'), ('\u{2F863}', '\u{5A1B}'),
+    ('\u{2F864}', '\u{5A27}'), ('\u{2F865}', '\u{59D8}'), ('\u{2F866}', '\u{5A66}'),
+    ('\u{2F867}', '\u{36EE}'), ('\u{2F868}', '\u{36FC}'), ('\u{2F869}', '\u{5B08}'),
+    ('\u{2F86A}', '\u{5B3E}'), ('\u{2F86B}', '\u{5B3E}'), ('\u{2F86C}', '\u{219C8}'),
+    ('\u{2F86D}', '\u{5BC3}'), ('\u{2F86E}', '\u{5BD8}'), ('\u{2F86F}', '\u{5BE7}'),
+    ('\u{2F870}', '\u{5BF3}'), ('\u{2F871}', '\u{21B18}'), ('\u{2F872}', '\u{5BFF}'),
+    ('\u{2F873}', '\u{5C06}'), ('\u{2F874}', '\u{5F53}'), ('\u{2F875}', '\u{5C22}'),
+    ('\u{2F876}', '\u{3781}'), ('\u{2F877}', '\u{5C60}'), ('\u{2F878}', '\u{5C6E}'),
+    ('\u{2F879}', '\u{5CC0}'), ('\u{2F87A}', '\u{5C8D}'), ('\u{2F87B}', '\u{21DE4}'),
+    ('\u{2F87C}', '\u{5D43}'), ('\u{2F87D}', '\u{21DE6}'), ('\u{2F87E}', '\u{5D6E}'),
+    ('\u{2F87F}', '\u{5D6B}'), ('\u{2F880}', '\u{5D7C}'), ('\u{2F881}', '\u{5DE1}'),
+    ('\u{2F882}', '\u{5DE2}'), ('\u{2F883}', '\u{382F}'), ('\u{2F884}', '\u{5DFD}'),
+    ('\u{2F885}', '\u{5E28}'), ('\u{2F886}', '\u{5E3D}'), ('\u{2F887}', '\u{5E69}'),
+    ('\u{2F888}', '\u{3862}'), ('\u{2F889}', '\u{22183}'), ('\u{2F88A}', '\u{387C}'),
+    ('\u{2F88B}', '\u{5EB0}'), ('\u{2F88C}', '\u{5EB3}'), ('\u{2F88D}', '\u{5EB6}'),
+    ('\u{2F88E}', '\u{5ECA}'), ('\u{2F88F}', '\u{2A392}'), ('\u{2F890}', '\u{5EFE}'),
+    ('\u{2F891}', '\u{22331}'), ('\u{2F892}', '\u{22331}'), ('\u{2F893}', '\u{8201}'),
+    ('\u{2F894}', '\u{5F22}'), ('\u{2F895}', '\u{5F22}'), ('\u{2F896}', '\u{38C7}'),
+    ('\u{2F897}', '\u{232B8}'), ('\u{2F898}', '\u{261DA}'), ('\u{2F899}', '\u{5F62}'),
+    ('\u{2F89A}', '\u{5F6B}'), ('\u{2F89B}', '\u{38E3}'), ('\u{2F89C}', '\u{5F9A}'),
+    ('\u{2F89D}', '\u{5FCD}'), ('\u{2F89E}', '\u{5FD7}'), ('\u{2F89F}', '\u{5FF9}'),
+    ('\u{2F8A0}', '\u{6081}'), ('\u{2F8A1}', '\u{393A}'), ('\u{2F8A2}', '\u{391C}'),
+    ('\u{2F8A3}', '\u{6094}'), ('\u{2F8A4}', '\u{226D4}'), ('\u{2F8A5}', '\u{60C7}'),
+    ('\u{2F8A6}', '\u{6148}'), ('\u{2F8A7}', '\u{614C}'), ('\u{2F8A8}', '\u{614E}'),
+    ('\u{2F8A9}', '\u{614C}'), ('\u{2F8AA}', '\u{617A}'), ('\u{2F8AB}', '\u{618E}'),
+    ('\u{2F8AC}', '\u{61B2}'), ('\u{2F8AD}', '\u{61A4}'), ('\u{2F8AE}', '\u{61AF}'),
+    ('\u{2F8AF}', '\u{61DE}'), ('\u{2F8B0}', '\u{61F2}'), ('\u{2F8B1}', '\u{61F6}'),
+    ('\u{2F8B2}', '\u{6210}'), ('\u{2F8B3}', '\u{621B}'), ('\u{2F8B4}', '\u{625D}'),
+    ('\u{2F8B5}', '\u{62B1}'), ('\u{2F8B6}', '\u{62D4}'), ('\u{2F8B7}', '\u{6350}'),
+    ('\u{2F8B8}', '\u{22B0C}'), ('\u{2F8B9}', '\u{633D}'), ('\u{2F8BA}', '\u{62FC}'),
+    ('\u{2F8BB}', '\u{6368}'), ('\u{2F8BC}', '\u{6383}'), ('\u{2F8BD}', '\u{63E4}'),
+    ('\u{2F8BE}', '\u{22BF1}'), ('\u{2F8BF}', '\u{6422}'), ('\u{2F8C0}', '\u{63C5}'),
+    ('\u{2F8C1}', '\u{63A9}'), ('\u{2F8C2}', '\u{3A2E}'), ('\u{2F8C3}', '\u{6469}'),
+    ('\u{2F8C4}', '\u{647E}'), ('\u{2F8C5}', '\u{649D}'), ('\u{2F8C6}', '\u{6477}'),
+    ('\u{2F8C7}', '\u{3A6C}'), ('\u{2F8C8}', '\u{654F}'), ('\u{2F8C9}', '\u{656C}'),
+    ('\u{2F8CA}', '\u{2300A}'), ('\u{2F8CB}', '\u{65E3}'), ('\u{2F8CC}', '\u{66F8}'),
+    ('\u{2F8CD}', '\u{6649}'), ('\u{2F8CE}', '\u{3B19}'), ('\u{2F8CF}', '\u{6691}'),
+    ('\u{2F8D0}', '\u{3B08}'), ('\u{2F8D1}', '\u{3AE4}'), ('\u{2F8D2}', '\u{5192}'),
+    ('\u{2F8D3}', '\u{5195}'), ('\u{2F8D4}', '\u{6700}'), ('\u{2F8D5}', '\u{669C}'),
+    ('\u{2F8D6}', '\u{80AD}'), ('\u{2F8D7}', '\u{43D9}'), ('\u{2F8D8}', '\u{6717}'),
+    ('\u{2F8D9}', '\u{671B}'), ('\u{2F8DA}', '\u{6721}'), ('\u{2F8DB}', '\u{675E}'),
+    ('\u{2F8DC}', '\u{6753}'), ('\u{2F8DD}', '\u{233C3}'), ('\u{2F8DE}', '\u{3B49}'),
+    ('\u{2F8DF}', '\u{67FA}'), ('\u{2F8E0}', '\u{6785}'), ('\u{2F8E1}', '\u{6852}'),
+    ('\u{2F8E2}', '\u{6885}'), ('\u{2F8E3}', '\u{2346D}'), ('\u{2F8E4}', '\u{688E}'),
+    ('\u{2F8E5}', '\u{681F}'), ('\u{2F8E6}', '\u{6914}'), ('\u{2F8E7}', '\u{3B9D}'),
+    ('\u{2F8E8}', '\u{6942}'), ('\u{2F8E9}', '\u{69A3}'), ('\u{2F8EA}', '\u{69EA}'),
+    ('\u{2F8EB}', '\u{6AA8}'), ('\u{2F8EC}', '\u{236A3}'), ('\u{2F8ED}', '\u{6ADB}'),
+    ('\u{2F8EE}', '\u{3C18}'), ('\u{2F8EF}', '\u{6B21}'), ('\u{2F8F0}', '\u{238A7}'),
+    ('\u{2F8F1}', '\u{6B54}'), ('\u{2F8F2}', '\u{3C4E}'), ('\u{2F8F3}', '\u{6B72}'),
+    ('\u{2F8F4}', '\u{6B9F}'), ('\u{2F8F5}', '\u{6BBA}'), ('\u{2F8F6}', '\u{6BBB}'),
+    ('\u{2F8F7}', '\u{23A8D}'), ('\u{2F8F8}', '\u{21D0B}'), ('\u{2F8F9}', '\u{23AFA}'),
+    ('\u{2F8FA}', '\u{6C4E}'), ('\u{2F8FB}', '\u{23CBC}'), ('\u{2F8FC}', '\u{6CBF}'),
+    ('\u{2F8FD}', '\u{6CCD}'), ('\u{2F8FE}', '\u{6C67}'), ('\u{2F8FF}', '\u{6D16}'),
+    ('\u{2F900}', '\u{6D3E}'), ('\u{2F901}', '\u{6D77}'), ('\u{2F902}', '\u{6D41}'),
+    ('\u{2F903}', '\u{6D69}'), ('\u{2F904}', '\u{6D78}'), ('\u{2F905}', '\u{6D85}'),
+    ('\u{2F906}', '\u{23D1E}'), ('\u{2F907}', '\u{6D34}'), ('\u{2F908}', '\u{6E2F}'),
+    ('\u{2F909}', '\u{6E6E}'), ('\u{2F90A}', '\u{3D33}'), ('\u{2F90B}', '\u{6ECB}'),
+    ('\u{2F90C}', '\u{6EC7}'), ('\u{2F90D}', '\u{23ED1}'), ('\u{2F90E}', '\u{6DF9}'),
+    ('\u{2F90F}', '\u{6F6E}'), ('\u{2F910}', '\u{23F5E}'), ('\u{2F911}', '\u{23F8E}'),
+    ('\u{2F912}', '\u{6FC6}'), ('\u{2F913}', '\u{7039}'), ('\u{2F914}', '\u{701E}'),
+    ('\u{2F915}', '\u{701B}'), ('\u{2F916}', '\u{3D96}'), ('\u{2F917}', '\u{704A}'),
+    ('\u{2F918}', '\u{707D}'), ('\u{2F919}', '\u{7077}'), ('\u{2F91A}', '\u{70AD}'),
+    ('\u{2F91B}', '\u{20525}'), ('\u{2F91C}', '\u{7145}'), ('\u{2F91D}', '\u{24263}'),
+    ('\u{2F91E}', '\u{719C}'), ('\u{2F91F}', '\u{243AB}'), ('\u{2F920}', '\u{7228}'),
+    ('\u{2F921}', '\u{7235}'), ('\u{2F922}', '\u{7250}'), ('\u{2F923}', '\u{24608}'),
+    ('\u{2F924}', '\u{7280}'), ('\u{2F925}', '\u{7295}'), ('\u{2F926}', '\u{24735}'),
+    ('\u{2F927}', '\u{24814}'), ('\u{2F928}', '\u{737A}'), ('\u{2F929}', '\u{738B}'),
+    ('\u{2F92A}', '\u{3EAC}'), ('\u{2F92B}', '\u{73A5}'), ('\u{2F92C}', '\u{3EB8}'),
+    ('\u{2F92D}', '\u{3EB8}'), ('\u{2F92E}', '\u{7447}'), ('\u{2F92F}', '\u{745C}'),
+    ('\u{2F930}', '\u{7471}'), ('\u{2F931}', '\u{7485}'), ('\u{2F932}', '\u{74CA}'),
+    ('\u{2F933}', '\u{3F1B}'), ('\u{2F934}', '\u{7524}'), ('\u{2F935}', '\u{24C36}'),
+    ('\u{2F936}', '\u{753E}'), ('\u{2F937}', '\u{24C92}'), ('\u{2F938}', '\u{7570}'),
+    ('\u{2F939}', '\u{2219F}'), ('\u{2F93A}', '\u{7610}'), ('\u{2F93B}', '\u{24FA1}'),
+    ('\u{2F93C}', '\u{24FB8}'), ('\u{2F93D}', '\u{25044}'), ('\u{2F93E}', '\u{3FFC}'),
+    ('\u{2F93F}', '\u{4008}'), ('\u{2F940}', '\u{76F4}'), ('\u{2F941}', '\u{250F3}'),
+    ('\u{2F942}', '\u{250F2}'), ('\u{2F943}', '\u{25119}'), ('\u{2F944}', '\u{25133}'),
+    ('\u{2F945}', '\u{771E}'), ('\u{2F946}', '\u{771F}'), ('\u{2F947}', '\u{771F}'),
+    ('\u{2F948}', '\u{774A}'), ('\u{2F949}', '\u{4039}'), ('\u{2F94A}', '\u{778B}'),
+    ('\u{2F94B}', '\u{4046}'), ('\u{2F94C}', '\u{4096}'), ('\u{2F94D}', '\u{2541D}'),
+    ('\u{2F94E}', '\u{784E}'), ('\u{2F94F}', '\u{788C}'), ('\u{2F950}', '\u{78CC}'),
+    ('\u{2F951}', '\u{40E3}'), ('\u{2F952}', '\u{25626}'), ('\u{2F953}', '\u{7956}'),
+    ('\u{2F954}', '\u{2569A}'), ('\u{2F955}', '\u{256C5}'), ('\u{2F956}', '\u{798F}'),
+    ('\u{2F957}', '\u{79EB}'), ('\u{2F958}', '\u{412F}'), ('\u{2F959}', '\u{7A40}'),
+    ('\u{2F95A}', '\u{7A4A}'), ('\u{2F95B}', '\u{7A4F}'), ('\u{2F95C}', '\u{2597C}'),
+    ('\u{2F95D}', '\u{25AA7}'), ('\u{2F95E}', '\u{25AA7}'), ('\u{2F95F}', '\u{7AEE}'),
+    ('\u{2F960}', '\u{4202}'), ('\u{2F961}', '\u{25BAB}'), ('\u{2F962}', '\u{7BC6}'),
+    ('\u{2F963}', '\u{7BC9}'), ('\u{2F964}', '\u{4227}'), ('\u{2F965}', '\u{25C80}'),
+    ('\u{2F966}', '\u{7CD2}'), ('\u{2F967}', '\u{42A0}'), ('\u{2F968}', '\u{7CE8}'),
+    ('\u{2F969}', '\u{7CE3}'), ('\u{2F96A}', '\u{7D00}'), ('\u{2F96B}', '\u{25F86}'),
+    ('\u{2F96C}', '\u{7D63}'), ('\u{2F96D}', '\u{4301}'), ('\u{2F96E}', '\u{7DC7}'),
+    ('\u{2F96F}', '\u{7E02}'), ('\u{2F970}', '\u{7E45}'), ('\u{2F971}', '\u{4334}'),
+    ('\u{2F972}', '\u{26228}'), ('\u{2F973}', '\u{26247}'), ('\u{2F974}', '\u{4359}'),
+    ('\u{2F975}', '\u{262D9}'), ('\u{2F976}', '\u{7F7A}'), ('\u{2F977}', '\u{2633E}'),
+    ('\u{2F978}', '\u{7F95}'), ('\u{2F979}', '\u{7FFA}'), ('\u{2F97A}', '\u{8005}'),
+    ('\u{2F97B}', '\u{264DA}'), ('\u{2F97C}', '\u{26523}'), ('\u{2F97D}', '\u{8060}'),
+    ('\u{2F97E}', '\u{265A8}'), ('\u{2F97F}', '\u{8070}'), ('\u{2F980}', '\u{2335F}'),
+    ('\u{2F981}', '\u{43D5}'), ('\u{2F982}', '\u{80B2}'), ('\u{2F983}', '\u{8103}'),
+    ('\u{2F984}', '\u{440B}'), ('\u{2F985}', '\u{813E}'), ('\u{2F986}', '\u{5AB5}'),
+    ('\u{2F987}', '\u{267A7}'), ('\u{2F988}', '\u{267B5}'), ('\u{2F989}', '\u{23393}'),
+    ('\u{2F98A}', '\u{2339C}'), ('\u{2F98B}', '\u{8201}'), ('\u{2F98C}', '\u{8204}'),
+    ('\u{2F98D}', '\u{8F9E}'), ('\u{2F98E}', '\u{446B}'), ('\u{2F98F}', '\u{8291}'),
+    ('\u{2F990}', '\u{828B}'), ('\u{2F991}', '\u{829D}'), ('\u{2F992}', '\u{52B3}'),
+    ('\u{2F993}', '\u{82B1}'), ('\u{2F994}', '\u{82B3}'), ('\u{2F995}', '\u{82BD}'),
+    ('\u{2F996}', '\u{82E6}'), ('\u{2F997}', '\u{26B3C}'), ('\u{2F998}', '\u{82E5}'),
+    ('\u{2F999}', '\u{831D}'), ('\u{2F99A}', '\u{8363}'), ('\u{2F99B}', '\u{83AD}'),
+    ('\u{2F99C}', '\u{8323}'), ('\u{2F99D}', '\u{83BD}'), ('\u{2F99E}', '\u{83E7}'),
+    ('\u{2F99F}', '\u{8457}'), ('\u{2F9A0}', '\u{8353}'), ('\u{2F9A1}', '\u{83CA}'),
+    ('\u{2F9A2}', '\u{83CC}'), ('\u{2F9A3}', '\u{83DC}'), ('\u{2F9A4}', '\u{26C36}'),
+    ('\u{2F9A5}', '\u{26D6B}'), ('\u{2F9A6}', '\u{26CD5}'), ('\u{2F9A7}', '\u{452B}'),
+    ('\u{2F9A8}', '\u{84F1}'), ('\u{2F9A9}', '\u{84F3}'), ('\u{2F9AA}', '\u{8516}'),
+    ('\u{2F9AB}', '\u{273CA}'), ('\u{2F9AC}', '\u{8564}'), ('\u{2F9AD}', '\u{26F2C}'),
+    ('\u{2F9AE}', '\u{455D}'), ('\u{2F9AF}', '\u{4561}'), ('\u{2F9B0}', '\u{26FB1}'),
+    ('\u{2F9B1}', '\u{270D2}'), ('\u{2F9B2}', '\u{456B}'), ('\u{2F9B3}', '\u{8650}'),
+    ('\u{2F9B4}', '\u{865C}'), ('\u{2F9B5}', '\u{8667}'), ('\u{2F9B6}', '\u{8669}'),
+    ('\u{2F9B7}', '\u{86A9}'), ('\u{2F9B8}', '\u{8688}'), ('\u{2F9B9}', '\u{870E}'),
+    ('\u{2F9BA}', '\u{86E2}'), ('\u{2F9BB}', '\u{8779}'), ('\u{2F9BC}', '\u{8728}'),
+    ('\u{2F9BD}', '\u{876B}'), ('\u{2F9BE}', '\u{8786}'), ('\u{2F9BF}', '\u{45D7}'),
+    ('\u{2F9C0}', '\u{87E1}'), ('\u{2F9C1}', '\u{8801}'), ('\u{2F9C2}', '\u{45F9}'),
+    ('\u{2F9C3}', '\u{8860}'), ('\u{2F9C4}', '\u{8863}'), ('\u{2F9C5}', '\u{27667}'),
+    ('\u{2F9C6}', '\u{88D7}'), ('\u{2F9C7}', '\u{88DE}'), ('\u{2F9C8}', '\u{4635}'),
+    ('\u{2F9C9}', '\u{88FA}'), ('\u{2F9CA}', '\u{34BB}'), ('\u{2F9CB}', '\u{278AE}'),
+    ('\u{2F9CC}', '\u{27966}'), ('\u{2F9CD}', '\u{46BE}'), ('\u{2F9CE}', '\u{46C7}'),
+    ('\u{2F9CF}', '\u{8AA0}'), ('\u{2F9D0}', '\u{8AED}'), ('\u{2F9D1}', '\u{8B8A}'),
+    ('\u{2F9D2}', '\u{8C55}'), ('\u{2F9D3}', '\u{27CA8}'), ('\u{2F9D4}', '\u{8CAB}'),
+    ('\u{2F9D5}', '\u{8CC1}'), ('\u{2F9D6}', '\u{8D1B}'), ('\u{2F9D7}', '\u{8D77}'),
+    ('\u{2F9D8}', '\u{27F2F}'), ('\u{2F9D9}', '\u{20804}'), ('\u{2F9DA}', '\u{8DCB}'),
+    ('\u{2F9DB}', '\u{8DBC}'), ('\u{2F9DC}', '\u{8DF0}'), ('\u{2F9DD}', '\u{208DE}'),
+    ('\u{2F9DE}', '\u{8ED4}'), ('\u{2F9DF}', '\u{8F38}'), ('\u{2F9E0}', '\u{285D2}'),
+    ('\u{2F9E1}', '\u{285ED}'), ('\u{2F9E2}', '\u{9094}'), ('\u{2F9E3}', '\u{90F1}'),
+    ('\u{2F9E4}', '\u{9111}'), ('\u{2F9E5}', '\u{2872E}'), ('\u{2F9E6}', '\u{911B}'),
+    ('\u{2F9E7}', '\u{9238}'), ('\u{2F9E8}', '\u{92D7}'), ('\u{2F9E9}', '\u{92D8}'),
+    ('\u{2F9EA}', '\u{927C}'), ('\u{2F9EB}', '\u{93F9}'), ('\u{2F9EC}', '\u{9415}'),
+    ('\u{2F9ED}', '\u{28BFA}'), ('\u{2F9EE}', '\u{958B}'), ('\u{2F9EF}', '\u{4995}'),
+    ('\u{2F9F0}', '\u{95B7}'), ('\u{2F9F1}', '\u{28D77}'), ('\u{2F9F2}', '\u{49E6}'),
+    ('\u{2F9F3}', '\u{96C3}'), ('\u{2F9F4}', '\u{5DB2}'), ('\u{2F9F5}', '\u{9723}'),
+    ('\u{2F9F6}', '\u{29145}'), ('\u{2F9F7}', '\u{2921A}'), ('\u{2F9F8}', '\u{4A6E}'),
+    ('\u{2F9F9}', '\u{4A76}'), ('\u{2F9FA}', '\u{97E0}'), ('\u{2F9FB}', '\u{2940A}'),
+    ('\u{2F9FC}', '\u{4AB2}'), ('\u{2F9FD}', '\u{29496}'), ('\u{2F9FE}', '\u{980B}'),
+    ('\u{2F9FF}', '\u{980B}'), ('\u{2FA00}', '\u{9829}'), ('\u{2FA01}', '\u{295B6}'),
+    ('\u{2FA02}', '\u{98E2}'), ('\u{2FA03}', '\u{4B33}'), ('\u{2FA04}', '\u{9929}'),
+    ('\u{2FA05}', '\u{99A7}'), ('\u{2FA06}', '\u{99C2}'), ('\u{2FA07}', '\u{99FE}'),
+    ('\u{2FA08}', '\u{4BCE}'), ('\u{2FA09}', '\u{29B30}'), ('\u{2FA0A}', '\u{9B12}'),
+    ('\u{2FA0B}', '\u{9C40}'), ('\u{2FA0C}', '\u{9CFD}'), ('\u{2FA0D}', '\u{4CCE}'),
+    ('\u{2FA0E}', '\u{4CED}'), ('\u{2FA0F}', '\u{9D67}'), ('\u{2FA10}', '\u{2A0CE}'),
+    ('\u{2FA11}', '\u{4CF8}'), ('\u{2FA12}', '\u{2A105}'), ('\u{2FA13}', '\u{2A20E}'),
+    ('\u{2FA14}', '\u{2A291}'), ('\u{2FA15}', '\u{9EBB}'), ('\u{2FA16}', '\u{4D56}'),
+    ('\u{2FA17}', '\u{9EF9}'), ('\u{2FA18}', '\u{9EFE}'), ('\u{2FA19}', '\u{9F05}'),
+    ('\u{2FA1A}', '\u{9F0F}'), ('\u{2FA1B}', '\u{9F16}'), ('\u{2FA1C}', '\u{9F3B}'),
+    ('\u{2FA1D}', '\u{2A600}'),
+];
+
+/// The base character of a precomposed character, or the character itself if it has no
+/// canonical decomposition into a base character and diacritical marks.
+pub(crate) fn base_char(char: char) -> char {
+    match DECOMPOSITIONS.binary_search_by_key(&char, |&(composed, _)| composed) {
+        Ok(index) => DECOMPOSITIONS[index].1,
+        Err(_) => char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_char() {
+        assert!(DECOMPOSITIONS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(base_char('é'), 'e');
+        assert_eq!(base_char('\u{1EC7}'), 'e');
+        assert_eq!(base_char('\u{212B}'), 'A');
+        assert_eq!(base_char('ø'), 'ø');
+        assert_eq!(base_char('x'), 'x');
+    }
+}
@@ -0,0 +1,686 @@
+use std::borrow::Cow;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use super::catalog::sync_dir;
+use super::codec::{Reader, crc32, put_row, put_u32, put_u64};
+use super::database::Database;
+use super::error::{Error, Result};
+use super::operations::{insert_all, load_rows, remove_rows, replace_rows};
+use super::row::Row;
+use super::schema::Schema;
+use super::types::Value;
+
+/// The first bytes of a write-ahead log, followed by the length and CRC-32 of the snapshot
+/// the log applies to.
+const MAGIC: &[u8; 8] = b"TCDBWAL1";
+const HEADER_LEN: usize = 20;
+
+const COMMIT: u8 = 0;
+const INSERT: u8 = 1;
+const DELETE: u8 = 2;
+const UPDATE: u8 = 3;
+
+/// A change to the rows of a database, as written to the log.
+///
+/// Deletes and updates record the positions of the rows they changed rather than their
+/// predicate, so replaying them does not depend on code that may have changed since.
+pub(crate) enum Record<'a> {
+    Insert(Cow<'a, [Row]>),
+    Delete(Cow<'a, [usize]>),
+    Update(Cow<'a, [usize]>, Cow<'a, [Row]>),
+}
+
+impl Record<'_> {
+    /// Append the record as a frame: the payload length and CRC-32, then the payload.
+    fn write(&self, bytes: &mut Vec<u8>) {
+        let mut payload = Vec::new();
+        match self {
+            Record::Insert(rows) => {
+                payload.push(INSERT);
+                put_u32(&mut payload, rows.len() as u32);
+                for row in rows.iter() {
+                    put_row(&mut payload, row);
+                }
+            }
+            Record::Delete(positions) => {
+                payload.push(DELETE);
+                put_u32(&mut payload, positions.len() as u32);
+                for &position in positions.iter() {
+                    put_u64(&mut payload, position as u64);
+                }
+            }
+            Record::Update(positions, rows) => {
+                payload.push(UPDATE);
+                put_u32(&mut payload, positions.len() as u32);
+                for (&position, row) in positions.iter().zip(rows.iter()) {
+                    put_u64(&mut payload, position as u64);
+                    put_row(&mut payload, row);
+                }
+            }
+        }
+        frame(bytes, &payload);
+    }
+
+    fn read(payload: &[u8]) -> Option<Record<'static>> {
+        let mut reader = Reader::new(payload);
+        let kind = reader.u8()?;
+        let count = reader.u32()?;
+        let mut positions = Vec::new();
+        let mut rows = Vec::new();
+
+        for _ in 0..count {
+            if kind != INSERT {
+                positions.push(usize::try_from(reader.u64()?).ok()?);
+            }
+            if kind != DELETE {
+                rows.push(reader.row()?);
+            }
+        }
+
+        let record = match kind {
+            INSERT => Record::Insert(Cow::Owned(rows)),
+            DELETE => Record::Delete(Cow::Owned(positions)),
+            UPDATE => Record::Update(Cow::Owned(positions), Cow::Owned(rows)),
+            _ => return None,
+        };
+        reader.is_empty().then_some(record)
+    }
+
+    /// Apply a replayed record to the database.
+    fn apply(self, db: &mut Database) -> Result<()> {
+        let corrupt = || Error::Corrupt("write-ahead log refers to a missing row".to_string());
+        let valid = |positions: &[usize], length: usize| {
+            positions.windows(2).all(|pair| pair[0] < pair[1])
+                && positions.last().is_none_or(|&last| last < length)
+        };
+
+        match self {
+            Record::Insert(rows) => insert_all(db, rows.into_owned()).map(drop),
+            Record::Delete(positions) => {
+                if !valid(&positions, db.rows.len()) {
+                    return Err(corrupt());
+                }
+                remove_rows(db, &positions);
+                Ok(())
+            }
+            Record::Update(positions, rows) => {
+                if !valid(&positions, db.rows.len()) {
+                    return Err(corrupt());
+                }
+                for &position in positions.iter() {
+                    for index in &mut db.unique {
+                        index.remove(&db.rows[position]);
+                    }
+                }
+                replace_rows(db, &positions, rows.into_owned());
+                Ok(())
+            }
+        }
+    }
+}
+
+fn frame(bytes: &mut Vec<u8>, payload: &[u8]) {
+    put_u32(bytes, payload.len() as u32);
+    put_u32(bytes, crc32(payload));
+    bytes.extend_from_slice(payload);
+}
+
+/// Where the changes of a database are recorded before they are applied.
+pub(crate) enum Log {
+    /// The write-ahead log of a database opened from a file.
+    File(LogFile),
+    /// Records of a transaction, written to the log of its database as one batch when it
+    /// commits.
+    Buffer(Vec<u8>),
+}
+
+pub(crate) struct LogFile {
+    /// The path of the snapshot; the log is next to it with a `.wal` suffix.
+    path: PathBuf,
+    file: File,
+    /// Set when a failed append could not be cut off again. Later batches would follow the
+    /// partial one and be dropped by the next replay, so the log refuses them until a
+    /// checkpoint starts a new one.
+    poisoned: bool,
+}
+
+impl LogFile {
+    /// Append records, framed and followed by a commit marker, and wait until they are on
+    /// disk.
+    ///
+    /// If writing or syncing fails, the log is truncated back to its length before the
+    /// append, so nothing of the batch remains.
+    pub(crate) fn commit(&mut self, records: &[u8]) -> Result<()> {
+        if self.poisoned {
+            return Err(Error::Io(io::Error::other(
+                "the write-ahead log is unusable after a failed write",
+            )));
+        }
+
+        let mut batch = records.to_vec();
+        frame(&mut batch, &[COMMIT]);
+        let length = self.file.stream_position()?;
+        let written = (self.file.write_all(&batch)).and_then(|()| self.file.sync_data());
+
+        if let Err(error) = written {
+            let truncated = (self.file.set_len(length))
+                .and_then(|()| self.file.seek(SeekFrom::Start(length)))
+                .and_then(|_| self.file.sync_data());
+            self.poisoned = truncated.is_err();
+            return Err(error.into());
+        }
+        Ok(())
+    }
+}
+
+fn wal_path(path: &Path) -> PathBuf {
+    let mut wal = path.as_os_str().to_owned();
+    wal.push(".wal");
+    PathBuf::from(wal)
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    PathBuf::from(temporary)
+}
+
+/// Write a file through a temporary file renamed over it, so it is never half-written, and
+/// sync its directory so the rename survives a crash.
+fn replace_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temporary = temporary_path(path);
+    let mut file = File::create(&temporary)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)?;
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => sync_dir(parent),
+        _ => sync_dir(Path::new(".")),
+    }
+}
+
+fn header(snapshot: &[u8]) -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    put_u64(&mut header, snapshot.len() as u64);
+    put_u32(&mut header, crc32(snapshot));
+    header
+}
+
+/// Replay the committed batches of a log onto the database.
+///
+/// # Returns
+/// The length of the log up to the end of the last complete batch; anything after it was
+/// torn by a crash and is ignored.
+fn replay(db: &mut Database, log: &[u8]) -> Result<usize> {
+    let mut reader = Reader::new(log);
+    reader.position = HEADER_LEN;
+    let mut end = HEADER_LEN;
+    let mut batch = Vec::new();
+
+    while let Some(length) = reader.u32()
+        && let Some(checksum) = reader.u32()
+        && let Some(payload) = reader.bytes(length as usize)
+        && crc32(payload) == checksum
+    {
+        if payload == [COMMIT] {
+            for record in batch.drain(..) {
+                Record::apply(record, db)?;
+            }
+            end = reader.position;
+            continue;
+        }
+        let record = Record::read(payload)
+            .ok_or_else(|| Error::Corrupt("invalid write-ahead log record".to_string()))?;
+        batch.push(record);
+    }
+
+    Ok(end)
+}
+
+/// Move the rows of a snapshot into a database with another schema, matching columns by
+/// name. Values are checked against the columns but keep their nulls, as in the snapshot.
+fn conform(snapshot: Database, schema: &Schema) -> Result<Database> {
+    for column in &snapshot.schema.columns {
+        schema.column_index(&column.name)?;
+    }
+    let positions = (schema.columns.iter())
+        .map(|column| snapshot.schema.column_index(&column.name))
+        .collect::<Result<Vec<_>>>()?;
+    let mut db = Database::new(schema.clone());
+    let rows = (snapshot.rows.into_iter())
+        .map(|mut row| {
+            let values = (positions.iter().zip(&db.schema.columns)).map(|(&i, column)| {
+                column.validate(mem::replace(&mut row.values[i], Value::Null), db.coercion)
+            });
+            Ok(Row::new(values.collect::<Result<_>>()?))
+        })
+        .collect::<Result<_>>()?;
+
+    load_rows(&mut db, rows)?;
+    Ok(db)
+}
+
+impl Database {
+    /// Create a database stored in a file, with the given schema and no rows.
+    ///
+    /// The rows are kept in memory and saved as a snapshot in the file, in the binary format
+    /// of `Database::to_bytes`. Every change made by `insert`, `delete`, `update`, `execute` or a
+    /// committed transaction is first appended to a write-ahead log next to it, `<path>.wal`,
+    /// and flushed to disk, so it survives a crash. `Database::checkpoint` folds the log
+    /// into the snapshot.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if the file already exists or cannot be written.
+    pub fn create(path: impl AsRef<Path>, schema: Schema) -> Result<Database> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            )));
+        }
+
+        let mut db = Database::new(schema);
+        db.attach(path.to_path_buf(), None)?;
+        Ok(db)
+    }
+
+    /// Open a database created with `Database::create`, replaying its write-ahead log.
+    ///
+    /// Changes whose log records were cut short by a crash are dropped, so the database
+    /// holds exactly the changes that completed. The schema is read from the snapshot with
+    /// every constraint but CHECK; use `Database::open_with_schema` to restore those.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if a file cannot be read or written, the errors of
+    /// `Database::from_bytes` if the snapshot is invalid and `Error::Corrupt` if the log
+    /// holds an invalid record.
+    pub fn open(path: impl AsRef<Path>) -> Result<Database> {
+        Database::open_file(path.as_ref(), None)
+    }
+
+    /// Open a database like `Database::open`, with the columns and constraints of a schema.
+    ///
+    /// The columns of the snapshot are matched to those of the schema by name, and its rows
+    /// are checked against the constraints of the schema. Null values are kept as they are,
+    /// without column defaults.
+    ///
+    /// # Errors
+    /// Returns the errors of `Database::open`, `Error::UnknownColumn` if a column is in only
+    /// one of the snapshot and the schema, and the errors of `insert` if a row does not fit
+    /// the schema.
+    pub fn open_with_schema(path: impl AsRef<Path>, schema: &Schema) -> Result<Database> {
+        Database::open_file(path.as_ref(), Some(schema))
+    }
+
+    fn open_file(path: &Path, schema: Option<&Schema>) -> Result<Database> {
+        let snapshot = fs::read(path)?;
+        let mut db = Database::from_bytes(&snapshot)?;
+
+        let log = match fs::read(wal_path(path)) {
+            Ok(log) => Some(log),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
+        // A log written for another snapshot was left behind by a checkpoint interrupted
+        // after the new snapshot was in place, so its changes are already in it.
+        let log =
+            log.filter(|log| log.len() >= HEADER_LEN && log[..HEADER_LEN] == header(&snapshot)[..]);
+        let mut end = match &log {
+            Some(log) => Some(replay(&mut db, log)?),
+            None => None,
+        };
+        if let Some(schema) = schema {
+            // Logged rows follow the columns of the snapshot, so another schema starts a new
+            // snapshot.
+            if db.schema != *schema {
+                end = None;
+            }
+            db = conform(db, schema)?;
+        }
+
+        db.attach(path.to_path_buf(), end)?;
+        Ok(db)
+    }
+
+    /// Start logging to the files at a path: keep the first `end` bytes of the existing
+    /// log, or write a new snapshot and an empty log.
+    fn attach(&mut self, path: PathBuf, end: Option<usize>) -> Result<()> {
+        let mut file = match end {
+            Some(end) => {
+                let file = OpenOptions::new().write(true).open(wal_path(&path))?;
+                file.set_len(end as u64)?;
+                file
+            }
+            None => {
+                let snapshot = self.to_bytes();
+                replace_file(&path, &snapshot)?;
+                replace_file(&wal_path(&path), &header(&snapshot))?;
+                OpenOptions::new().write(true).open(wal_path(&path))?
+            }
+        };
+        file.sync_all()?;
+        file.seek(SeekFrom::End(0))?;
+        self.log = Some(Log::File(LogFile {
+            path,
+            file,
+            poisoned: false,
+        }));
+        Ok(())
+    }
+
+    /// Write all rows to the snapshot file and empty the write-ahead log.
+    ///
+    /// The new snapshot replaces the old one atomically, so a crash during a checkpoint
+    /// leaves either the old snapshot and its log or the new snapshot.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if the database was not opened from a file or a file cannot be
+    /// written.
+    pub fn checkpoint(&mut self) -> Result<()> {
+        let Some(Log::File(log)) = &self.log else {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the database is not stored in a file",
+            )));
+        };
+        let path = log.path.clone();
+        self.attach(path, None)
+    }
+
+    /// Record a change about to be applied: append it to the write-ahead log, or to the
+    /// records of the running transaction.
+    pub(crate) fn log(&mut self, record: Record) -> Result<()> {
+        match &mut self.log {
+            None => Ok(()),
+            Some(Log::Buffer(records)) => {
+                record.write(records);
+                Ok(())
+            }
+            Some(Log::File(log)) => {
+                let mut records = Vec::new();
+                record.write(&mut records);
+                log.commit(&records)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tinycsv_db::fixtures::TempDir;
+    use crate::tinycsv_db::{
+        Check, Column, DataType, Filter, Value, delete, execute, find_exact, insert, update_where,
+    };
+
+    fn user_schema() -> Schema {
+        Schema::from_columns(vec![
+            Column::new("id", DataType::Integer).primary_key(),
+            Column::new("name", DataType::Text),
+            Column::new("score", DataType::Float).nullable(),
+        ])
+    }
+
+    fn user(id: i64, name: &str, score: f64) -> Row {
+        Row::new(vec![
+            Value::Integer(id),
+            Value::Text(name.to_string()),
+            Value::Float(score),
+        ])
+    }
+
+    /// Build a database through every kind of change, without a checkpoint.
+    fn changed(path: &Path) -> Database {
+        let mut db = Database::create(path, user_schema()).unwrap();
+        insert(&mut db, user(1, "Alice", 0.1)).unwrap();
+        insert(&mut db, user(2, "Bob, \"Jr\"\n", 80.0)).unwrap();
+        insert(&mut db, user(3, "Carol", 70.0)).unwrap();
+        update_where(
+            &mut db,
+            &Filter::eq("id", Value::Integer(2)),
+            &[("score", Value::Null)],
+        )
+        .unwrap();
+        delete(&mut db, |row| row.values[0] == Value::Integer(1)).unwrap();
+
+        let mut transaction = db.begin();
+        transaction.insert(user(4, "Dave", 1.0 / 3.0));
+        transaction.update_where(
+            Filter::eq("name", Value::Text("Carol".to_string())),
+            &[("id", Value::Integer(5))],
+        );
+        transaction.commit().unwrap();
+        db
+    }
+
+    #[test]
+    fn test_reopen_replays_the_log() {
+        let dir = TempDir::new("wal_replay");
+        let path = dir.0.join("users.db");
+        let db = changed(&path);
+        let rows = db.get_rows().clone();
+        drop(db);
+
+        let mut reopened = Database::open_with_schema(&path, &user_schema()).unwrap();
+        assert_eq!(reopened.get_rows(), &rows);
+        // The unique index was rebuilt by the replay.
+        assert!(matches!(
+            insert(&mut reopened, user(5, "Eve", 0.0)),
+            Err(Error::UniqueViolation { .. })
+        ));
+
+        reopened.checkpoint().unwrap();
+        assert_eq!(fs::read(wal_path(&path)).unwrap().len(), HEADER_LEN);
+        execute(&mut reopened, "DELETE FROM users WHERE id = 4").unwrap();
+        drop(reopened);
+
+        let mut reopened = Database::open(&path).unwrap();
+        assert_eq!(reopened.get_rows(), &rows[..2]);
+        // The snapshot keeps the constraints of the schema.
+        assert_eq!(reopened.get_schema(), &user_schema());
+        assert!(matches!(
+            insert(&mut reopened, user(2, "Eve", 0.0)),
+            Err(Error::UniqueViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_open_with_schema_matches_columns_by_name() {
+        let dir = TempDir::new("wal_schema");
+        let path = dir.0.join("users.db");
+        drop(changed(&path));
+
+        let mut columns = user_schema().get_columns().to_vec();
+        columns.reverse();
+        let reversed = Schema::from_columns(columns.clone());
+        let mut db = Database::open_with_schema(&path, &reversed).unwrap();
+        assert_eq!(db.get_rows()[0].values[2], Value::Integer(2));
+        let row = Row::new(vec![
+            Value::Null,
+            Value::Text("Eve".to_string()),
+            Value::Integer(6),
+        ]);
+        insert(&mut db, row.clone()).unwrap();
+        drop(db);
+        let db = Database::open(&path).unwrap();
+        assert_eq!(db.get_schema(), &reversed);
+        assert_eq!(db.get_rows()[3], row);
+
+        columns[0] = Column::new("score", DataType::Float)
+            .nullable()
+            .check(Check::new(
+                "below_one",
+                |value| matches!(value, Value::Float(num) if *num < 1.0),
+            ));
+        let checked = Schema::from_columns(columns.clone());
+        assert!(matches!(
+            Database::open_with_schema(&path, &checked),
+            Err(Error::CheckViolation { .. })
+        ));
+
+        // A default added by the schema does not replace stored nulls.
+        columns[0] = Column::new("score", DataType::Float)
+            .nullable()
+            .default(Value::Float(0.0));
+        let defaulted = Schema::from_columns(columns.clone());
+        let db = Database::open_with_schema(&path, &defaulted).unwrap();
+        assert_eq!(db.get_rows()[0].values[0], Value::Null);
+
+        columns.pop();
+        let missing = Schema::from_columns(columns);
+        assert!(matches!(
+            Database::open_with_schema(&path, &missing),
+            Err(Error::UnknownColumn(name)) if name == "id"
+        ));
+    }
+
+    #[test]
+    fn test_recovery_from_truncated_log() {
+        let dir = TempDir::new("wal_truncation");
+        let path = dir.0.join("users.db");
+        drop(changed(&path));
+        let log = fs::read(wal_path(&path)).unwrap();
+
+        // Replaying every prefix of the log gives the state after some whole batch.
+        let mut states = Vec::new();
+        for end in HEADER_LEN..=log.len() {
+            fs::write(wal_path(&path), &log[..end]).unwrap();
+            let db = Database::open_with_schema(&path, &user_schema()).unwrap();
+            let rows = db.get_rows().clone();
+            if states.last() != Some(&rows) {
+                states.push(rows);
+            }
+            drop(db);
+            // The torn tail was cut off, so new batches follow a complete one.
+            assert!(fs::read(wal_path(&path)).unwrap().len() <= end);
+        }
+
+        let ids = |rows: &Vec<Row>| rows.iter().map(|row| row.values[0].clone()).collect();
+        let states: Vec<Vec<Value>> = states.iter().map(ids).collect();
+        let ints = |ids: &[i64]| ids.iter().map(|&id| Value::Integer(id)).collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![
+                ints(&[]),
+                ints(&[1]),
+                ints(&[1, 2]),
+                ints(&[1, 2, 3]),
+                ints(&[1, 2, 3]),
+                ints(&[2, 3]),
+                ints(&[2, 5, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_after_recovery_and_interrupted_checkpoint() {
+        let dir = TempDir::new("wal_checkpoint");
+        let path = dir.0.join("users.db");
+        drop(changed(&path));
+
+        // A crash tore the last batch.
+        let log = fs::read(wal_path(&path)).unwrap();
+        fs::write(wal_path(&path), &log[..log.len() - 3]).unwrap();
+        let mut db = Database::open_with_schema(&path, &user_schema()).unwrap();
+        insert(&mut db, user(9, "Zed", 9.0)).unwrap();
+        let rows = db.get_rows().clone();
+        drop(db);
+
+        let db = Database::open_with_schema(&path, &user_schema()).unwrap();
+        assert_eq!(db.get_rows(), &rows);
+        drop(db);
+
+        // A checkpoint interrupted after writing the snapshot leaves the old log behind.
+        let log = fs::read(wal_path(&path)).unwrap();
+        let mut db = Database::open_with_schema(&path, &user_schema()).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+        fs::write(wal_path(&path), &log).unwrap();
+
+        let db = Database::open_with_schema(&path, &user_schema()).unwrap();
+        assert_eq!(db.get_rows(), &rows);
+    }
+
+    #[test]
+    fn test_failed_changes_are_not_logged() {
+        let dir = TempDir::new("wal_failures");
+        let path = dir.0.join("users.db");
+        let mut db = Database::create(&path, user_schema()).unwrap();
+        insert(&mut db, user(1, "Alice", 1.0)).unwrap();
+        let length = fs::read(wal_path(&path)).unwrap().len();
+
+        assert!(insert(&mut db, user(1, "Bob", 2.0)).is_err());
+        let mut transaction = db.begin();
+        transaction
+            .insert(user(2, "Bob", 2.0))
+            .insert(user(2, "Carol", 3.0));
+        assert!(transaction.commit().is_err());
+        assert_eq!(fs::read(wal_path(&path)).unwrap().len(), length);
+
+        assert!(matches!(
+            Database::create(&path, user_schema()),
+            Err(Error::Io(error)) if error.kind() == io::ErrorKind::AlreadyExists
+        ));
+        assert!(matches!(
+            Database::new(user_schema()).checkpoint(),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_failed_log_write_poisons_the_log() {
+        let dir = TempDir::new("wal_poison");
+        let path = dir.0.join("users.db");
+        let mut db = Database::create(&path, user_schema()).unwrap();
+        insert(&mut db, user(1, "Alice", 1.0)).unwrap();
+        let log = fs::read(wal_path(&path)).unwrap();
+        let rows = db.get_rows().clone();
+
+        // A read-only handle fails both the append and the truncation after it.
+        let Some(Log::File(file)) = &mut db.log else {
+            unreachable!();
+        };
+        file.file = File::open(wal_path(&path)).unwrap();
+
+        assert!(matches!(
+            insert(&mut db, user(2, "Bob", 2.0)),
+            Err(Error::Io(_))
+        ));
+
+        // Even with a working handle again, the log refuses further batches.
+        let Some(Log::File(file)) = &mut db.log else {
+            unreachable!();
+        };
+        file.file = OpenOptions::new()
+            .append(true)
+            .open(wal_path(&path))
+            .unwrap();
+        assert!(matches!(delete(&mut db, |_| true), Err(Error::Io(_))));
+        let mut transaction = db.begin();
+        transaction.insert(user(3, "Carol", 3.0));
+        assert!(transaction.commit().is_err());
+        assert_eq!(db.get_rows(), &rows);
+        assert!(
+            find_exact(&db, "id", &Value::Integer(3))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(fs::read(wal_path(&path)).unwrap(), log);
+
+        // A checkpoint starts a new log.
+        db.checkpoint().unwrap();
+        insert(&mut db, user(3, "Carol", 3.0)).unwrap();
+        drop(db);
+        assert_eq!(
+            Database::open_with_schema(&path, &user_schema())
+                .unwrap()
+                .get_rows()
+                .len(),
+            2
+        );
+    }
+}